@@ -1,9 +1,13 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "folio")]
 #[command(about = "A tool to manage your reading list", version = "0.1.0")]
 pub struct Cli {
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE")]
+    pub config_overrides: Vec<String>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -28,6 +32,34 @@ pub enum Commands {
 
         #[arg(long)]
         kind: Option<String>,
+
+        #[arg(long, value_name = "DURATION")]
+        minutes: Option<String>,
+
+        #[arg(long, value_delimiter = ',', value_name = "ID")]
+        evict: Vec<usize>,
+
+        #[arg(long, value_name = "ACTION", default_value = "archive")]
+        evict_action: String,
+
+        #[arg(long, conflicts_with = "evict")]
+        someday: bool,
+
+        #[arg(long, value_name = "ACTION")]
+        on_duplicate: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["name", "author", "link", "note", "kind", "minutes", "evict", "someday", "on_duplicate", "stdin"]
+        )]
+        from_file: Option<PathBuf>,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["name", "author", "link", "note", "kind", "minutes", "evict", "someday", "on_duplicate"]
+        )]
+        stdin: bool,
     },
 
     List {
@@ -36,6 +68,17 @@ pub enum Commands {
 
         #[arg(long, value_delimiter = ',')]
         r#type: Option<Vec<String>>,
+
+        #[arg(long)]
+        someday: bool,
+
+        #[arg(long)]
+        older: bool,
+    },
+
+    Next {
+        #[arg(long, value_name = "DURATION")]
+        minutes: Option<String>,
     },
 
     SetStatus {
@@ -48,18 +91,76 @@ pub enum Commands {
         id: usize,
     },
 
-    Archive {
+    Show {
         id: usize,
+
+        #[arg(long)]
+        someday: bool,
+    },
+
+    Snapshot {
+        id: usize,
+
+        #[arg(long)]
+        someday: bool,
+
+        #[arg(long)]
+        show: bool,
+    },
+
+    #[command(args_conflicts_with_subcommands = true)]
+    Archive {
+        id: Option<usize>,
+
+        #[command(subcommand)]
+        subcommand: Option<ArchiveSubcommands>,
     },
 
     Delete {
         id: usize,
     },
 
+    CheckLinks {
+        #[arg(long)]
+        inbox: bool,
+
+        #[arg(long)]
+        wayback: bool,
+
+        #[arg(long, value_name = "N", default_value = "8")]
+        concurrency: usize,
+
+        #[arg(long, value_name = "PER_SECOND", default_value = "5")]
+        rate: u32,
+
+        #[arg(long)]
+        older: bool,
+    },
+
     MarkRef {
         id: usize,
     },
 
+    Promote {
+        id: usize,
+    },
+
+    Import {
+        #[command(subcommand)]
+        subcommand: ImportSubcommands,
+    },
+
+    Review,
+
+    #[command(args_conflicts_with_subcommands = true)]
+    Export {
+        #[command(flatten)]
+        args: ExportArgs,
+
+        #[command(subcommand)]
+        subcommand: Option<ExportSubcommands>,
+    },
+
     Config {
         #[command(subcommand)]
         subcommand: ConfigSubcommands,
@@ -74,5 +175,216 @@ pub enum ConfigSubcommands {
 
     Set { key: String, value: String },
 
+    Describe { key: Option<String> },
+
     Reset,
 }
+
+#[derive(Subcommand, Debug)]
+pub enum ArchiveSubcommands {
+    Compact {
+        #[arg(long)]
+        compress: bool,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct ImportArgs {
+    pub file: PathBuf,
+
+    #[arg(long, value_name = "LIST", default_value = "archive")]
+    pub overflow: String,
+
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct BrowserImportArgs {
+    #[arg(long, value_name = "NAME")]
+    pub folder: Option<String>,
+
+    #[arg(long)]
+    pub list_folders: bool,
+
+    #[arg(long, value_name = "MODE", default_value = "tags")]
+    pub folders: String,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ImportSubcommands {
+    Bookmarks {
+        #[command(flatten)]
+        args: ImportArgs,
+
+        #[arg(long, value_name = "MODE", default_value = "tags")]
+        folders: String,
+    },
+
+    Firefox {
+        #[command(flatten)]
+        args: ImportArgs,
+
+        #[command(flatten)]
+        browser: BrowserImportArgs,
+    },
+
+    Chromium {
+        #[command(flatten)]
+        args: ImportArgs,
+
+        #[command(flatten)]
+        browser: BrowserImportArgs,
+    },
+
+    Pocket {
+        #[command(flatten)]
+        args: ImportArgs,
+    },
+
+    Instapaper {
+        #[command(flatten)]
+        args: ImportArgs,
+    },
+
+    Omnivore {
+        #[command(flatten)]
+        args: ImportArgs,
+    },
+
+    Bibtex {
+        #[command(flatten)]
+        args: ImportArgs,
+    },
+
+    CslJson {
+        #[command(flatten)]
+        args: ImportArgs,
+    },
+
+    Clippings {
+        file: PathBuf,
+
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    Org {
+        file: PathBuf,
+
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    Markdown {
+        path: PathBuf,
+
+        #[arg(long, value_name = "TEXT")]
+        heading: Option<String>,
+
+        #[arg(long)]
+        tasks: bool,
+
+        #[arg(short, long)]
+        r#type: Option<String>,
+
+        #[arg(long)]
+        dry_run: bool,
+
+        #[arg(long)]
+        no_review: bool,
+    },
+
+    Opml {
+        #[command(flatten)]
+        args: ImportArgs,
+
+        #[arg(short, long, default_value = "podcast")]
+        r#type: String,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    #[arg(short, long, default_value = "csv")]
+    pub format: String,
+
+    #[arg(long, value_delimiter = ',')]
+    pub columns: Option<Vec<String>>,
+
+    #[arg(long, value_delimiter = ',')]
+    pub status: Option<Vec<String>>,
+
+    #[arg(long, value_delimiter = ',')]
+    pub r#type: Option<Vec<String>>,
+
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ExportSubcommands {
+    Markdown {
+        #[arg(long)]
+        dir: PathBuf,
+    },
+
+    Bibtex {
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        #[arg(long, value_delimiter = ',')]
+        status: Option<Vec<String>>,
+    },
+
+    CslJson {
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        #[arg(long, value_delimiter = ',')]
+        status: Option<Vec<String>>,
+    },
+
+    Org {
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    Site {
+        dir: PathBuf,
+
+        #[arg(long, default_value = "Reading list")]
+        title: String,
+    },
+
+    Feed {
+        #[arg(short, long, default_value = "atom")]
+        format: String,
+
+        #[arg(long)]
+        references: bool,
+
+        #[arg(long, value_delimiter = ',')]
+        tag: Option<Vec<String>>,
+
+        #[arg(long, default_value = "What I've been reading")]
+        title: String,
+
+        #[arg(long, default_value = "folio")]
+        author: String,
+
+        #[arg(long)]
+        link: Option<String>,
+
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    Opml {
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        #[arg(short, long, default_value = "podcast")]
+        r#type: String,
+    },
+}
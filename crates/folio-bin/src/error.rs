@@ -9,19 +9,13 @@ pub enum CliError {
     #[error("Invalid status '{status}'. Valid options are: todo, doing, done")]
     InvalidStatus { status: String },
 
-    #[error(
-        "Invalid item type '{item_type}'. Valid options are: blog_post, video, podcast, news, thread, academic_paper, other"
-    )]
-    InvalidItemType { item_type: String },
+    #[error("Invalid item:{}", field_list(.errors))]
+    InvalidFields {
+        errors: folio_core::ValidationErrors,
+    },
 
-    #[error("Invalid value for max_items: {value}. Must be a number between 1 and 1000")]
-    InvalidMaxItems { value: String },
-
-    #[error("Invalid value for archive_on_overflow: {value}. Valid options are: abort, todo, any")]
-    InvalidOverflowStrategy { value: String },
-
-    #[error("Unknown config key '{key}'. Valid keys are: max_items, archive_on_overflow")]
-    UnknownConfigKey { key: String },
+    #[error("{0}")]
+    Config(#[from] folio_core::ConfigError),
 
     #[error("Inbox limit ({limit}) reached. {suggestions}")]
     InboxFull { limit: u32, suggestions: String },
@@ -35,6 +29,12 @@ pub enum CliError {
     #[error("Configuration error: {message}")]
     ConfigError { message: String },
 
+    #[error("Import error: {message}")]
+    ImportError { message: String },
+
+    #[error("{0}")]
+    Fetch(#[from] folio_fetch::FetchError),
+
     #[error("Storage error: {0}")]
     Storage(#[from] folio_storage::StorageError),
 
@@ -42,6 +42,14 @@ pub enum CliError {
     Tui(#[from] folio_tui::TuiError),
 }
 
+fn field_list(errors: &folio_core::ValidationErrors) -> String {
+    errors
+        .errors
+        .iter()
+        .map(|error| format!("\n  {:<7} {}", error.field.as_string(), error.message))
+        .collect()
+}
+
 impl From<Box<dyn std::error::Error>> for CliError {
     fn from(error: Box<dyn std::error::Error>) -> Self {
         CliError::IoError {
@@ -86,6 +94,10 @@ impl From<folio_core::CoreError> for CliError {
             folio_core::CoreError::ValidationError(msg) => {
                 CliError::ValidationError { message: msg }
             }
+            folio_core::CoreError::InvalidFields(errors) => CliError::InvalidFields { errors },
+            error @ folio_core::CoreError::Duplicate(_) => CliError::ValidationError {
+                message: error.to_string(),
+            },
             folio_core::CoreError::ItemNotFound => CliError::ValidationError {
                 message: "Item not found".to_string(),
             },
@@ -95,6 +107,11 @@ impl From<folio_core::CoreError> for CliError {
             folio_core::CoreError::InboxFull => CliError::ValidationError {
                 message: "Inbox is full".to_string(),
             },
+            folio_core::CoreError::SomedayFull => CliError::ValidationError {
+                message: "Someday list is full".to_string(),
+            },
+            folio_core::CoreError::Import(message) => CliError::ImportError { message },
+            folio_core::CoreError::Export(message) => CliError::IoError { message },
         }
     }
 }
@@ -1,13 +1,35 @@
 use clap::Parser;
-use folio_bin::cli::{Cli, Commands, ConfigSubcommands};
+use folio_bin::cli::{
+    ArchiveSubcommands, BrowserImportArgs, Cli, Commands, ConfigSubcommands, ExportArgs,
+    ExportSubcommands, ImportArgs, ImportSubcommands,
+};
 use folio_bin::error::{CliError, print_error};
-use folio_core::{ItemType, Kind, OverflowStrategy, Status};
+use folio_core::export::{
+    self, CitedItem, ExportColumn, ExportFormat, ExportRow, FeedFormat, FeedMeta,
+};
+use folio_core::import::{self, Browser, BrowserSyncState, FolderMode, ImportOverflow, Service};
+use folio_core::{
+    CONFIG_SCHEMA, Config, CoreError, DROPPED_TAG, Duplicate, DuplicateAction, DuplicateLocation,
+    EvictAction, Eviction, Item, ItemType, Kind, LinkCheck, LinkHealth, PageMetadata, Snapshot,
+    Status, TimeTotal, TypeRules, ValidationErrors, citation_keys, estimate_minutes,
+    extract_article, format_minutes, parse_item_type_field, parse_minutes_field,
+    parse_page_metadata,
+};
+use folio_fetch::{FetchError, Fetcher, LinkChecker, Page};
 use folio_storage::{
-    ConfigManager, append_to_archive, get_archive_path, get_inbox_path, load_items_from_file,
-    save_inbox,
+    ArchiveScope, ConfigManager, append_to_archive, archive_len, get_inbox_path,
+    list_archive_segments, load_archive, load_browser_sync_state, load_items_from_file,
+    load_snapshot, load_someday, load_staging, read_archive_item, read_firefox_bookmarks,
+    remove_archive_item, remove_snapshot, reopen_archive_item, save_archive_scoped,
+    save_browser_sync_state, save_inbox, save_snapshot, save_someday, save_staging,
+    update_archive_item, update_inbox_item, update_someday_item,
 };
 
+use std::collections::{HashMap, HashSet};
+use std::io::{IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Arc;
 
 fn main() {
     tokio::runtime::Builder::new_multi_thread()
@@ -25,9 +47,20 @@ fn main() {
 async fn run() -> Result<(), CliError> {
     let cli = Cli::parse();
 
-    let config_manager = ConfigManager::new().map_err(|e| CliError::ConfigError {
-        message: e.to_string(),
-    })?;
+    let overrides = cli
+        .config_overrides
+        .iter()
+        .map(|raw| folio_core::parse_override(raw))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let Some(Commands::Config { subcommand }) = &cli.command {
+        return handle_config_command(subcommand, overrides).await;
+    }
+
+    let config_manager =
+        ConfigManager::with_overrides(overrides).map_err(|e| CliError::ConfigError {
+            message: e.to_string(),
+        })?;
 
     match &cli.command {
         Some(command) => match command {
@@ -38,11 +71,44 @@ async fn run() -> Result<(), CliError> {
                 link,
                 note,
                 kind,
+                minutes,
+                evict,
+                evict_action,
+                someday,
+                on_duplicate,
+                from_file,
+                stdin,
+            } => {
+                if from_file.is_some() || *stdin {
+                    handle_add_batch_command(&config_manager, from_file.as_deref(), r#type).await?;
+                    return Ok(());
+                }
+                handle_add_command(
+                    &config_manager,
+                    name,
+                    r#type,
+                    author,
+                    link,
+                    note,
+                    kind,
+                    minutes,
+                    evict,
+                    evict_action,
+                    *someday,
+                    on_duplicate,
+                )
+                .await?;
+            }
+            Commands::List {
+                status,
+                r#type,
+                someday,
+                older,
             } => {
-                handle_add_command(&config_manager, name, r#type, author, link, note, kind).await?;
+                handle_list_command(status.as_deref(), r#type.as_deref(), *someday, *older).await?;
             }
-            Commands::List { status, r#type } => {
-                handle_list_command(status.as_deref(), r#type.as_deref()).await?;
+            Commands::Next { minutes } => {
+                handle_next_command(minutes.as_deref()).await?;
             }
             Commands::SetStatus { id, status } => {
                 handle_set_status_command(&config_manager, *id, status).await?;
@@ -50,69 +116,137 @@ async fn run() -> Result<(), CliError> {
             Commands::Edit { id } => {
                 handle_edit_command(*id).await?;
             }
-            Commands::Archive { id } => {
-                handle_archive_command(*id).await?;
+            Commands::Show { id, someday } => {
+                handle_show_command(*id, *someday).await?;
+            }
+            Commands::Snapshot { id, someday, show } => {
+                handle_snapshot_command(config_manager.get(), *id, *someday, *show).await?;
             }
+            Commands::Archive { id, subcommand } => match (id, subcommand) {
+                (_, Some(ArchiveSubcommands::Compact { compress })) => {
+                    handle_archive_compact_command(*compress).await?;
+                }
+                (Some(id), None) => {
+                    handle_archive_command(*id).await?;
+                }
+                (None, None) => {
+                    return Err(CliError::ValidationError {
+                        message: "Provide an item ID or a subcommand, e.g. 'folio archive compact'"
+                            .to_string(),
+                    });
+                }
+            },
             Commands::Delete { id } => {
                 handle_delete_command(*id).await?;
             }
+            Commands::CheckLinks {
+                inbox,
+                wayback,
+                concurrency,
+                rate,
+                older,
+            } => {
+                handle_check_links_command(
+                    config_manager.get(),
+                    *inbox,
+                    *wayback,
+                    *concurrency,
+                    *rate,
+                    *older,
+                )
+                .await?;
+            }
             Commands::MarkRef { id } => {
                 handle_mark_ref_command(*id).await?;
             }
-            Commands::Config { subcommand } => {
-                handle_config_command(subcommand).await?;
+            Commands::Promote { id } => {
+                handle_promote_command(&config_manager, *id).await?;
+            }
+            Commands::Import { subcommand } => {
+                handle_import_command(&config_manager, subcommand).await?;
+            }
+            Commands::Review => {
+                handle_review_command(&config_manager).await?;
             }
+            Commands::Export { args, subcommand } => match subcommand {
+                Some(subcommand) => handle_export_command(subcommand).await?,
+                None => handle_export_table_command(args).await?,
+            },
+            Commands::Config { .. } => unreachable!("config commands are handled before loading"),
         },
         None => {
-            folio_tui::run_tui_default().await?;
+            folio_tui::run_tui_default(config_manager.overrides().to_vec()).await?;
         }
     }
 
     Ok(())
 }
 
+fn matches_list_filters(
+    item: &Item,
+    status_filters: Option<&[String]>,
+    type_filters: Option<&[String]>,
+) -> bool {
+    if let Some(status_filters) = status_filters
+        && !status_filters.is_empty()
+    {
+        let matches = status_filters
+            .iter()
+            .any(|s| Status::from_str(&s.to_lowercase()).is_ok_and(|status| status == item.status));
+        if !matches {
+            return false;
+        }
+    }
+
+    if let Some(type_filters) = type_filters
+        && !type_filters.is_empty()
+    {
+        let matches = type_filters.iter().any(|t| {
+            ItemType::from_str(&t.to_lowercase()).is_ok_and(|item_type| item_type == item.item_type)
+        });
+        if !matches {
+            return false;
+        }
+    }
+
+    true
+}
+
 async fn handle_list_command(
     status_filters: Option<&[String]>,
     type_filters: Option<&[String]>,
+    someday: bool,
+    older: bool,
 ) -> Result<(), CliError> {
-    let inbox_path = get_inbox_path()?;
-    let archive_path = get_archive_path()?;
+    let mut all_items = Vec::new();
+    let mut older_hidden = false;
 
-    let inbox_items = load_items_from_file(&inbox_path)?;
-    let archive_items = load_items_from_file(&archive_path)?;
+    if someday {
+        all_items.extend(load_someday()?);
+    } else {
+        // Older archive years are only read when asked for, or when a filter could pick
+        // archived items out of them.
+        let wants_archived = type_filters.is_some_and(|filters| !filters.is_empty())
+            || status_filters.is_some_and(|filters| {
+                filters.iter().any(|s| {
+                    Status::from_str(&s.to_lowercase()).is_ok_and(|status| status == Status::Done)
+                })
+            });
+        let scope = if older || wants_archived {
+            ArchiveScope::All
+        } else {
+            older_hidden = !list_archive_segments()?.is_empty();
+            ArchiveScope::Recent
+        };
 
-    let mut all_items = Vec::new();
-    all_items.extend(inbox_items);
-    all_items.extend(archive_items);
+        let inbox_path = get_inbox_path()?;
+        all_items.extend(load_items_from_file(&inbox_path)?);
+        all_items.extend(load_archive(scope)?);
+    }
 
     let filtered_items: Vec<_> = all_items
         .into_iter()
-        .filter(|item| {
-            if let Some(status_filters) = status_filters
-                && !status_filters.is_empty()
-            {
-                let matches = status_filters.iter().any(|s| {
-                    Status::from_str(&s.to_lowercase()).is_ok_and(|status| status == item.status)
-                });
-                if !matches {
-                    return false;
-                }
-            }
-
-            if let Some(type_filters) = type_filters
-                && !type_filters.is_empty()
-            {
-                let matches = type_filters.iter().any(|t| {
-                    ItemType::from_str(&t.to_lowercase())
-                        .is_ok_and(|item_type| item_type == item.item_type)
-                });
-                if !matches {
-                    return false;
-                }
-            }
-
-            true
-        })
+        .filter(|item| matches_list_filters(item, status_filters, type_filters))
         .collect();
 
     if filtered_items.is_empty() {
@@ -126,6 +260,309 @@ async fn handle_list_command(
     for (index, item) in filtered_items.iter().enumerate() {
         println!("{}", item.format_for_list(index + 1));
     }
+    if older_hidden {
+        println!();
+        println!("Older archive years are not listed (`folio list --older` to include them).");
+    }
+    Ok(())
+}
+
+async fn handle_next_command(minutes: Option<&str>) -> Result<(), CliError> {
+    let inbox_items = load_items_from_file(get_inbox_path()?)?;
+    let total = TimeTotal::of(&inbox_items);
+
+    let slot = match minutes {
+        Some(minutes) => parse_minutes_field(minutes)
+            .map_err(|error| CliError::ValidationError {
+                message: format!("Invalid --minutes: {}", error.message),
+            })?
+            .unwrap_or_default(),
+        None => {
+            // Without a time slot, just the item at the front of the queue.
+            let next = inbox_items
+                .iter()
+                .position(|item| item.status == Status::Doing)
+                .or_else(|| {
+                    inbox_items
+                        .iter()
+                        .position(|item| item.status == Status::Todo)
+                });
+            match next {
+                Some(index) => {
+                    println!("{}", Item::format_list_header());
+                    println!("{}", Item::format_list_separator());
+                    println!("{}", inbox_items[index].format_for_list(index + 1));
+                    println!();
+                    println!("Inbox: {}", total.describe());
+                }
+                None => println!("Nothing left in the inbox."),
+            }
+            return Ok(());
+        }
+    };
+
+    let picked = folio_core::pick_for_time_slot(&inbox_items, slot);
+    if picked.is_empty() {
+        println!("Nothing in the inbox fits in {}.", format_minutes(slot));
+        if total.unestimated > 0 {
+            println!(
+                "{} item(s) have no estimate. Set one with `folio edit <id>`.",
+                total.unestimated
+            );
+        }
+        return Ok(());
+    }
+
+    let used: u32 = picked
+        .iter()
+        .filter_map(|index| inbox_items[*index].estimated_minutes)
+        .sum();
+    println!(
+        "{} of {} fits {} item(s):",
+        format_minutes(used),
+        format_minutes(slot),
+        picked.len()
+    );
+    for index in &picked {
+        let item = &inbox_items[*index];
+        println!(
+            "  #{:<4} {:>7}  {}",
+            index + 1,
+            format_minutes(item.estimated_minutes.unwrap_or_default()),
+            item.name
+        );
+    }
+    if total.unestimated > 0 {
+        println!(
+            "Skipped {} item(s) without an estimate. Set one with `folio edit <id>`.",
+            total.unestimated
+        );
+    }
+    Ok(())
+}
+
+enum ItemLocation {
+    Inbox(usize),
+    Archive(ArchiveScope, usize),
+}
+
+// Ids past the inbox number the recent archive first, so older years are only opened for ids
+// beyond it.
+fn locate_item(id: usize, inbox_len: usize) -> Result<ItemLocation, CliError> {
+    if id > 0 && id <= inbox_len {
+        return Ok(ItemLocation::Inbox(id - 1));
+    }
+
+    let position = id
+        .checked_sub(inbox_len + 1)
+        .ok_or(CliError::ItemNotFound { id })?;
+    for scope in [ArchiveScope::Recent, ArchiveScope::All] {
+        if position < archive_len(scope)? {
+            return Ok(ItemLocation::Archive(scope, position));
+        }
+    }
+    Err(CliError::ItemNotFound { id })
+}
+
+fn load_located_item(
+    location: &ItemLocation,
+    inbox_items: &[Item],
+    id: usize,
+) -> Result<Item, CliError> {
+    match location {
+        ItemLocation::Inbox(index) => Ok(inbox_items[*index].clone()),
+        ItemLocation::Archive(scope, index) => {
+            read_archive_item(*scope, *index)?.ok_or(CliError::ItemNotFound { id })
+        }
+    }
+}
+
+fn store_located_item(location: &ItemLocation, item: &Item) -> Result<(), CliError> {
+    match location {
+        ItemLocation::Inbox(index) => update_inbox_item(*index, item)?,
+        ItemLocation::Archive(scope, index) => update_archive_item(*scope, *index, item)?,
+    }
+    Ok(())
+}
+
+fn store_item(id: usize, someday: bool, item: &Item) -> Result<(), CliError> {
+    if someday {
+        update_someday_item(id - 1, item)?;
+    } else {
+        let inbox_items = load_items_from_file(get_inbox_path()?)?;
+        store_located_item(&locate_item(id, inbox_items.len())?, item)?;
+    }
+    Ok(())
+}
+
+fn load_item(id: usize, someday: bool) -> Result<Item, CliError> {
+    if someday {
+        load_someday()?
+            .get(id.wrapping_sub(1))
+            .cloned()
+            .ok_or(CliError::ItemNotFound { id })
+    } else {
+        let inbox_items = load_items_from_file(get_inbox_path()?)?;
+        let location = locate_item(id, inbox_items.len())?;
+        load_located_item(&location, &inbox_items, id)
+    }
+}
+
+async fn handle_show_command(id: usize, someday: bool) -> Result<(), CliError> {
+    let item = load_item(id, someday)?;
+
+    let date = |time: &Option<chrono::DateTime<chrono::Utc>>| {
+        time.map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    println!("{}", item.name);
+    println!("{}", "-".repeat(item.name.chars().count().clamp(10, 100)));
+    println!("{:<10} {}", "Type:", item.item_type.as_string());
+    println!("{:<10} {}", "Status:", item.status.as_string());
+    println!("{:<10} {}", "Kind:", item.kind.as_string());
+    if !item.author.is_empty() {
+        println!("{:<10} {}", "Author:", item.author);
+    }
+    if !item.link.is_empty() {
+        println!("{:<10} {}", "Link:", item.link);
+    }
+    println!("{:<10} {}", "Added:", date(&Some(item.added_at)));
+    println!("{:<10} {}", "Started:", date(&item.started_at));
+    println!("{:<10} {}", "Finished:", date(&item.finished_at));
+    if !item.tags.is_empty() {
+        println!("{:<10} {}", "Tags:", item.tags.join(", "));
+    }
+    if let Some(minutes) = item.estimated_minutes {
+        println!("{:<10} {}", "Time:", format_minutes(minutes));
+    }
+    if let Some(check) = &item.link_check {
+        println!(
+            "{:<10} {} on {}",
+            "Checked:",
+            check.describe(),
+            check.checked_at.format("%Y-%m-%d")
+        );
+        if let Some(archived_url) = &check.archived_url {
+            println!("{:<10} {}", "Archived:", archived_url);
+        }
+    }
+    if let Some(snapshot) = load_snapshot(&item)? {
+        println!(
+            "{:<10} {} words, saved {}",
+            "Snapshot:",
+            snapshot.word_count(),
+            date(&Some(snapshot.fetched_at))
+        );
+    }
+
+    if let Some(bib) = &item.bib {
+        if !bib.authors.is_empty() {
+            println!("{:<10} {}", "Authors:", bib.authors.join("; "));
+        }
+        if let Some(venue) = &bib.venue {
+            println!("{:<10} {}", "Venue:", venue);
+        }
+        if let Some(year) = bib.year {
+            println!("{:<10} {}", "Year:", year);
+        }
+        if let Some(doi) = &bib.doi {
+            println!("{:<10} {}", "DOI:", doi);
+        }
+        if let Some(arxiv_id) = &bib.arxiv_id {
+            println!("{:<10} {}", "arXiv:", arxiv_id);
+        }
+    }
+
+    if !item.note.is_empty() {
+        println!();
+        println!("Note:");
+        for line in item.note.lines() {
+            println!("  {}", line);
+        }
+    }
+
+    if !item.highlights.is_empty() {
+        println!();
+        println!("Highlights ({}):", item.highlights.len());
+        for highlight in &item.highlights {
+            println!();
+            for line in highlight.text.lines() {
+                println!("  > {}", line);
+            }
+            if !highlight.note.is_empty() {
+                println!("  Note: {}", highlight.note);
+            }
+            if let Some(location) = &highlight.location {
+                println!("  ({})", location);
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn handle_snapshot_command(
+    config: &Config,
+    id: usize,
+    someday: bool,
+    show: bool,
+) -> Result<(), CliError> {
+    let mut item = load_item(id, someday)?;
+
+    if show {
+        let snapshot = load_snapshot(&item)?.ok_or_else(|| CliError::ValidationError {
+            message: format!(
+                "Item #{} has no snapshot yet. Save one with `folio snapshot {}`",
+                id, id
+            ),
+        })?;
+
+        println!("{}", snapshot.title.as_deref().unwrap_or(&item.name));
+        if let Some(byline) = &snapshot.byline {
+            println!("by {}", byline);
+        }
+        println!(
+            "{} (saved {})",
+            snapshot.url,
+            snapshot.fetched_at.format("%Y-%m-%d %H:%M")
+        );
+        println!();
+        println!("{}", snapshot.text);
+        return Ok(());
+    }
+
+    if !folio_core::is_supported_link(&item.link) {
+        return Err(CliError::ValidationError {
+            message: format!("Item #{} has no link to take a snapshot of", id),
+        });
+    }
+
+    let page = Fetcher::from_config(config)?.fetch_page(&item.link).await?;
+    let snapshot = Snapshot::new(page.url, extract_article(&page.html));
+    if snapshot.text.is_empty() {
+        return Err(CliError::ValidationError {
+            message: format!("No readable text found at {}", item.link),
+        });
+    }
+
+    let path = save_snapshot(&item, &snapshot)?;
+    println!(
+        "Saved a snapshot of '{}' ({} words) to {}",
+        item.name,
+        snapshot.word_count(),
+        path.display()
+    );
+
+    // An estimate set by hand, or from a published duration, is left alone.
+    if item.estimated_minutes.is_none() {
+        let duration = parse_page_metadata(&page.html).duration_seconds;
+        item.estimated_minutes =
+            estimate_minutes(&item.item_type, duration, Some(snapshot.word_count()));
+        if let Some(minutes) = item.estimated_minutes {
+            store_item(id, someday, &item)?;
+            println!("Estimated time: {}", format_minutes(minutes));
+        }
+    }
     Ok(())
 }
 
@@ -135,83 +572,77 @@ async fn handle_set_status_command(
     status_str: &str,
 ) -> Result<(), CliError> {
     let inbox_path = get_inbox_path()?;
-    let archive_path = get_archive_path()?;
-
-    let inbox_items = load_items_from_file(&inbox_path)?;
-    let archive_items = load_items_from_file(&archive_path)?;
+    let mut inbox_items = load_items_from_file(&inbox_path)?;
 
     let new_status = Status::from_str(status_str).map_err(|_| CliError::InvalidStatus {
         status: status_str.to_string(),
     })?;
 
-    let config = config_manager.get();
-    let result =
-        folio_core::update_item_status(id, new_status, inbox_items, archive_items, config)?;
+    let location = locate_item(id, inbox_items.len())?;
+    let mut item = load_located_item(&location, &inbox_items, id)?;
+    let transition = folio_core::change_item_status(&mut item, new_status);
 
-    if !result.item_found {
-        return Err(CliError::ItemNotFound { id });
-    }
+    match location {
+        ItemLocation::Inbox(index) if transition.should_archive => {
+            inbox_items.remove(index);
+            append_to_archive(&item)?;
+            save_inbox(&inbox_items)?;
 
-    if !result.moved_to_archive.is_empty() {
-        println!(
-            "Item #{} status updated to '{}' and moved to archive",
-            id, status_str
-        );
-    } else if result.moved_to_inbox {
-        if !result.overflow_items.is_empty() {
-            println!(
-                "Item #{} status updated to '{}' and moved to inbox. {} item(s) were archived due to overflow:",
-                id,
-                status_str,
-                result.overflow_items.len()
-            );
-            for item in &result.overflow_items {
-                println!("  - {} ({})", item.name, item.status.as_string());
-            }
-        } else {
             println!(
-                "Item #{} status updated to '{}' and moved to inbox",
+                "Item #{} status updated to '{}' and moved to archive",
                 id, status_str
             );
         }
-    } else {
-        println!("Item #{} status updated to '{}'", id, status_str);
-    }
+        ItemLocation::Archive(scope, index) if transition.should_move_to_inbox => {
+            let (new_inbox, overflow_items) =
+                folio_core::move_item_to_inbox(inbox_items, item, config_manager.get())?;
 
-    save_inbox(&result.inbox_items)?;
-    folio_storage::save_archive(&result.archive_items)?;
+            save_inbox(&new_inbox)?;
+            reopen_archive_item(scope, index, &overflow_items)?;
+
+            if !overflow_items.is_empty() {
+                println!(
+                    "Item #{} status updated to '{}' and moved to inbox. {} item(s) were archived due to overflow:",
+                    id,
+                    status_str,
+                    overflow_items.len()
+                );
+                for item in &overflow_items {
+                    println!("  - {} ({})", item.name, item.status.as_string());
+                }
+            } else {
+                println!(
+                    "Item #{} status updated to '{}' and moved to inbox",
+                    id, status_str
+                );
+            }
+        }
+        _ => {
+            store_located_item(&location, &item)?;
+            println!("Item #{} status updated to '{}'", id, status_str);
+        }
+    }
 
     Ok(())
 }
 
 async fn handle_edit_command(id: usize) -> Result<(), CliError> {
     let inbox_path = get_inbox_path()?;
-    let archive_path = get_archive_path()?;
-
-    let mut inbox_items = load_items_from_file(&inbox_path)?;
-    let mut archive_items = load_items_from_file(&archive_path)?;
-
-    let (is_in_inbox, item_index) = if id > 0 && id <= inbox_items.len() {
-        let item_index = id - 1;
-        (true, item_index)
-    } else if id > inbox_items.len() && id <= inbox_items.len() + archive_items.len() {
-        let item_index = id - inbox_items.len() - 1;
-        (false, item_index)
-    } else {
-        return Err(CliError::ItemNotFound { id });
-    };
+    let inbox_items = load_items_from_file(&inbox_path)?;
 
-    let item = if is_in_inbox {
-        &mut inbox_items[item_index]
-    } else {
-        &mut archive_items[item_index]
-    };
+    let location = locate_item(id, inbox_items.len())?;
+    let mut item = load_located_item(&location, &inbox_items, id)?;
+    let before = item.clone();
 
     let original_name = item.name.clone();
     let original_type = format!("{:?}", item.item_type);
     let original_author = item.author.clone();
     let original_link = item.link.clone();
     let original_note = item.note.clone();
+    let original_minutes = item
+        .estimated_minutes
+        .map(format_minutes)
+        .unwrap_or_else(|| "-".to_string());
 
     println!("Editing item #{}. Leave blank to keep current value.", id);
     println!("Current name: {}", original_name);
@@ -222,11 +653,12 @@ async fn handle_edit_command(id: usize) -> Result<(), CliError> {
 
     println!("Current type: {}", original_type);
     let type_input = prompt_for_input("Type").await?;
+    let mut errors = ValidationErrors::default();
     if !type_input.is_empty() {
-        item.item_type =
-            ItemType::from_str(&type_input).map_err(|_| CliError::InvalidItemType {
-                item_type: type_input,
-            })?;
+        match parse_item_type_field(&type_input) {
+            Ok(item_type) => item.item_type = item_type,
+            Err(error) => errors.errors.push(error),
+        }
     }
 
     println!("Current author: {}", original_author);
@@ -237,8 +669,9 @@ async fn handle_edit_command(id: usize) -> Result<(), CliError> {
 
     println!("Current link: {}", original_link);
     let link_input = prompt_for_input("Link").await?;
-    if !link_input.is_empty() {
+    if !link_input.is_empty() && link_input != item.link {
         item.link = link_input;
+        item.link_check = None;
     }
 
     println!("Current note: {}", original_note);
@@ -247,62 +680,75 @@ async fn handle_edit_command(id: usize) -> Result<(), CliError> {
         item.note = note_input;
     }
 
-    item.validate()?;
-
-    if is_in_inbox {
-        save_inbox(&inbox_items)?;
-    } else {
-        folio_storage::save_archive(&archive_items)?;
+    println!("Current estimate: {}", original_minutes);
+    let minutes_input = prompt_for_input("Minutes").await?;
+    match parse_minutes_field(&minutes_input) {
+        Ok(Some(minutes)) => item.estimated_minutes = Some(minutes),
+        Ok(None) => {}
+        Err(error) => errors.errors.push(error),
     }
 
+    errors
+        .errors
+        .extend(item.changed_field_errors(&before).errors);
+    errors.into_result()?;
+    store_located_item(&location, &item)?;
+
     println!("Item #{} updated successfully", id);
     Ok(())
 }
 
 async fn handle_archive_command(id: usize) -> Result<(), CliError> {
     let inbox_path = get_inbox_path()?;
-    let archive_path = get_archive_path()?;
     let mut inbox_items = load_items_from_file(&inbox_path)?;
-    let archive_items = load_items_from_file(&archive_path)?;
 
-    if id > 0 && id <= inbox_items.len() {
-        let item_index = id - 1;
-        let mut item = inbox_items.remove(item_index);
+    match locate_item(id, inbox_items.len())? {
+        ItemLocation::Inbox(item_index) => {
+            let mut item = inbox_items.remove(item_index);
+
+            item.status = Status::Done;
+            folio_core::update_timestamps(&mut item);
+
+            append_to_archive(&item)?;
+
+            save_inbox(&inbox_items)?;
 
-        item.status = Status::Done;
-        folio_core::update_timestamps(&mut item);
+            println!("Item #{} marked as done and archived successfully", id);
+        }
+        ItemLocation::Archive(..) => {
+            println!("Item #{} is already in archive", id);
+        }
+    }
 
-        append_to_archive(&item)?;
+    Ok(())
+}
 
-        save_inbox(&inbox_items)?;
+async fn handle_archive_compact_command(compress: bool) -> Result<(), CliError> {
+    let report = folio_storage::compact_archive(compress)?;
 
-        println!("Item #{} marked as done and archived successfully", id);
-        Ok(())
-    } else if id > inbox_items.len() && id <= inbox_items.len() + archive_items.len() {
-        println!("Item #{} is already in archive", id);
-        Ok(())
+    if report.moved.is_empty() {
+        println!("Nothing to compact");
     } else {
-        Err(CliError::ItemNotFound { id })
+        for (year, count) in &report.moved {
+            println!("Moved {} item(s) to the {} segment", count, year);
+        }
+    }
+
+    if !report.compressed.is_empty() {
+        let years: Vec<String> = report.compressed.iter().map(|y| y.to_string()).collect();
+        println!("Compressed segments: {}", years.join(", "));
     }
+
+    println!("{} item(s) remain in the recent archive", report.remaining);
+    Ok(())
 }
 
 async fn handle_delete_command(id: usize) -> Result<(), CliError> {
     let inbox_path = get_inbox_path()?;
-    let archive_path = get_archive_path()?;
     let mut inbox_items = load_items_from_file(&inbox_path)?;
-    let mut archive_items = load_items_from_file(&archive_path)?;
-
-    let (is_in_inbox, item_index, item) = if id > 0 && id <= inbox_items.len() {
-        let item_index = id - 1;
-        let item = inbox_items[item_index].clone();
-        (true, item_index, item)
-    } else if id > inbox_items.len() && id <= inbox_items.len() + archive_items.len() {
-        let item_index = id - inbox_items.len() - 1;
-        let item = archive_items[item_index].clone();
-        (false, item_index, item)
-    } else {
-        return Err(CliError::ItemNotFound { id });
-    };
+
+    let location = locate_item(id, inbox_items.len())?;
+    let item = load_located_item(&location, &inbox_items, id)?;
 
     println!("Item to delete:");
     println!("  Name: {}", item.name);
@@ -312,7 +758,7 @@ async fn handle_delete_command(id: usize) -> Result<(), CliError> {
     println!("  Link: {}", item.link);
 
     print!("Delete this item permanently? (y/N): ");
-    use std::io::{Write, stdin, stdout};
+    use std::io::{stdin, stdout};
     stdout().flush()?;
 
     let mut input = String::new();
@@ -324,69 +770,1143 @@ async fn handle_delete_command(id: usize) -> Result<(), CliError> {
         return Ok(());
     }
 
-    if is_in_inbox {
-        inbox_items.remove(item_index);
-        save_inbox(&inbox_items)?;
-    } else {
-        archive_items.remove(item_index);
-        folio_storage::save_archive(&archive_items)?;
+    match location {
+        ItemLocation::Inbox(item_index) => {
+            inbox_items.remove(item_index);
+            save_inbox(&inbox_items)?;
+        }
+        ItemLocation::Archive(scope, item_index) => {
+            remove_archive_item(scope, item_index)?;
+        }
     }
+    remove_snapshot(&item)?;
 
     println!("Item #{} deleted successfully", id);
     Ok(())
 }
 
-async fn handle_mark_ref_command(id: usize) -> Result<(), CliError> {
-    let inbox_path = get_inbox_path()?;
-    let archive_path = get_archive_path()?;
-    let inbox_items = load_items_from_file(&inbox_path)?;
-    let mut archive_items = load_items_from_file(&archive_path)?;
-
-    let (is_in_inbox, item_index) = if id > 0 && id <= inbox_items.len() {
-        let item_index = id - 1;
-        (true, item_index)
-    } else if id > inbox_items.len() && id <= inbox_items.len() + archive_items.len() {
-        let item_index = id - inbox_items.len() - 1;
-        (false, item_index)
+fn is_checkable_link(link: &str) -> bool {
+    let link = link.trim().to_lowercase();
+    folio_core::is_supported_link(&link)
+        && (link.starts_with("http://") || link.starts_with("https://"))
+}
+
+async fn handle_check_links_command(
+    config: &Config,
+    inbox_only: bool,
+    wayback: bool,
+    concurrency: usize,
+    rate: u32,
+    older: bool,
+) -> Result<(), CliError> {
+    if concurrency == 0 || rate == 0 {
+        return Err(CliError::ValidationError {
+            message: "--concurrency and --rate must be at least 1".to_string(),
+        });
+    }
+
+    let checker = Arc::new(LinkChecker::from_config(config)?);
+    let mut inbox_items = load_items_from_file(get_inbox_path()?)?;
+    let archive_scope = if older {
+        ArchiveScope::All
     } else {
-        return Err(CliError::ItemNotFound { id });
+        ArchiveScope::Recent
+    };
+    let mut archive_items = if inbox_only {
+        Vec::new()
+    } else {
+        load_archive(archive_scope)?
     };
 
-    if is_in_inbox {
-        println!(
-            "Cannot mark reference for items in inbox. Only archived (done) items can be marked as references."
-        );
+    // (in the archive, position) for every item with a web link, in `folio list` order.
+    let targets: Vec<(bool, usize)> = inbox_items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| (false, index, item))
+        .chain(
+            archive_items
+                .iter()
+                .enumerate()
+                .map(|(index, item)| (true, index, item)),
+        )
+        .filter(|(_, _, item)| is_checkable_link(&item.link))
+        .map(|(archived, index, _)| (archived, index))
+        .collect();
+    if targets.is_empty() {
+        println!("No links to check");
         return Ok(());
-    } else {
-        let item = &mut archive_items[item_index];
-        match item.kind {
-            folio_core::Kind::Normal => {
-                item.kind = folio_core::Kind::Reference;
-                folio_storage::save_archive(&archive_items)?;
-                println!("Item #{} marked as reference", id);
-            }
-            folio_core::Kind::Reference => {
-                item.kind = folio_core::Kind::Normal;
-                folio_storage::save_archive(&archive_items)?;
-                println!("Item #{} unmarked as reference", id);
+    }
+
+    let urls: Vec<String> = targets
+        .iter()
+        .map(|&(archived, index)| {
+            let items = if archived {
+                &archive_items
+            } else {
+                &inbox_items
+            };
+            items[index].link.clone()
+        })
+        .collect();
+    let total = urls.len();
+    println!("Checking {} link(s)...", total);
+
+    let interactive = std::io::stdout().is_terminal();
+    let mut finished = 0;
+    let checks = checker
+        .check_all(urls, concurrency, rate, wayback, |_, _| {
+            finished += 1;
+            if interactive {
+                print!("\r  {}/{}", finished, total);
+                let _ = std::io::stdout().flush();
             }
-        }
+        })
+        .await;
+    if interactive {
+        println!();
+    }
+
+    for (&(archived, index), check) in targets.iter().zip(checks) {
+        let items = if archived {
+            &mut archive_items
+        } else {
+            &mut inbox_items
+        };
+        items[index].link_check = Some(check);
+    }
+    save_inbox(&inbox_items)?;
+    if targets.iter().any(|&(archived, _)| archived) {
+        save_archive_scoped(&archive_items, archive_scope)?;
     }
 
+    let inbox_len = inbox_items.len();
+    let mut checked: Vec<(usize, &Item, &LinkCheck)> = targets
+        .iter()
+        .filter_map(|&(archived, index)| {
+            let (id, item) = if archived {
+                (inbox_len + index + 1, &archive_items[index])
+            } else {
+                (index + 1, &inbox_items[index])
+            };
+            item.link_check.as_ref().map(|check| (id, item, check))
+        })
+        .collect();
+    let count = |wanted: fn(&LinkCheck) -> bool| {
+        checked.iter().filter(|(_, _, check)| wanted(check)).count()
+    };
+
+    println!("Checked {} link(s):", total);
+    println!(
+        "  {:<34} {}",
+        "ok",
+        count(|check| check.health == LinkHealth::Ok)
+    );
+    println!(
+        "  {:<34} {}",
+        "redirected",
+        count(|check| check.final_url.is_some())
+    );
+    println!(
+        "  {:<34} {}",
+        "not found (404/410)",
+        count(|check| check.health == LinkHealth::NotFound)
+    );
+    println!(
+        "  {:<34} {}",
+        "DNS failures",
+        count(|check| check.health == LinkHealth::DnsFailure)
+    );
+    println!(
+        "  {:<34} {}",
+        "other errors",
+        count(|check| check.health != LinkHealth::Ok && !check.is_dead())
+    );
+
+    // Dead links first; the rest may well work next time.
+    checked.retain(|(_, _, check)| check.health != LinkHealth::Ok);
+    checked.sort_by_key(|(_, _, check)| !check.is_dead());
+
+    for (id, item, check) in checked {
+        println!();
+        println!("#{} {}", id, item.name);
+        println!("  {}", item.link);
+        println!("  {}", check.describe());
+        if let Some(archived_url) = &check.archived_url {
+            println!("  archived copy: {}", archived_url);
+        } else if wayback && check.is_dead() {
+            println!("  no archived copy found");
+        }
+    }
     Ok(())
 }
 
-async fn prompt_for_input(field_name: &str) -> Result<String, CliError> {
-    use std::io::{Write, stdin, stdout};
+async fn handle_mark_ref_command(id: usize) -> Result<(), CliError> {
+    let inbox_path = get_inbox_path()?;
+    let inbox_items = load_items_from_file(&inbox_path)?;
 
-    print!("{}: ", field_name);
-    stdout().flush()?;
+    let location = locate_item(id, inbox_items.len())?;
 
-    let mut input = String::new();
+    if let ItemLocation::Inbox(_) = location {
+        println!(
+            "Cannot mark reference for items in inbox. Only archived (done) items can be marked as references."
+        );
+        return Ok(());
+    }
+
+    let mut item = load_located_item(&location, &inbox_items, id)?;
+    match item.kind {
+        folio_core::Kind::Normal => {
+            item.kind = folio_core::Kind::Reference;
+            store_located_item(&location, &item)?;
+            println!("Item #{} marked as reference", id);
+        }
+        folio_core::Kind::Reference => {
+            item.kind = folio_core::Kind::Normal;
+            store_located_item(&location, &item)?;
+            println!("Item #{} unmarked as reference", id);
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_promote_command(config_manager: &ConfigManager, id: usize) -> Result<(), CliError> {
+    let config = config_manager.get();
+
+    let someday_items = load_someday()?;
+    if id == 0 || id > someday_items.len() {
+        return Err(CliError::ItemNotFound { id });
+    }
+
+    let inbox_items = load_items_from_file(get_inbox_path()?)?;
+
+    let result = match folio_core::promote_from_someday(inbox_items, someday_items, id - 1, config)
+    {
+        Ok(result) => result,
+        Err(folio_core::CoreError::InboxFull) => {
+            return Err(CliError::InboxFull {
+                limit: config.max_items,
+                suggestions:
+                    "Finish or archive an inbox item first, or change `archive_on_overflow`."
+                        .to_string(),
+            });
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    // Inbox first: an interruption leaves the item in both lists rather than in neither.
+    save_inbox(&result.inbox_items)?;
+    for item in &result.overflow_items {
+        append_to_archive(item)?;
+    }
+    save_someday(&result.someday_items)?;
+
+    println!("Promoted '{}' to inbox", result.promoted.name);
+    for item in &result.overflow_items {
+        println!("  - archived due to overflow: {}", item.name);
+    }
+    Ok(())
+}
+
+fn read_import_file(file: &Path) -> Result<String, CliError> {
+    std::fs::read_to_string(file).map_err(|e| CliError::IoError {
+        message: format!("Failed to read {}: {}", file.display(), e),
+    })
+}
+
+async fn handle_import_command(
+    config_manager: &ConfigManager,
+    subcommand: &ImportSubcommands,
+) -> Result<(), CliError> {
+    let rules = TypeRules::from_config(config_manager.get());
+
+    let (args, (candidates, skipped)) = match subcommand {
+        ImportSubcommands::Clippings { file, dry_run } => return import_clippings(file, *dry_run),
+        ImportSubcommands::Org { file, dry_run } => {
+            return import_org(config_manager, file, *dry_run);
+        }
+        ImportSubcommands::Markdown {
+            path,
+            heading,
+            tasks,
+            r#type,
+            dry_run,
+            no_review,
+        } => {
+            let item_type = r#type.as_deref().map(parse_item_type).transpose()?;
+            let staged = import_markdown(
+                path,
+                heading.as_deref(),
+                *tasks,
+                item_type.as_ref(),
+                &rules,
+                *dry_run,
+            )?;
+
+            if staged > 0 && !*dry_run && !*no_review && std::io::stdout().is_terminal() {
+                return handle_review_command(config_manager).await;
+            }
+            return Ok(());
+        }
+        ImportSubcommands::Firefox { args, browser } => {
+            return import_browser(config_manager, Browser::Firefox, args, browser);
+        }
+        ImportSubcommands::Chromium { args, browser } => {
+            return import_browser(config_manager, Browser::Chromium, args, browser);
+        }
+        ImportSubcommands::Bookmarks { args, folders } => {
+            let folder_mode = parse_folder_mode(folders)?;
+
+            let bookmarks = import::parse_netscape_bookmarks(&read_import_file(&args.file)?);
+            let converted = import::convert_records(
+                &bookmarks,
+                |bookmark| record_label(&bookmark.title, &bookmark.url),
+                |bookmark| import::bookmark_to_item(bookmark, folder_mode, &rules),
+            );
+            (args, converted)
+        }
+        ImportSubcommands::Opml { args, r#type } => {
+            let item_type = parse_item_type(r#type)?;
+
+            let outlines = import::parse_opml(&read_import_file(&args.file)?);
+            let converted = import::convert_records(
+                &outlines,
+                |outline| record_label(&outline.title, &outline.feed_url),
+                |outline| import::outline_to_item(outline, &item_type),
+            );
+            (args, converted)
+        }
+        ImportSubcommands::Bibtex { args } | ImportSubcommands::CslJson { args } => {
+            let data = read_import_file(&args.file)?;
+            let records = match subcommand {
+                ImportSubcommands::Bibtex { .. } => import::parse_bibtex(&data)?,
+                _ => import::parse_csl_json(&data)?,
+            };
+            let converted = import::convert_records(
+                &records,
+                |record| record.title.clone(),
+                import::bib_record_to_item,
+            );
+            (args, converted)
+        }
+        ImportSubcommands::Pocket { args }
+        | ImportSubcommands::Instapaper { args }
+        | ImportSubcommands::Omnivore { args } => {
+            let service = match subcommand {
+                ImportSubcommands::Pocket { .. } => Service::Pocket,
+                ImportSubcommands::Instapaper { .. } => Service::Instapaper,
+                _ => Service::Omnivore,
+            };
+
+            let entries = import::parse_service_export(service, &read_import_file(&args.file)?)?;
+            let converted = import::convert_records(
+                &entries,
+                |entry| record_label(&entry.title, &entry.url),
+                |entry| import::entry_to_item(entry, &rules),
+            );
+            (args, converted)
+        }
+    };
+
+    run_import(config_manager, args, candidates, &skipped)
+}
+
+fn parse_folder_mode(folders: &str) -> Result<FolderMode, CliError> {
+    FolderMode::from_str(folders).map_err(|_| CliError::ValidationError {
+        message: format!(
+            "Invalid folder mode '{}'. Valid options are: tags, note",
+            folders
+        ),
+    })
+}
+
+// Bookmarks are remembered by the browser's GUID per profile, so re-running only brings in
+// links added since, even if earlier ones were deleted from folio in the meantime.
+fn import_browser(
+    config_manager: &ConfigManager,
+    browser: Browser,
+    args: &ImportArgs,
+    browser_args: &BrowserImportArgs,
+) -> Result<(), CliError> {
+    let folder_mode = parse_folder_mode(&browser_args.folders)?;
+    let path = if args.file.is_dir() {
+        args.file.join(browser.bookmarks_file())
+    } else {
+        args.file.clone()
+    };
+
+    let bookmarks = match browser {
+        Browser::Firefox => read_firefox_bookmarks(&path)?,
+        Browser::Chromium => import::parse_chromium_bookmarks(&read_import_file(&path)?)?,
+    };
+
+    if browser_args.list_folders {
+        for (folder, count) in import::folder_counts(&bookmarks) {
+            println!("  {:<34} {}", folder, count);
+        }
+        return Ok(());
+    }
+
+    let bookmarks: Vec<_> = match &browser_args.folder {
+        Some(folder) => {
+            let selected: Vec<_> = bookmarks
+                .into_iter()
+                .filter(|bookmark| import::in_folder(&bookmark.bookmark.folders, folder))
+                .collect();
+            if selected.is_empty() {
+                return Err(CliError::ValidationError {
+                    message: format!(
+                        "No bookmarks in a folder named '{}'. Use --list-folders to see the folders",
+                        folder
+                    ),
+                });
+            }
+            selected
+        }
+        None => bookmarks,
+    };
+
+    let profile = path.canonicalize().unwrap_or_else(|_| path.clone());
+    let source = BrowserSyncState::source_key(browser, &profile.display().to_string());
+    let mut state = load_browser_sync_state()?;
+
+    let (seen, new): (Vec<_>, Vec<_>) = bookmarks
+        .into_iter()
+        .partition(|bookmark| state.is_imported(&source, &bookmark.guid));
+
+    if new.is_empty() {
+        println!(
+            "No new bookmarks since the last import ({} already imported)",
+            seen.len()
+        );
+        return Ok(());
+    }
+
+    let rules = TypeRules::from_config(config_manager.get());
+    let (candidates, skipped) = import::convert_records(
+        &new,
+        |bookmark| record_label(&bookmark.bookmark.title, &bookmark.bookmark.url),
+        |bookmark| import::bookmark_to_item(&bookmark.bookmark, folder_mode, &rules),
+    );
+    run_import(config_manager, args, candidates, &skipped)?;
+    println!("  {:<34} {}", "imported on an earlier run", seen.len());
+
+    if !args.dry_run {
+        state.mark_imported(&source, new.iter().map(|bookmark| bookmark.guid.as_str()));
+        save_browser_sync_state(&state)?;
+    }
+    Ok(())
+}
+
+fn collect_markdown_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), CliError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| CliError::IoError {
+        message: format!("Failed to read {}: {}", dir.display(), e),
+    })?;
+
+    for entry in entries {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+
+        if hidden {
+            continue;
+        } else if path.is_dir() {
+            collect_markdown_files(&path, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext == "md" || ext == "markdown")
+        {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+// Links are staged rather than added, so a notes folder full of links doesn't hit the cap
+// before it has been reviewed. Returns the number of newly staged links.
+fn import_markdown(
+    path: &Path,
+    heading: Option<&str>,
+    tasks_only: bool,
+    item_type: Option<&ItemType>,
+    rules: &TypeRules,
+    dry_run: bool,
+) -> Result<usize, CliError> {
+    let mut files = Vec::new();
+    if path.is_dir() {
+        collect_markdown_files(path, &mut files)?;
+        files.sort();
+    } else {
+        files.push(path.to_path_buf());
+    }
+
+    let mut candidates = Vec::new();
+    let mut skipped = Vec::new();
+    for file in &files {
+        let links = import::parse_markdown_links(&read_import_file(file)?, heading, tasks_only);
+        let (items, file_skipped) = import::convert_records(
+            &links,
+            |link| record_label(&link.name, &link.url),
+            |link| import::markdown_link_to_item(link, item_type, rules),
+        );
+        candidates.extend(items);
+        skipped.extend(file_skipped);
+    }
+    let found = candidates.len();
+
+    let mut staging_items = load_staging()?;
+    let existing = load_all_items()?;
+    let (candidates, duplicates) =
+        import::dedup_candidates(existing.iter().chain(&staging_items), candidates);
+    let staged = candidates.len();
+    let awaiting = staging_items.len() + staged;
+
+    if !dry_run && staged > 0 {
+        staging_items.extend(candidates);
+        save_staging(&staging_items)?;
+    }
+
+    println!(
+        "{} {} link(s) from {} file(s) for review:",
+        if dry_run { "Would stage" } else { "Staged" },
+        staged,
+        files.len()
+    );
+    println!("  {:<34} {}", "links found", found);
+    println!("  {:<34} {}", "duplicates skipped", duplicates);
+    println!("  {:<34} {}", "awaiting review", awaiting);
+    println!("  {:<34} {}", "invalid, skipped", skipped.len());
+    print_skipped(&skipped);
+    Ok(staged)
+}
+
+async fn handle_review_command(config_manager: &ConfigManager) -> Result<(), CliError> {
+    let config = config_manager.get();
+    let summary = folio_tui::run_staging_picker(config).await?;
+
+    if summary.added.is_empty()
+        && summary.duplicates.is_empty()
+        && summary.discarded == 0
+        && summary.remaining == 0
+    {
+        println!("No links are waiting for review");
+        return Ok(());
+    }
+
+    println!("Reviewed staged links:");
+    println!("  {:<34} {}", "added to the inbox", summary.added.len());
+    println!("  {:<34} {}", "already saved", summary.duplicates.len());
+    println!("  {:<34} {}", "discarded", summary.discarded);
+    println!(
+        "  {:<34} {}",
+        "rejected by the cap, still staged", summary.rejected
+    );
+    println!("  {:<34} {}", "awaiting review", summary.remaining);
+
+    if !summary.archived.is_empty() {
+        println!(
+            "Archived to make room (archive_on_overflow = {}):",
+            config.archive_on_overflow.as_string()
+        );
+        for item in &summary.archived {
+            println!("  - {}", item.name);
+        }
+    }
+
+    // Looked up again each time: re-queueing one duplicate renumbers the inbox.
+    for item in &summary.duplicates {
+        let inbox_items = load_items_from_file(get_inbox_path()?)?;
+        let duplicate = folio_core::find_duplicate(
+            &inbox_items,
+            &load_someday()?,
+            &load_archive(ArchiveScope::All)?,
+            item,
+        );
+        if let Some(duplicate) = duplicate {
+            resolve_duplicate(config_manager, &duplicate, inbox_items, &None).await?;
+        }
+    }
+    Ok(())
+}
+
+fn import_org(config_manager: &ConfigManager, file: &Path, dry_run: bool) -> Result<(), CliError> {
+    let entries = import::parse_org(&read_import_file(file)?)?;
+
+    let plan = import::plan_org_import(
+        load_items_from_file(get_inbox_path()?)?,
+        load_someday()?,
+        load_archive(ArchiveScope::All)?,
+        entries,
+        config_manager.get(),
+    )?;
+
+    if !dry_run && plan.updated + plan.created > 0 {
+        save_inbox(&plan.inbox_items)?;
+        save_someday(&plan.someday_items)?;
+        save_archive_scoped(&plan.archive_items, ArchiveScope::All)?;
+    }
+
+    println!(
+        "{} {}:",
+        if dry_run { "Would import" } else { "Imported" },
+        file.display()
+    );
+    println!("  {:<34} {}", "updated", plan.updated);
+    println!("  {:<34} {}", "created", plan.created);
+    println!("  {:<34} {}", "unchanged", plan.unchanged);
+    println!(
+        "  {:<34} {}",
+        "over the inbox cap, to archive", plan.over_cap
+    );
+    println!("  {:<34} {}", "invalid, skipped", plan.skipped.len());
+    print_skipped(&plan.skipped);
+    Ok(())
+}
+
+fn import_clippings(file: &Path, dry_run: bool) -> Result<(), CliError> {
+    let books = import::parse_clippings(&read_import_file(file)?);
+    if books.is_empty() {
+        println!("No highlights found in {}", file.display());
+        return Ok(());
+    }
+
+    let mut inbox_items = load_items_from_file(get_inbox_path()?)?;
+    let mut someday_items = load_someday()?;
+    let mut archive_items = load_archive(ArchiveScope::All)?;
+    let mut changed = [false; 3];
+
+    let mut new_items = Vec::new();
+    let mut skipped = Vec::new();
+    let (mut matched, mut new_highlights) = (0, 0);
+
+    for book in &books {
+        let found = [&mut inbox_items, &mut someday_items, &mut archive_items]
+            .into_iter()
+            .zip(changed.iter_mut())
+            .find_map(|(items, changed)| {
+                items
+                    .iter_mut()
+                    .find(|item| import::title_matches(&item.name, &book.title))
+                    .map(|item| (item, changed))
+            });
+
+        match found {
+            Some((item, changed)) => {
+                let before = item.highlights.clone();
+                new_highlights += import::merge_highlights(item, &book.highlights);
+                *changed |= item.highlights != before;
+                matched += 1;
+            }
+            None => match import::book_to_item(book) {
+                Ok(item) => new_items.push(item),
+                Err(e) => skipped.push(import::skip_reason(&book.title, &e)),
+            },
+        }
+    }
+
+    let (new_items, _) = import::dedup_candidates(
+        inbox_items
+            .iter()
+            .chain(&someday_items)
+            .chain(&archive_items),
+        new_items,
+    );
+    new_highlights += new_items
+        .iter()
+        .map(|item| item.highlights.len())
+        .sum::<usize>();
+
+    if !dry_run {
+        let [inbox_changed, someday_changed, archive_changed] = changed;
+        if inbox_changed {
+            save_inbox(&inbox_items)?;
+        }
+        if someday_changed {
+            save_someday(&someday_items)?;
+        }
+        if archive_changed {
+            save_archive_scoped(&archive_items, ArchiveScope::All)?;
+        }
+        for item in &new_items {
+            append_to_archive(item)?;
+        }
+    }
+
+    println!(
+        "{} {} new highlight(s) from {} book(s):",
+        if dry_run { "Would import" } else { "Imported" },
+        new_highlights,
+        books.len()
+    );
+    println!("  {:<34} {}", "matched existing items", matched);
+    println!(
+        "  {:<34} {}",
+        "new reference items, to archive",
+        new_items.len()
+    );
+    println!("  {:<34} {}", "invalid, skipped", skipped.len());
+    print_skipped(&skipped);
+    Ok(())
+}
+
+fn parse_item_type(item_type: &str) -> Result<ItemType, CliError> {
+    parse_item_type_field(item_type).map_err(|error| CliError::InvalidFields {
+        errors: ValidationErrors {
+            errors: vec![error],
+        },
+    })
+}
+
+fn load_all_items() -> Result<Vec<Item>, CliError> {
+    let mut items = load_items_from_file(get_inbox_path()?)?;
+    items.extend(load_someday()?);
+    items.extend(load_archive(ArchiveScope::All)?);
+    Ok(items)
+}
+
+fn write_export(output: &Option<PathBuf>, contents: &str) -> Result<(), CliError> {
+    match output {
+        Some(path) => std::fs::write(path, contents).map_err(|e| CliError::IoError {
+            message: format!("Failed to write {}: {}", path.display(), e),
+        }),
+        None => {
+            print!("{}", contents);
+            Ok(())
+        }
+    }
+}
+
+fn collect_markdown_notes(dir: &Path, notes: &mut HashMap<usize, PathBuf>) -> Result<(), CliError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| CliError::IoError {
+        message: format!("Failed to read {}: {}", dir.display(), e),
+    })?;
+
+    for entry in entries {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+
+        if hidden {
+            continue;
+        } else if path.is_dir() {
+            collect_markdown_notes(&path, notes)?;
+        } else if path.extension().is_some_and(|ext| ext == "md")
+            && let Ok(contents) = std::fs::read_to_string(&path)
+            && let Some(id) = export::markdown_note_id(&contents)
+        {
+            notes.insert(id, path);
+        }
+    }
+
+    Ok(())
+}
+
+fn export_markdown(dir: &Path) -> Result<(), CliError> {
+    let references: Vec<Item> = load_all_items()?
+        .into_iter()
+        .filter(|item| item.kind == Kind::Reference)
+        .collect();
+
+    std::fs::create_dir_all(dir).map_err(|e| CliError::IoError {
+        message: format!("Failed to create {}: {}", dir.display(), e),
+    })?;
+
+    let mut existing = HashMap::new();
+    collect_markdown_notes(dir, &mut existing)?;
+
+    let (mut created, mut updated, mut unchanged) = (0, 0, 0);
+
+    for item in &references {
+        let contents = export::markdown_note(item);
+
+        let path = match existing.get(&item.id()) {
+            Some(path) => {
+                if std::fs::read_to_string(path).is_ok_and(|current| current == contents) {
+                    unchanged += 1;
+                    continue;
+                }
+                updated += 1;
+                path.clone()
+            }
+            None => {
+                let stem = export::markdown_file_stem(item);
+                let mut path = dir.join(format!("{}.md", stem));
+                if path.exists() {
+                    path = dir.join(format!("{} ({}).md", stem, item.id()));
+                }
+                created += 1;
+                existing.insert(item.id(), path.clone());
+                path
+            }
+        };
+
+        std::fs::write(&path, contents).map_err(|e| CliError::IoError {
+            message: format!("Failed to write {}: {}", path.display(), e),
+        })?;
+    }
+
+    println!(
+        "Exported {} reference item(s) to {}: {} created, {} updated, {} unchanged",
+        references.len(),
+        dir.display(),
+        created,
+        updated,
+        unchanged
+    );
+    Ok(())
+}
+
+async fn handle_export_command(subcommand: &ExportSubcommands) -> Result<(), CliError> {
+    match subcommand {
+        ExportSubcommands::Markdown { dir } => export_markdown(dir),
+        ExportSubcommands::Bibtex { output, status }
+        | ExportSubcommands::CslJson { output, status } => {
+            let papers: Vec<Item> = load_all_items()?
+                .into_iter()
+                .filter(|item| item.item_type == ItemType::AcademicPaper)
+                .collect();
+
+            // Keys are assigned over every paper so filtering never changes an entry's key.
+            let keys = citation_keys(&papers);
+            let cited: Vec<CitedItem> = keys
+                .iter()
+                .zip(&papers)
+                .filter(|(_, item)| matches_list_filters(item, status.as_deref(), None))
+                .map(|(key, item)| CitedItem { key, item })
+                .collect();
+
+            let contents = match subcommand {
+                ExportSubcommands::Bibtex { .. } => export::to_bibtex(&cited),
+                _ => export::to_csl_json(&cited)?,
+            };
+            write_export(output, &contents)?;
+
+            if let Some(path) = output {
+                println!("Exported {} paper(s) to {}", cited.len(), path.display());
+            }
+            Ok(())
+        }
+        ExportSubcommands::Org { output } => {
+            let inbox_items = load_items_from_file(get_inbox_path()?)?;
+            let someday_items = load_someday()?;
+            let archive_items = load_archive(ArchiveScope::All)?;
+
+            let org = export::to_org(&[
+                ("Inbox", &inbox_items),
+                ("Someday", &someday_items),
+                ("Archive", &archive_items),
+            ]);
+            write_export(output, &org)?;
+
+            if let Some(path) = output {
+                println!(
+                    "Exported {} item(s) to {}",
+                    inbox_items.len() + someday_items.len() + archive_items.len(),
+                    path.display()
+                );
+            }
+            Ok(())
+        }
+        ExportSubcommands::Site { dir, title } => {
+            let references: Vec<Item> = load_archive(ArchiveScope::All)?
+                .into_iter()
+                .filter(|item| item.kind == Kind::Reference)
+                .collect();
+
+            std::fs::create_dir_all(dir).map_err(|e| CliError::IoError {
+                message: format!("Failed to create {}: {}", dir.display(), e),
+            })?;
+            let path = dir.join("index.html");
+            write_export(&Some(path.clone()), &export::to_site(&references, title)?)?;
+
+            println!(
+                "Exported {} reference item(s) to {}",
+                references.len(),
+                path.display()
+            );
+            Ok(())
+        }
+        ExportSubcommands::Feed {
+            format,
+            references,
+            tag,
+            title,
+            author,
+            link,
+            output,
+        } => {
+            let format = FeedFormat::from_str(&format.to_lowercase()).map_err(|_| {
+                CliError::ValidationError {
+                    message: format!(
+                        "Invalid feed format '{}'. Valid options are: atom, rss",
+                        format
+                    ),
+                }
+            })?;
+
+            let items: Vec<Item> = load_archive(ArchiveScope::All)?
+                .into_iter()
+                .filter(|item| item.status == Status::Done && item.finished_at.is_some())
+                .filter(|item| !item.tags.iter().any(|tag| tag == DROPPED_TAG))
+                .filter(|item| !references || item.kind == Kind::Reference)
+                .filter(|item| {
+                    tag.as_ref().is_none_or(|tags| {
+                        tags.iter()
+                            .any(|tag| item.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+                    })
+                })
+                .collect();
+
+            let meta = FeedMeta {
+                title: title.clone(),
+                author: author.clone(),
+                link: link.clone(),
+            };
+            write_export(output, &export::to_feed(&items, format, &meta))?;
+
+            if let Some(path) = output {
+                println!("Exported {} entries to {}", items.len(), path.display());
+            }
+            Ok(())
+        }
+        ExportSubcommands::Opml { output, r#type } => {
+            let item_type = parse_item_type(r#type)?;
+
+            let mut seen = HashSet::new();
+            let items: Vec<Item> = load_all_items()?
+                .into_iter()
+                .filter(|item| item.item_type == item_type && !item.link.is_empty())
+                .filter(|item| seen.insert(folio_core::link_key(&item.link)))
+                .collect();
+
+            write_export(output, &export::to_opml(&items, "folio"))?;
+
+            if let Some(path) = output {
+                println!("Exported {} feed(s) to {}", items.len(), path.display());
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn handle_export_table_command(args: &ExportArgs) -> Result<(), CliError> {
+    let format = ExportFormat::from_str(&args.format.to_lowercase()).map_err(|_| {
+        CliError::ValidationError {
+            message: format!(
+                "Invalid export format '{}'. Valid options are: csv, json, jsonl",
+                args.format
+            ),
+        }
+    })?;
+
+    let columns = match &args.columns {
+        Some(columns) => columns
+            .iter()
+            .map(|column| {
+                ExportColumn::from_str(column.trim()).map_err(|_| CliError::ValidationError {
+                    message: format!(
+                        "Unknown column '{}'. Valid columns are: {}",
+                        column,
+                        export::DEFAULT_COLUMNS
+                            .iter()
+                            .map(ExportColumn::as_string)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => export::DEFAULT_COLUMNS.to_vec(),
+    };
+
+    let inbox_items = load_items_from_file(get_inbox_path()?)?;
+    let someday_items = load_someday()?;
+    let archive_items = load_archive(ArchiveScope::All)?;
+
+    let rows: Vec<ExportRow> = [
+        ("inbox", &inbox_items),
+        ("someday", &someday_items),
+        ("archive", &archive_items),
+    ]
+    .into_iter()
+    .flat_map(|(list, items)| items.iter().map(move |item| ExportRow { list, item }))
+    .filter(|row| matches_list_filters(row.item, args.status.as_deref(), args.r#type.as_deref()))
+    .collect();
+
+    write_export(
+        &args.output,
+        &export::export_table(&rows, &columns, format)?,
+    )?;
+
+    if let Some(path) = &args.output {
+        println!("Exported {} item(s) to {}", rows.len(), path.display());
+    }
+    Ok(())
+}
+
+fn run_import(
+    config_manager: &ConfigManager,
+    args: &ImportArgs,
+    candidates: Vec<Item>,
+    skipped: &[String],
+) -> Result<(), CliError> {
+    let overflow =
+        ImportOverflow::from_str(&args.overflow).map_err(|_| CliError::ValidationError {
+            message: format!(
+                "Invalid overflow list '{}'. Valid options are: archive, someday",
+                args.overflow
+            ),
+        })?;
+
+    if candidates.is_empty() {
+        println!("No items found in {}", args.file.display());
+        print_skipped(skipped);
+        return Ok(());
+    }
+
+    let plan = import::plan_import(
+        load_items_from_file(get_inbox_path()?)?,
+        load_someday()?,
+        &load_archive(ArchiveScope::All)?,
+        candidates,
+        overflow,
+        config_manager.get(),
+    )?;
+
+    if !args.dry_run {
+        save_inbox(&plan.inbox_items)?;
+        save_someday(&plan.someday_items)?;
+        for item in &plan.to_archive {
+            append_to_archive(item)?;
+        }
+    }
+
+    println!(
+        "{} {} item(s):",
+        if args.dry_run {
+            "Would import"
+        } else {
+            "Imported"
+        },
+        plan.added_to_inbox + plan.added_to_someday + plan.added_to_archive + plan.already_read
+    );
+    println!("  {:<34} {}", "unread, to inbox", plan.added_to_inbox);
+    println!("  {:<34} {}", "unread, to someday", plan.added_to_someday);
+    println!(
+        "  {:<34} {}",
+        "unread, over the cap, to archive", plan.added_to_archive
+    );
+    println!("  {:<34} {}", "already read, to archive", plan.already_read);
+    println!("  {:<34} {}", "duplicates skipped", plan.duplicates);
+    println!("  {:<34} {}", "invalid, skipped", skipped.len());
+    print_skipped(skipped);
+    Ok(())
+}
+
+fn record_label(title: &str, link: &str) -> String {
+    if title.trim().is_empty() { link } else { title }.to_string()
+}
+
+fn print_skipped(skipped: &[String]) {
+    if !skipped.is_empty() {
+        println!("Skipped:");
+        for reason in skipped {
+            println!("  - {}", reason);
+        }
+    }
+}
+
+async fn prompt_for_input(field_name: &str) -> Result<String, CliError> {
+    use std::io::{stdin, stdout};
+
+    print!("{}: ", field_name);
+    stdout().flush()?;
+
+    let mut input = String::new();
     stdin().read_line(&mut input)?;
     Ok(input.trim().to_string())
 }
 
+async fn handle_add_batch_command(
+    config_manager: &ConfigManager,
+    from_file: Option<&Path>,
+    item_type: &Option<String>,
+) -> Result<(), CliError> {
+    let config = config_manager.get();
+
+    let text = match from_file {
+        Some(file) => read_import_file(file)?,
+        None => {
+            let mut text = String::new();
+            std::io::stdin().read_to_string(&mut text)?;
+            text
+        }
+    };
+    let default_type = item_type.as_deref().map(parse_item_type).transpose()?;
+    let rules = TypeRules::from_config(config);
+
+    let (candidates, invalid) = import::parse_link_lines(&text, default_type.as_ref(), &rules);
+
+    let inbox_items = load_items_from_file(get_inbox_path()?)?;
+    let someday_items = load_someday()?;
+    let archive_items = load_archive(ArchiveScope::All)?;
+    let (candidates, duplicates) = import::dedup_candidates(
+        inbox_items
+            .iter()
+            .chain(&someday_items)
+            .chain(&archive_items),
+        candidates,
+    );
+
+    let outcome = folio_core::add_items_to_inbox(inbox_items, candidates, config)?;
+
+    if !outcome.added.is_empty() {
+        save_inbox(&outcome.inbox)?;
+        for item in &outcome.archived {
+            append_to_archive(item)?;
+        }
+    }
+
+    println!(
+        "Added {} of {} link(s) to the inbox:",
+        outcome.added.len(),
+        outcome.added.len() + duplicates + outcome.rejected.len() + invalid.len()
+    );
+    println!("  {:<24} {}", "added", outcome.added.len());
+    println!("  {:<24} {}", "duplicates skipped", duplicates);
+    println!("  {:<24} {}", "rejected by the cap", outcome.rejected.len());
+    println!("  {:<24} {}", "invalid lines", invalid.len());
+
+    if !outcome.archived.is_empty() {
+        println!(
+            "Archived to make room (archive_on_overflow = {}):",
+            config.archive_on_overflow.as_string()
+        );
+        for item in &outcome.archived {
+            println!("  - {}", item.name);
+        }
+    }
+    if !outcome.rejected.is_empty() {
+        println!(
+            "Not added, the inbox is at its limit of {}:",
+            config.max_items
+        );
+        for item in &outcome.rejected {
+            println!("  - {}", item.link);
+        }
+    }
+    for error in &invalid {
+        println!("Line {}: {}", error.line, error.message);
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn handle_add_command(
     config_manager: &ConfigManager,
     name: &Option<String>,
@@ -395,9 +1915,19 @@ async fn handle_add_command(
     link: &Option<String>,
     note: &Option<String>,
     kind: &Option<String>,
+    minutes: &Option<String>,
+    evict: &[usize],
+    evict_action: &str,
+    someday: bool,
+    on_duplicate: &Option<String>,
 ) -> Result<(), CliError> {
-    if name.is_none() {
-        folio_tui::run_tui_add_form().await?;
+    if name.is_none() && link.is_none() {
+        if someday {
+            return Err(CliError::ValidationError {
+                message: "--someday requires --name or --link".to_string(),
+            });
+        }
+        folio_tui::run_tui_add_form(config_manager.overrides().to_vec()).await?;
         return Ok(());
     }
 
@@ -406,14 +1936,132 @@ async fn handle_add_command(
     let inbox_path = get_inbox_path()?;
     let inbox_items = load_items_from_file(&inbox_path)?;
 
-    let new_item = folio_core::create_item(
-        name.clone().unwrap_or_else(|| "Untitled".to_string()),
-        item_type.clone(),
-        author.clone(),
+    // Without a name the page itself is asked for its title, author and type, and with
+    // auto_snapshot on the same download is kept as the item's snapshot.
+    let page = match link {
+        Some(link) if name.is_none() || config.auto_snapshot => fetch_page(config, link).await,
+        _ => None,
+    };
+    let page_metadata = page.as_ref().map(|page| parse_page_metadata(&page.html));
+    let metadata = match (name, &page_metadata) {
+        (None, Some(metadata)) => metadata.clone(),
+        _ => PageMetadata::default(),
+    };
+
+    let inferred_type = match (item_type, link) {
+        (None, Some(link)) => TypeRules::from_config(config).infer(link),
+        _ => None,
+    };
+    let page_type = match (item_type, &inferred_type) {
+        (None, None) => metadata.item_type.clone(),
+        _ => None,
+    };
+
+    let mut errors = ValidationErrors::default();
+    let estimated_minutes = match minutes.as_deref().map(parse_minutes_field) {
+        Some(Ok(minutes)) => minutes,
+        Some(Err(error)) => {
+            errors.errors.push(error);
+            None
+        }
+        None => None,
+    };
+
+    let created = folio_core::create_item(
+        name.clone()
+            .or(metadata.title.clone())
+            .or_else(|| link.clone())
+            .unwrap_or_default(),
+        item_type.clone().or_else(|| {
+            inferred_type
+                .as_ref()
+                .or(page_type.as_ref())
+                .map(|t| t.as_string().to_string())
+        }),
+        author.clone().or(metadata.author.clone()),
         link.clone(),
         note.clone(),
         kind.clone(),
-    )?;
+    );
+    if let Err(CoreError::InvalidFields(found)) = &created {
+        errors.errors.extend(found.errors.iter().cloned());
+    }
+    errors.into_result()?;
+    let mut new_item = created?;
+
+    let snapshot = page
+        .filter(|_| config.auto_snapshot)
+        .map(|page| Snapshot::new(page.url, extract_article(&page.html)))
+        .filter(|snapshot| !snapshot.text.is_empty());
+
+    let derived_minutes = estimate_minutes(
+        &new_item.item_type,
+        page_metadata.and_then(|metadata| metadata.duration_seconds),
+        snapshot.as_ref().map(Snapshot::word_count),
+    );
+    new_item.estimated_minutes = estimated_minutes.or(derived_minutes);
+
+    if name.is_none() {
+        println!("Name: {}", new_item.name);
+        if author.is_none() && !new_item.author.is_empty() {
+            println!("Author: {}", new_item.author);
+        }
+    }
+    if let Some(inferred_type) = &inferred_type {
+        println!("Type detected from the link: {}", inferred_type.as_string());
+    }
+    if let Some(page_type) = &page_type {
+        println!("Type detected from the page: {}", page_type.as_string());
+    }
+    if estimated_minutes.is_none()
+        && let Some(minutes) = derived_minutes
+    {
+        println!("Estimated time: {}", format_minutes(minutes));
+    }
+
+    let saved_item = new_item.clone();
+    let added = store_new_item(
+        config_manager,
+        inbox_items,
+        new_item,
+        evict,
+        evict_action,
+        someday,
+        on_duplicate,
+    )
+    .await?;
+
+    if added && let Some(snapshot) = snapshot {
+        save_snapshot(&saved_item, &snapshot)?;
+        println!("Snapshot saved ({} words)", snapshot.word_count());
+    }
+    Ok(())
+}
+
+const EVICT_IGNORED: &str = "Nothing was evicted: `--evict` only applies when the inbox is full and archive_on_overflow is abort";
+
+// Saves a newly created item to the archive, someday list or inbox, and reports whether it
+// was saved (a duplicate or a cancelled eviction leaves everything as it was).
+async fn store_new_item(
+    config_manager: &ConfigManager,
+    inbox_items: Vec<Item>,
+    new_item: Item,
+    evict: &[usize],
+    evict_action: &str,
+    someday: bool,
+    on_duplicate: &Option<String>,
+) -> Result<bool, CliError> {
+    let config = config_manager.get();
+
+    // References and someday items are checked too, so a link is only ever saved once.
+    let someday_items = load_someday()?;
+    let archive_items = load_archive(ArchiveScope::All)?;
+    if let Some(duplicate) =
+        folio_core::find_duplicate(&inbox_items, &someday_items, &archive_items, &new_item)
+    {
+        resolve_duplicate(config_manager, &duplicate, inbox_items, on_duplicate).await?;
+        return Ok(false);
+    }
 
     if matches!(new_item.kind, Kind::Reference) {
         let mut ref_item = new_item;
@@ -421,10 +2069,44 @@ async fn handle_add_command(
         folio_core::update_timestamps(&mut ref_item);
         append_to_archive(&ref_item)?;
         println!("Added reference item as done and archived");
-        return Ok(());
+        if !evict.is_empty() {
+            println!("{}", EVICT_IGNORED);
+        }
+        return Ok(true);
     }
 
-    match folio_core::add_item_to_inbox(inbox_items, new_item, config) {
+    if someday {
+        return match folio_core::add_item_to_someday(someday_items, new_item, config) {
+            Ok((new_someday, to_archive)) => {
+                save_someday(&new_someday)?;
+
+                for item in &to_archive {
+                    append_to_archive(item)?;
+                }
+
+                println!("Item added to someday");
+                for item in to_archive {
+                    println!("  - archived due to overflow: {}", item.name);
+                }
+                Ok(true)
+            }
+            Err(folio_core::CoreError::SomedayFull) => Err(CliError::ValidationError {
+                message: format!(
+                    "Someday limit ({}) reached. Promote or delete an item, raise `someday_max_items`, or change `someday_on_overflow`.",
+                    config.someday_max_items
+                ),
+            }),
+            Err(e) => Err(e.into()),
+        };
+    }
+
+    match folio_core::add_item_to_inbox(
+        inbox_items.clone(),
+        new_item.clone(),
+        &someday_items,
+        &archive_items,
+        config,
+    ) {
         Ok((new_inbox, to_archive)) => {
             let has_archived_items = !to_archive.is_empty();
 
@@ -444,142 +2126,343 @@ async fn handle_add_command(
             } else {
                 println!("Item added successfully");
             }
-            Ok(())
+            if !evict.is_empty() {
+                println!("{}", EVICT_IGNORED);
+            }
+            Ok(true)
         }
-        Err(_) => {
-            match &config.archive_on_overflow {
-                OverflowStrategy::Abort => {
-                    println!("Inbox limit ({}) reached.", config.max_items);
-                    println!();
-                    println!("Choose an action:");
-                    println!("  [D]elete an existing item (use 'folio delete <id>')");
-                    println!(
-                        "  [A]rchive an item (change status to 'done' or use 'folio archive <id>')"
-                    );
-                    println!("  [I]ncrease inbox size: `folio config set max_items N`");
-                    println!(
-                        "  [C]hange overflow strategy: `folio config set archive_on_overflow [todo|any]`"
-                    );
-                    println!();
-                    println!("Would you like to see the current inbox items? (y/N): ");
-
-                    use std::io::{Write, stdin, stdout};
-                    stdout().flush()?;
-                    let mut input = String::new();
-                    tokio::task::block_in_place(|| stdin().read_line(&mut input))?;
-                    let response = input.trim().to_lowercase();
-
-                    if response == "y" || response == "yes" {
-                        let inbox_items = load_items_from_file(&inbox_path)?;
-                        if !inbox_items.is_empty() {
-                            println!();
-                            println!("{}", folio_core::Item::format_list_header());
-                            println!("{}", folio_core::Item::format_list_separator());
-
-                            for (index, item) in inbox_items.iter().enumerate() {
-                                println!("{}", item.format_for_list(index + 1));
-                            }
-                        }
+        Err(folio_core::CoreError::InboxFull) => {
+            let needed = (inbox_items.len() + 1).saturating_sub(config.max_items as usize);
+
+            let evictions = if !evict.is_empty() {
+                let action =
+                    EvictAction::from_str(evict_action).map_err(|_| CliError::ValidationError {
+                        message: format!(
+                            "Invalid evict action '{}'. Valid options are: archive, drop, delete",
+                            evict_action
+                        ),
+                    })?;
+                parse_evictions(evict, action, inbox_items.len())?
+            } else if std::io::stdin().is_terminal() {
+                match prompt_for_evictions(&inbox_items, config.max_items, needed).await? {
+                    Some(evictions) => evictions,
+                    None => {
+                        println!("Add cancelled");
+                        return Ok(false);
                     }
-
-                    std::process::exit(1);
                 }
-                _ => {
-                    // This shouldn't happen since we only return Err for Abort strategy
-                    // But just in case, handle it gracefully
-                    Err(CliError::InboxFull {
+            } else {
+                return Err(CliError::InboxFull {
+                    limit: config.max_items,
+                    suggestions: format!(
+                        "Free up {} slot(s) with `--evict <id>`, raise `max_items`, or change `archive_on_overflow`.",
+                        needed
+                    ),
+                });
+            };
+
+            let outcome = match folio_core::add_item_to_inbox_with_evictions(
+                inbox_items,
+                new_item,
+                &someday_items,
+                &archive_items,
+                &evictions,
+                config,
+            ) {
+                Ok(outcome) => outcome,
+                Err(folio_core::CoreError::InboxFull) => {
+                    return Err(CliError::InboxFull {
                         limit: config.max_items,
-                        suggestions: "This should not happen with current overflow strategies."
-                            .to_string(),
-                    })
+                        suggestions: format!(
+                            "At least {} item(s) must be evicted to make room.",
+                            needed
+                        ),
+                    });
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            for item in &outcome.archived {
+                append_to_archive(item)?;
+            }
+            save_inbox(&outcome.inbox)?;
+
+            println!("Item added successfully");
+            for item in &outcome.archived {
+                if item.tags.iter().any(|tag| tag == DROPPED_TAG) {
+                    println!("  - dropped: {}", item.name);
+                } else {
+                    println!("  - archived: {}", item.name);
                 }
             }
+            for item in &outcome.deleted {
+                remove_snapshot(item)?;
+                println!("  - deleted: {}", item.name);
+            }
+            Ok(true)
         }
+        Err(e) => Err(e.into()),
     }
 }
 
-async fn handle_config_command(subcommand: &ConfigSubcommands) -> Result<(), CliError> {
-    match subcommand {
-        ConfigSubcommands::List => {
-            let config_manager = ConfigManager::new().map_err(|e| CliError::ConfigError {
-                message: e.to_string(),
-            })?;
-            let json = serde_json::to_string_pretty(config_manager.get())?;
-            println!("{}", json);
+async fn fetch_page(config: &Config, link: &str) -> Option<Page> {
+    if !folio_core::is_supported_link(link) {
+        return None;
+    }
+
+    let result = match Fetcher::from_config(config) {
+        Ok(fetcher) => fetcher.fetch_page(link).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(page) => Some(page),
+        Err(FetchError::Offline) => None,
+        Err(e) => {
+            println!("Could not fetch the page: {}", e);
+            None
         }
-        ConfigSubcommands::Get { key } => {
-            let config_manager = ConfigManager::new().map_err(|e| CliError::ConfigError {
-                message: e.to_string(),
-            })?;
-            let config_value = serde_json::to_value(config_manager.get())?;
+    }
+}
 
-            match key.as_str() {
-                "max_items" => println!("{}", config_value["max_items"]),
-                "archive_on_overflow" => println!("{}", config_value["archive_on_overflow"]),
-                "version" | "_v" => println!("{}", config_value["_v"]),
-                _ => {
-                    return Err(CliError::UnknownConfigKey { key: key.clone() });
+async fn resolve_duplicate(
+    config_manager: &ConfigManager,
+    duplicate: &Duplicate,
+    inbox_items: Vec<Item>,
+    on_duplicate: &Option<String>,
+) -> Result<(), CliError> {
+    let id = duplicate.display_id(inbox_items.len());
+    let place = match duplicate.location {
+        DuplicateLocation::Inbox(_) => "inbox",
+        DuplicateLocation::Someday(_) => "someday list",
+        DuplicateLocation::Archive(_) => "archive",
+    };
+
+    let action = match on_duplicate {
+        Some(action) => {
+            DuplicateAction::from_str(action).map_err(|_| CliError::ValidationError {
+                message: format!(
+                    "Invalid duplicate action '{}'. Valid options are: open, requeue, skip",
+                    action
+                ),
+            })?
+        }
+        None if std::io::stdin().is_terminal() => {
+            println!(
+                "Already saved as #{} in the {} ({}): {}",
+                id,
+                place,
+                duplicate.item.status.as_string(),
+                duplicate.item.name
+            );
+            loop {
+                let input = prompt_for_input("[o]pen it, [r]e-queue it or [s]kip").await?;
+                match input.to_lowercase().as_str() {
+                    "o" | "open" => break DuplicateAction::Open,
+                    "r" | "requeue" | "re-queue" => break DuplicateAction::Requeue,
+                    "s" | "skip" | "" => break DuplicateAction::Skip,
+                    _ => println!("Enter o, r or s"),
                 }
             }
         }
-        ConfigSubcommands::Set { key, value } => {
-            let mut config_manager = ConfigManager::new().map_err(|e| CliError::ConfigError {
-                message: e.to_string(),
+        None => {
+            return Err(CliError::ValidationError {
+                message: format!(
+                    "'{}' is already saved as #{} in the {}. Use `--on-duplicate open|requeue|skip` to choose what to do.",
+                    duplicate.item.name, id, place
+                ),
+            });
+        }
+    };
+
+    match (action, duplicate.location) {
+        (DuplicateAction::Open, _) => {
+            opener::open(&duplicate.item.link).map_err(|e| CliError::IoError {
+                message: format!("Could not open {}: {}", duplicate.item.link, e),
             })?;
+            println!("Opened item #{}, nothing added", id);
+        }
+        (DuplicateAction::Requeue, DuplicateLocation::Inbox(index)) => {
+            let inbox_items = folio_core::requeue_inbox_item(inbox_items, index);
+            save_inbox(&inbox_items)?;
+            println!(
+                "Item #{} moved to the back of the inbox as #{}",
+                id,
+                inbox_items.len()
+            );
+        }
+        (DuplicateAction::Requeue, DuplicateLocation::Someday(_)) => {
+            handle_promote_command(config_manager, id).await?;
+        }
+        (DuplicateAction::Requeue, DuplicateLocation::Archive(_)) => {
+            handle_set_status_command(config_manager, id, "todo").await?;
+        }
+        (DuplicateAction::Skip, _) => {
+            println!("Skipped, item #{} is already saved", id);
+        }
+    }
+    Ok(())
+}
 
-            config_manager
-                .update(|config| match key.as_str() {
-                    "max_items" => {
-                        if let Ok(val) = value.parse::<u32>() {
-                            config.max_items = val;
-                        }
-                    }
-                    "archive_on_overflow" => match value.as_str() {
-                        "abort" => config.archive_on_overflow = OverflowStrategy::Abort,
-                        "todo" => config.archive_on_overflow = OverflowStrategy::Todo,
-                        "any" => config.archive_on_overflow = OverflowStrategy::Any,
-                        _ => {}
-                    },
-                    _ => {}
+fn parse_evictions(
+    ids: &[usize],
+    action: EvictAction,
+    inbox_len: usize,
+) -> Result<Vec<Eviction>, CliError> {
+    ids.iter()
+        .map(|&id| {
+            if id == 0 || id > inbox_len {
+                Err(CliError::ValidationError {
+                    message: format!(
+                        "Item #{} is not in the inbox (valid IDs: 1-{})",
+                        id, inbox_len
+                    ),
+                })
+            } else {
+                Ok(Eviction {
+                    position: id - 1,
+                    action,
                 })
-                .map_err(|e| CliError::ConfigError {
+            }
+        })
+        .collect()
+}
+
+async fn prompt_for_evictions(
+    inbox_items: &[Item],
+    limit: u32,
+    needed: usize,
+) -> Result<Option<Vec<Eviction>>, CliError> {
+    println!(
+        "Inbox limit ({}) reached. Free up {} slot(s) to add this item.",
+        limit, needed
+    );
+    println!();
+    println!("{}", Item::format_list_header());
+    println!("{}", Item::format_list_separator());
+    for (index, item) in inbox_items.iter().enumerate() {
+        println!("{}", item.format_for_list(index + 1));
+    }
+    println!();
+
+    loop {
+        let input = prompt_for_input("Items to remove (e.g. 3 or 2,5; empty to cancel)").await?;
+        if input.is_empty() {
+            return Ok(None);
+        }
+
+        let ids: Result<Vec<usize>, _> = input
+            .split(',')
+            .map(|id| id.trim().parse::<usize>())
+            .collect();
+        let mut ids = match ids {
+            Ok(ids) => ids,
+            Err(_) => {
+                println!("Enter item numbers separated by commas");
+                continue;
+            }
+        };
+        ids.sort_unstable();
+        ids.dedup();
+
+        if ids.len() < needed {
+            println!("Select at least {} item(s)", needed);
+            continue;
+        }
+
+        let action = loop {
+            let choice = prompt_for_input(
+                "[a]rchive as done, [d]rop to archive as done and tagged dropped, or [x] delete permanently",
+            )
+            .await?;
+            match choice.to_lowercase().as_str() {
+                "a" | "archive" => break EvictAction::Archive,
+                "d" | "drop" => break EvictAction::Drop,
+                "x" | "delete" => break EvictAction::Delete,
+                "" => return Ok(None),
+                _ => println!("Choose a, d or x"),
+            }
+        };
+
+        match parse_evictions(&ids, action, inbox_items.len()) {
+            Ok(evictions) => return Ok(Some(evictions)),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
+async fn handle_config_command(
+    subcommand: &ConfigSubcommands,
+    overrides: Vec<(String, String)>,
+) -> Result<(), CliError> {
+    match subcommand {
+        ConfigSubcommands::List => {
+            let config_manager =
+                ConfigManager::with_overrides(overrides).map_err(|e| CliError::ConfigError {
                     message: e.to_string(),
                 })?;
+            let config = config_manager.get();
 
-            match key.as_str() {
-                "max_items" => {
-                    if value.parse::<u32>().is_err() {
-                        return Err(CliError::InvalidMaxItems {
-                            value: value.clone(),
-                        });
-                    }
-                }
-                "archive_on_overflow" => {
-                    if !matches!(value.as_str(), "abort" | "todo" | "any") {
-                        return Err(CliError::InvalidOverflowStrategy {
-                            value: value.to_string(),
-                        });
-                    }
-                }
-                _ => {
-                    return Err(CliError::UnknownConfigKey { key: key.clone() });
-                }
+            for field in CONFIG_SCHEMA {
+                println!(
+                    "{:<22} {:<10} ({})",
+                    field.key,
+                    field.get(config),
+                    config_manager.source(field.key).as_string()
+                );
             }
+        }
+        ConfigSubcommands::Get { key } => {
+            let config_manager =
+                ConfigManager::with_overrides(overrides).map_err(|e| CliError::ConfigError {
+                    message: e.to_string(),
+                })?;
 
-            println!("Config updated successfully");
+            match key.as_str() {
+                "version" | "_v" => println!("{}", config_manager.get().version),
+                _ => println!("{}", config_manager.get().get_value(key)?),
+            }
         }
-        ConfigSubcommands::Reset => {
-            let mut config_manager = ConfigManager::new().map_err(|e| CliError::ConfigError {
+        ConfigSubcommands::Set { key, value } => {
+            folio_core::config_field(key)?.validate(value)?;
+
+            // Only the file is read and written here, so a bad FOLIO_* variable in the
+            // environment can't stop the value from being saved or leak into the file.
+            let mut config = folio_storage::load_config().map_err(|e| CliError::ConfigError {
                 message: e.to_string(),
             })?;
+            config.set_value(key, value)?;
+            config.validate()?;
 
-            config_manager
-                .update(|config| {
-                    *config = folio_core::Config::default();
-                })
-                .map_err(|e| CliError::ConfigError {
+            folio_storage::save_config(&config).map_err(|e| CliError::ConfigError {
+                message: e.to_string(),
+            })?;
+
+            println!("Config updated successfully");
+        }
+        ConfigSubcommands::Describe { key } => {
+            let fields = match key {
+                Some(key) => vec![folio_core::config_field(key)?],
+                None => CONFIG_SCHEMA.iter().collect(),
+            };
+
+            for (index, field) in fields.iter().enumerate() {
+                if index > 0 {
+                    println!();
+                }
+                println!("{}", field.key);
+                println!("  {}", field.help);
+                println!("  Type:    {}", field.kind.type_name());
+                println!("  Default: {}", field.default);
+                println!("  Allowed: {}", field.kind.allowed());
+                println!("  Env:     {}", field.env_var());
+            }
+        }
+        ConfigSubcommands::Reset => {
+            folio_storage::save_config(&folio_core::Config::default()).map_err(|e| {
+                CliError::ConfigError {
                     message: e.to_string(),
-                })?;
+                }
+            })?;
 
             println!("Config reset to default values");
         }
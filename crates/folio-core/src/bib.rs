@@ -0,0 +1,115 @@
+use crate::Item;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BibFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arxiv_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub venue: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<i32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+}
+
+impl BibFields {
+    pub fn is_empty(&self) -> bool {
+        *self == BibFields::default()
+    }
+}
+
+const TITLE_STOPWORDS: &[&str] = &[
+    "a", "an", "the", "on", "of", "in", "for", "to", "and", "with", "from", "towards", "toward",
+];
+
+fn ascii_word(word: &str) -> String {
+    word.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+pub fn item_authors(item: &Item) -> Vec<String> {
+    match &item.bib {
+        Some(bib) if !bib.authors.is_empty() => bib.authors.clone(),
+        _ if item.author.trim().is_empty() => Vec::new(),
+        _ => item
+            .author
+            .split([',', ';'])
+            .map(str::trim)
+            .filter(|author| !author.is_empty())
+            .map(str::to_string)
+            .collect(),
+    }
+}
+
+pub fn split_name(author: &str) -> (String, String) {
+    match author.split_once(',') {
+        Some((family, given)) => (family.trim().to_string(), given.trim().to_string()),
+        None => match author.trim().rsplit_once(' ') {
+            Some((given, family)) => (family.trim().to_string(), given.trim().to_string()),
+            None => (author.trim().to_string(), String::new()),
+        },
+    }
+}
+
+fn base_citation_key(item: &Item) -> String {
+    let family = item_authors(item)
+        .first()
+        .map(|author| ascii_word(&split_name(author).0))
+        .filter(|family| !family.is_empty())
+        .unwrap_or_else(|| "anon".to_string());
+
+    let year = item
+        .bib
+        .as_ref()
+        .and_then(|bib| bib.year)
+        .map(|year| year.to_string())
+        .unwrap_or_default();
+
+    let word = item
+        .name
+        .split_whitespace()
+        .map(ascii_word)
+        .find(|word| !word.is_empty() && !TITLE_STOPWORDS.contains(&word.as_str()))
+        .unwrap_or_default();
+
+    format!("{}{}{}", family, year, word)
+}
+
+// Keys only depend on the items themselves, ordered by id, so the same library always
+// produces the same keys and a colliding key gets the same suffix every time.
+pub fn citation_keys(items: &[Item]) -> Vec<String> {
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by_key(|&index| items[index].id());
+
+    let mut keys = vec![String::new(); items.len()];
+    let mut used: HashMap<String, usize> = HashMap::new();
+
+    for index in order {
+        let base = base_citation_key(&items[index]);
+        let count = used.entry(base.clone()).or_default();
+
+        keys[index] = if *count == 0 {
+            base
+        } else {
+            let mut suffix = String::new();
+            let mut n = *count;
+            loop {
+                suffix.insert(0, (b'a' + (n - 1) as u8 % 26) as char);
+                if n <= 26 {
+                    break;
+                }
+                n = (n - 1) / 26;
+            }
+            format!("{}{}", base, suffix)
+        };
+        *count += 1;
+    }
+
+    keys
+}
@@ -1,4 +1,5 @@
 use crate::{CapError, Item, OverflowStrategy, Status};
+use strum::EnumString;
 
 pub fn add_with_cap(
     mut inbox: Vec<Item>,
@@ -37,3 +38,236 @@ pub fn add_with_cap(
         }
     }
 }
+
+#[derive(Debug, Default)]
+pub struct BatchOutcome {
+    pub inbox: Vec<Item>,
+    pub added: Vec<Item>,
+    pub archived: Vec<Item>,
+    pub rejected: Vec<Item>,
+}
+
+// The overflow strategy is applied once for the whole batch: room is made by evicting
+// existing items up front, and whatever still doesn't fit is rejected rather than evicting
+// items added earlier in the same batch.
+pub fn add_batch_with_cap(
+    mut inbox: Vec<Item>,
+    new_items: Vec<Item>,
+    max: usize,
+    strat: OverflowStrategy,
+) -> BatchOutcome {
+    let needed = (inbox.len() + new_items.len()).saturating_sub(max);
+    let mut archived = Vec::new();
+
+    if needed > 0 {
+        let evictable: Vec<usize> = match strat {
+            OverflowStrategy::Abort => Vec::new(),
+            OverflowStrategy::Todo => inbox
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| item.status == Status::Todo)
+                .map(|(pos, _)| pos)
+                .take(needed)
+                .collect(),
+            OverflowStrategy::Any => (0..inbox.len().min(needed)).collect(),
+        };
+
+        for pos in evictable.into_iter().rev() {
+            let mut removed = inbox.remove(pos);
+            removed.status = Status::Done;
+            crate::status::update_timestamps(&mut removed);
+            archived.push(removed);
+        }
+        archived.reverse();
+    }
+
+    let room = max.saturating_sub(inbox.len());
+    let mut added = new_items;
+    let rejected = added.split_off(room.min(added.len()));
+    inbox.extend(added.iter().cloned());
+
+    BatchOutcome {
+        inbox,
+        added,
+        archived,
+        rejected,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+pub enum EvictAction {
+    #[strum(serialize = "archive")]
+    Archive,
+    #[strum(serialize = "drop")]
+    Drop,
+    #[strum(serialize = "delete")]
+    Delete,
+}
+
+impl EvictAction {
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            EvictAction::Archive => "archive",
+            EvictAction::Drop => "drop",
+            EvictAction::Delete => "delete",
+        }
+    }
+}
+
+// Dropped items are archived as done like any other, and carry this tag so they can be told
+// apart from the ones that were read.
+pub const DROPPED_TAG: &str = "dropped";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eviction {
+    pub position: usize,
+    pub action: EvictAction,
+}
+
+#[derive(Debug)]
+pub struct EvictionOutcome {
+    pub inbox: Vec<Item>,
+    pub archived: Vec<Item>,
+    pub deleted: Vec<Item>,
+}
+
+pub fn add_with_evictions(
+    mut inbox: Vec<Item>,
+    new_item: Item,
+    evictions: &[Eviction],
+    max: usize,
+) -> Result<EvictionOutcome, CapError> {
+    let mut positions: Vec<&Eviction> = evictions.iter().collect();
+    positions.sort_by_key(|eviction| std::cmp::Reverse(eviction.position));
+    positions.dedup_by_key(|eviction| eviction.position);
+
+    if let Some(eviction) = positions.iter().find(|e| e.position >= inbox.len()) {
+        return Err(CapError::InvalidEviction {
+            position: eviction.position,
+        });
+    }
+
+    let mut archived = Vec::new();
+    let mut deleted = Vec::new();
+
+    for eviction in positions {
+        let mut removed = inbox.remove(eviction.position);
+        match eviction.action {
+            EvictAction::Archive | EvictAction::Drop => {
+                removed.status = Status::Done;
+                crate::status::update_timestamps(&mut removed);
+                if eviction.action == EvictAction::Drop
+                    && !removed.tags.iter().any(|tag| tag == DROPPED_TAG)
+                {
+                    removed.tags.push(DROPPED_TAG.to_string());
+                }
+                archived.push(removed);
+            }
+            EvictAction::Delete => deleted.push(removed),
+        }
+    }
+
+    if inbox.len() >= max {
+        return Err(CapError::Full);
+    }
+
+    archived.reverse();
+    deleted.reverse();
+    inbox.push(new_item);
+
+    Ok(EvictionOutcome {
+        inbox,
+        archived,
+        deleted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inbox(names: &[&str]) -> Vec<Item> {
+        names
+            .iter()
+            .map(|name| crate::create_item(name.to_string(), None, None, None, None, None).unwrap())
+            .collect()
+    }
+
+    fn evict(position: usize, action: EvictAction) -> Eviction {
+        Eviction { position, action }
+    }
+
+    #[test]
+    fn archived_items_are_done_without_the_dropped_tag() {
+        let new_item = inbox(&["new"]).remove(0);
+        let outcome = add_with_evictions(
+            inbox(&["a", "b"]),
+            new_item,
+            &[evict(0, EvictAction::Archive)],
+            2,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.archived.len(), 1);
+        assert_eq!(outcome.archived[0].status, Status::Done);
+        assert!(outcome.archived[0].finished_at.is_some());
+        assert!(outcome.archived[0].tags.is_empty());
+        let names: Vec<&str> = outcome.inbox.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["b", "new"]);
+    }
+
+    #[test]
+    fn dropped_items_are_done_and_tagged() {
+        let mut items = inbox(&["a", "b"]);
+        items[1].tags.push(DROPPED_TAG.to_string());
+        let new_item = inbox(&["new"]).remove(0);
+        let outcome = add_with_evictions(
+            items,
+            new_item,
+            &[evict(0, EvictAction::Drop), evict(1, EvictAction::Drop)],
+            2,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.archived.len(), 2);
+        for item in &outcome.archived {
+            assert_eq!(item.status, Status::Done);
+            assert_eq!(item.tags, [DROPPED_TAG]);
+        }
+        assert_eq!(outcome.archived[0].name, "a");
+    }
+
+    #[test]
+    fn deleted_items_are_not_archived() {
+        let new_item = inbox(&["new"]).remove(0);
+        let outcome = add_with_evictions(
+            inbox(&["a", "b"]),
+            new_item,
+            &[evict(1, EvictAction::Delete)],
+            2,
+        )
+        .unwrap();
+
+        assert!(outcome.archived.is_empty());
+        assert_eq!(outcome.deleted[0].name, "b");
+        assert_eq!(outcome.deleted[0].status, Status::Todo);
+    }
+
+    #[test]
+    fn too_few_evictions_or_unknown_positions_are_rejected() {
+        let new_item = inbox(&["new"]).remove(0);
+        let full = add_with_evictions(inbox(&["a", "b", "c"]), new_item.clone(), &[], 2);
+        assert!(matches!(full, Err(CapError::Full)));
+
+        let unknown = add_with_evictions(
+            inbox(&["a", "b"]),
+            new_item,
+            &[evict(5, EvictAction::Archive)],
+            2,
+        );
+        assert!(matches!(
+            unknown,
+            Err(CapError::InvalidEviction { position: 5 })
+        ));
+    }
+}
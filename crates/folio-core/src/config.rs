@@ -1,32 +1,316 @@
+use crate::ConfigError;
+use crate::infer::{TypeRule, parse_type_rule};
+use crate::is_supported_link;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use strum::EnumString;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub max_items: u32,
 
     pub archive_on_overflow: OverflowStrategy,
 
+    pub someday_max_items: u32,
+
+    pub someday_on_overflow: OverflowStrategy,
+
+    pub type_rules: Vec<TypeRule>,
+
+    pub offline: bool,
+
+    pub fetch_timeout: u32,
+
+    pub auto_snapshot: bool,
+
+    pub wayback_api: String,
+
     #[serde(rename = "_v")]
     pub version: u8,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, EnumString)]
 pub enum OverflowStrategy {
     #[serde(rename = "abort")]
+    #[strum(serialize = "abort")]
     Abort,
     #[serde(rename = "todo")]
+    #[strum(serialize = "todo")]
     Todo,
     #[serde(rename = "any")]
+    #[strum(serialize = "any")]
     Any,
 }
 
+impl OverflowStrategy {
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            OverflowStrategy::Abort => "abort",
+            OverflowStrategy::Todo => "todo",
+            OverflowStrategy::Any => "any",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigValueKind {
+    Integer { min: u64, max: u64 },
+    Choice(&'static [&'static str]),
+    Bool,
+    Rules,
+    Url,
+}
+
+impl ConfigValueKind {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ConfigValueKind::Integer { .. } => "integer",
+            ConfigValueKind::Choice(_) => "choice",
+            ConfigValueKind::Bool => "bool",
+            ConfigValueKind::Rules => "rules",
+            ConfigValueKind::Url => "url",
+        }
+    }
+
+    pub fn allowed(&self) -> String {
+        match self {
+            ConfigValueKind::Integer { min, max } => format!("{}-{}", min, max),
+            ConfigValueKind::Choice(options) => options.join(", "),
+            ConfigValueKind::Bool => "true, false".to_string(),
+            ConfigValueKind::Rules => "comma-separated domain=type pairs".to_string(),
+            ConfigValueKind::Url => "an http(s) URL".to_string(),
+        }
+    }
+
+    pub fn expected(&self) -> String {
+        match self {
+            ConfigValueKind::Integer { min, max } => {
+                format!("Must be a number between {} and {}", min, max)
+            }
+            ConfigValueKind::Choice(options) => {
+                format!("Valid options are: {}", options.join(", "))
+            }
+            ConfigValueKind::Bool => "Must be true or false".to_string(),
+            ConfigValueKind::Rules => {
+                "Must be comma-separated domain=type pairs, e.g. example.com/podcasts=podcast"
+                    .to_string()
+            }
+            ConfigValueKind::Url => "Must be an http or https URL".to_string(),
+        }
+    }
+
+    fn accepts(&self, value: &str) -> bool {
+        match self {
+            ConfigValueKind::Integer { min, max } => value
+                .parse::<u64>()
+                .is_ok_and(|number| number >= *min && number <= *max),
+            ConfigValueKind::Choice(options) => options.contains(&value),
+            ConfigValueKind::Bool => matches!(value, "true" | "false"),
+            ConfigValueKind::Rules => {
+                split_rules(value).all(|pair| parse_type_rule(pair).is_some())
+            }
+            ConfigValueKind::Url => {
+                is_supported_link(value)
+                    && (value.starts_with("http://") || value.starts_with("https://"))
+            }
+        }
+    }
+}
+
+fn split_rules(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+}
+
+pub struct ConfigField {
+    pub key: &'static str,
+    pub kind: ConfigValueKind,
+    pub default: &'static str,
+    pub help: &'static str,
+    get: fn(&Config) -> String,
+    set: fn(&mut Config, &str),
+}
+
+impl ConfigField {
+    pub fn env_var(&self) -> String {
+        format!("FOLIO_{}", self.key.to_uppercase())
+    }
+
+    pub fn get(&self, config: &Config) -> String {
+        (self.get)(config)
+    }
+
+    pub fn validate(&self, value: &str) -> Result<(), ConfigError> {
+        if self.kind.accepts(value) {
+            Ok(())
+        } else {
+            Err(ConfigError::InvalidValue {
+                key: self.key.to_string(),
+                value: value.to_string(),
+                expected: self.kind.expected(),
+            })
+        }
+    }
+}
+
+pub const CONFIG_SCHEMA: &[ConfigField] = &[
+    ConfigField {
+        key: "max_items",
+        kind: ConfigValueKind::Integer { min: 1, max: 1000 },
+        default: "30",
+        help: "Maximum number of items allowed in the inbox",
+        get: |config| config.max_items.to_string(),
+        set: |config, value| config.max_items = value.parse().unwrap_or(config.max_items),
+    },
+    ConfigField {
+        key: "archive_on_overflow",
+        kind: ConfigValueKind::Choice(&["abort", "todo", "any"]),
+        default: "abort",
+        help: "What to do when adding to a full inbox: abort, archive the oldest todo item, or archive the oldest item",
+        get: |config| config.archive_on_overflow.as_string().to_string(),
+        set: |config, value| {
+            if let Ok(strategy) = OverflowStrategy::from_str(value) {
+                config.archive_on_overflow = strategy;
+            }
+        },
+    },
+    ConfigField {
+        key: "someday_max_items",
+        kind: ConfigValueKind::Integer { min: 1, max: 10000 },
+        default: "100",
+        help: "Maximum number of items allowed in the someday list",
+        get: |config| config.someday_max_items.to_string(),
+        set: |config, value| {
+            config.someday_max_items = value.parse().unwrap_or(config.someday_max_items)
+        },
+    },
+    ConfigField {
+        key: "someday_on_overflow",
+        kind: ConfigValueKind::Choice(&["abort", "todo", "any"]),
+        default: "any",
+        help: "What to do when adding to a full someday list: abort, archive its oldest todo item, or archive its oldest item",
+        get: |config| config.someday_on_overflow.as_string().to_string(),
+        set: |config, value| {
+            if let Ok(strategy) = OverflowStrategy::from_str(value) {
+                config.someday_on_overflow = strategy;
+            }
+        },
+    },
+    ConfigField {
+        key: "type_rules",
+        kind: ConfigValueKind::Rules,
+        default: "",
+        help: "Extra rules for guessing an item's type from its link, checked before the built-in ones",
+        get: |config| {
+            config
+                .type_rules
+                .iter()
+                .map(|rule| format!("{}={}", rule.pattern, rule.item_type.as_string()))
+                .collect::<Vec<_>>()
+                .join(",")
+        },
+        set: |config, value| {
+            config.type_rules = split_rules(value).filter_map(parse_type_rule).collect();
+        },
+    },
+    ConfigField {
+        key: "offline",
+        kind: ConfigValueKind::Bool,
+        default: "false",
+        help: "Never go online, e.g. to fetch a page's title and author when adding a link",
+        get: |config| config.offline.to_string(),
+        set: |config, value| config.offline = value == "true",
+    },
+    ConfigField {
+        key: "fetch_timeout",
+        kind: ConfigValueKind::Integer { min: 1, max: 120 },
+        default: "10",
+        help: "Seconds to wait for a web page before giving up",
+        get: |config| config.fetch_timeout.to_string(),
+        set: |config, value| config.fetch_timeout = value.parse().unwrap_or(config.fetch_timeout),
+    },
+    ConfigField {
+        key: "auto_snapshot",
+        kind: ConfigValueKind::Bool,
+        default: "false",
+        help: "Save a readable copy of the page for offline reading whenever a link is added",
+        get: |config| config.auto_snapshot.to_string(),
+        set: |config, value| config.auto_snapshot = value == "true",
+    },
+    ConfigField {
+        key: "wayback_api",
+        kind: ConfigValueKind::Url,
+        default: "https://archive.org/wayback/available",
+        help: "Wayback Machine availability API asked for archived copies of dead links",
+        get: |config| config.wayback_api.clone(),
+        set: |config, value| config.wayback_api = value.to_string(),
+    },
+];
+
+pub fn config_field(key: &str) -> Result<&'static ConfigField, ConfigError> {
+    CONFIG_SCHEMA
+        .iter()
+        .find(|field| field.key == key)
+        .ok_or_else(|| ConfigError::UnknownKey {
+            key: key.to_string(),
+            valid: CONFIG_SCHEMA
+                .iter()
+                .map(|field| field.key)
+                .collect::<Vec<_>>()
+                .join(", "),
+        })
+}
+
+pub fn parse_override(raw: &str) -> Result<(String, String), ConfigError> {
+    match raw.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(ConfigError::MalformedOverride(raw.to_string())),
+    }
+}
+
 impl Config {
     pub fn new() -> Self {
-        Self {
-            max_items: 30,
+        let mut config = Self {
+            max_items: 0,
             archive_on_overflow: OverflowStrategy::Abort,
+            someday_max_items: 0,
+            someday_on_overflow: OverflowStrategy::Abort,
+            type_rules: Vec::new(),
+            offline: false,
+            fetch_timeout: 0,
+            auto_snapshot: false,
+            wayback_api: String::new(),
             version: 1,
+        };
+
+        for field in CONFIG_SCHEMA {
+            (field.set)(&mut config, field.default);
+        }
+
+        config
+    }
+
+    pub fn get_value(&self, key: &str) -> Result<String, ConfigError> {
+        Ok(config_field(key)?.get(self))
+    }
+
+    pub fn set_value(&mut self, key: &str, value: &str) -> Result<(), ConfigError> {
+        let field = config_field(key)?;
+        field.validate(value)?;
+        (field.set)(self, value);
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for field in CONFIG_SCHEMA {
+            field.validate(&field.get(self))?;
         }
+        Ok(())
     }
 }
 
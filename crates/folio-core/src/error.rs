@@ -5,6 +5,12 @@ pub enum CoreError {
     #[error("Validation error: {0}")]
     ValidationError(String),
 
+    #[error("Invalid item: {0}")]
+    InvalidFields(crate::ValidationErrors),
+
+    #[error("'{}' is already saved", .0.item.name)]
+    Duplicate(Box<crate::Duplicate>),
+
     #[error("Item not found")]
     ItemNotFound,
 
@@ -13,10 +19,38 @@ pub enum CoreError {
 
     #[error("Inbox is full")]
     InboxFull,
+
+    #[error("Someday list is full")]
+    SomedayFull,
+
+    #[error("Import error: {0}")]
+    Import(String),
+
+    #[error("Export error: {0}")]
+    Export(String),
 }
 
 #[derive(Error, Debug)]
 pub enum CapError {
     #[error("Inbox is full")]
     Full,
+
+    #[error("No inbox item at position {position}")]
+    InvalidEviction { position: usize },
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    #[error("Unknown config key '{key}'. Valid keys are: {valid}")]
+    UnknownKey { key: String, valid: String },
+
+    #[error("Invalid value for {key}: {value}. {expected}")]
+    InvalidValue {
+        key: String,
+        value: String,
+        expected: String,
+    },
+
+    #[error("Invalid config override '{0}'. Expected KEY=VALUE")]
+    MalformedOverride(String),
 }
@@ -0,0 +1,168 @@
+use crate::{Field, FieldError, Item, ItemType, Status};
+
+// A comfortable pace for reading on screen.
+pub const WORDS_PER_MINUTE: usize = 230;
+
+pub fn reading_minutes(word_count: usize) -> u32 {
+    word_count.div_ceil(WORDS_PER_MINUTE).max(1) as u32
+}
+
+pub fn minutes_from_seconds(seconds: u64) -> u32 {
+    seconds.div_ceil(60).max(1) as u32
+}
+
+// A duration published with the page wins. Otherwise text is timed from its snapshot, which
+// for a video or episode page would only measure the description.
+pub fn estimate_minutes(
+    item_type: &ItemType,
+    duration_seconds: Option<u64>,
+    word_count: Option<usize>,
+) -> Option<u32> {
+    match (duration_seconds, item_type) {
+        (Some(seconds), _) => Some(minutes_from_seconds(seconds)),
+        (None, ItemType::Video | ItemType::Podcast) => None,
+        (None, _) => word_count.filter(|words| *words > 0).map(reading_minutes),
+    }
+}
+
+// "PT1H2M30S" or "P1DT2H" from JSON-LD, in seconds.
+pub fn parse_iso8601_duration(value: &str) -> Option<u64> {
+    let rest = value.trim().strip_prefix(['P', 'p'])?;
+    let mut seconds = 0.0;
+    let mut number = String::new();
+    let mut in_time = false;
+    let mut any = false;
+
+    for c in rest.chars() {
+        match c.to_ascii_uppercase() {
+            'T' if number.is_empty() => in_time = true,
+            c if c.is_ascii_digit() || c == '.' || c == ',' => {
+                number.push(if c == ',' { '.' } else { c })
+            }
+            unit => {
+                let amount: f64 = number.parse().ok()?;
+                number.clear();
+                seconds += amount
+                    * match (unit, in_time) {
+                        ('W', false) => 604_800.0,
+                        ('D', false) => 86_400.0,
+                        ('H', true) => 3_600.0,
+                        ('M', true) => 60.0,
+                        ('S', true) => 1.0,
+                        _ => return None,
+                    };
+                any = true;
+            }
+        }
+    }
+
+    (any && number.is_empty()).then_some(seconds.round() as u64)
+}
+
+// What a person types: "25", "25m", "1h", "1h30", "1h 30m" or "1:30". Empty means no estimate.
+pub fn parse_minutes_field(value: &str) -> Result<Option<u32>, FieldError> {
+    let value = value.trim().to_lowercase();
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    let error = || FieldError {
+        field: Field::Minutes,
+        message: format!(
+            "'{}' is not a duration, use minutes like 25 or hours like 1h30",
+            value
+        ),
+    };
+
+    let compact: String = value.split_whitespace().collect();
+    let compact = compact
+        .strip_suffix("min")
+        .or_else(|| compact.strip_suffix('m'))
+        .unwrap_or(&compact);
+    let (hours, minutes) = match compact.split_once(['h', ':']) {
+        Some((hours, minutes)) => (hours, minutes),
+        None => ("0", compact),
+    };
+    let hours: u32 = hours.parse().map_err(|_| error())?;
+    let minutes: u32 = match minutes {
+        "" => 0,
+        minutes => minutes.parse().map_err(|_| error())?,
+    };
+
+    match hours.checked_mul(60).and_then(|h| h.checked_add(minutes)) {
+        Some(0) => Err(FieldError {
+            field: Field::Minutes,
+            message: "must be at least a minute".to_string(),
+        }),
+        Some(total) => Ok(Some(total)),
+        None => Err(error()),
+    }
+}
+
+pub fn format_minutes(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeTotal {
+    pub minutes: u32,
+    pub unestimated: usize,
+}
+
+impl TimeTotal {
+    // Finished items don't count, they are only waiting to be archived.
+    pub fn of(items: &[Item]) -> Self {
+        items
+            .iter()
+            .filter(|item| item.status != Status::Done)
+            .fold(Self::default(), |mut total, item| {
+                match item.estimated_minutes {
+                    Some(minutes) => total.minutes += minutes,
+                    None => total.unestimated += 1,
+                }
+                total
+            })
+    }
+
+    pub fn describe(&self) -> String {
+        match (self.minutes, self.unestimated) {
+            (0, 0) => "nothing left".to_string(),
+            (0, unestimated) => format!("{} unestimated", unestimated),
+            (minutes, 0) => format_minutes(minutes),
+            (minutes, unestimated) => {
+                format!("{} + {} unestimated", format_minutes(minutes), unestimated)
+            }
+        }
+    }
+}
+
+// Positions of the items to get through in a slot of `minutes`: started items first, then the
+// queue in order, skipping whatever would run over. Items without an estimate are left out.
+pub fn pick_for_time_slot(items: &[Item], minutes: u32) -> Vec<usize> {
+    let queue = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.status == Status::Doing)
+        .chain(
+            items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| item.status == Status::Todo),
+        );
+
+    let mut remaining = minutes;
+    let mut picked = Vec::new();
+    for (index, item) in queue {
+        if let Some(estimate) = item.estimated_minutes
+            && estimate <= remaining
+        {
+            remaining -= estimate;
+            picked.push(index);
+        }
+    }
+    picked
+}
@@ -0,0 +1,137 @@
+use crate::{CoreError, Item, item_authors, split_name};
+use serde_json::{Map, Value, json};
+
+pub struct CitedItem<'a> {
+    pub key: &'a str,
+    pub item: &'a Item,
+}
+
+fn escape_bibtex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '&' | '%' | '$' | '#' | '_' | '{' | '}') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn bibtex_author(author: &str) -> String {
+    match split_name(author) {
+        (family, given) if given.is_empty() => family,
+        (family, given) => format!("{}, {}", family, given),
+    }
+}
+
+fn bibtex_entry(cited: &CitedItem) -> String {
+    let item = cited.item;
+    let bib = item.bib.clone().unwrap_or_default();
+    let authors = item_authors(item);
+
+    let entry_type = if bib.venue.is_some() {
+        "article"
+    } else {
+        "misc"
+    };
+    let mut fields: Vec<(&str, String)> =
+        vec![("title", format!("{{{}}}", escape_bibtex(&item.name)))];
+
+    if !authors.is_empty() {
+        let authors: Vec<String> = authors.iter().map(|author| bibtex_author(author)).collect();
+        fields.push(("author", escape_bibtex(&authors.join(" and "))));
+    }
+    if let Some(year) = bib.year {
+        fields.push(("year", year.to_string()));
+    }
+    if let Some(venue) = &bib.venue {
+        fields.push(("journal", escape_bibtex(venue)));
+    }
+    if let Some(doi) = &bib.doi {
+        fields.push(("doi", escape_bibtex(doi)));
+    }
+    if let Some(arxiv_id) = &bib.arxiv_id {
+        fields.push(("eprint", escape_bibtex(arxiv_id)));
+        fields.push(("archivePrefix", "arXiv".to_string()));
+    }
+    if !item.link.is_empty() {
+        fields.push(("url", item.link.clone()));
+    }
+    if !item.tags.is_empty() {
+        fields.push(("keywords", escape_bibtex(&item.tags.join(", "))));
+    }
+
+    let mut entry = format!("@{}{{{},\n", entry_type, cited.key);
+    for (name, value) in fields {
+        entry.push_str(&format!("  {} = {{{}}},\n", name, value));
+    }
+    entry.push_str("}\n");
+    entry
+}
+
+pub fn to_bibtex(items: &[CitedItem]) -> String {
+    items
+        .iter()
+        .map(bibtex_entry)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn csl_entry(cited: &CitedItem) -> Value {
+    let item = cited.item;
+    let bib = item.bib.clone().unwrap_or_default();
+
+    let mut entry = Map::new();
+    entry.insert("id".into(), json!(cited.key));
+    entry.insert(
+        "type".into(),
+        json!(if bib.venue.is_some() {
+            "article-journal"
+        } else {
+            "article"
+        }),
+    );
+    entry.insert("title".into(), json!(item.name));
+
+    let authors: Vec<Value> = item_authors(item)
+        .iter()
+        .map(|author| match split_name(author) {
+            (family, given) if given.is_empty() => json!({ "literal": family }),
+            (family, given) => json!({ "family": family, "given": given }),
+        })
+        .collect();
+    if !authors.is_empty() {
+        entry.insert("author".into(), Value::Array(authors));
+    }
+    if let Some(year) = bib.year {
+        entry.insert("issued".into(), json!({ "date-parts": [[year]] }));
+    }
+    if let Some(venue) = &bib.venue {
+        entry.insert("container-title".into(), json!(venue));
+    }
+    if let Some(doi) = &bib.doi {
+        entry.insert("DOI".into(), json!(doi));
+    }
+    if let Some(arxiv_id) = &bib.arxiv_id {
+        entry.insert("number".into(), json!(format!("arXiv:{}", arxiv_id)));
+        if bib.venue.is_none() {
+            entry.insert("publisher".into(), json!("arXiv"));
+        }
+    }
+    if !item.link.is_empty() {
+        entry.insert("URL".into(), json!(item.link));
+    }
+    if !item.tags.is_empty() {
+        entry.insert("keyword".into(), json!(item.tags.join(", ")));
+    }
+
+    Value::Object(entry)
+}
+
+pub fn to_csl_json(items: &[CitedItem]) -> Result<String, CoreError> {
+    let entries: Vec<Value> = items.iter().map(csl_entry).collect();
+    let mut json =
+        serde_json::to_string_pretty(&entries).map_err(|e| CoreError::Export(e.to_string()))?;
+    json.push('\n');
+    Ok(json)
+}
@@ -0,0 +1,176 @@
+use crate::Item;
+use chrono::{DateTime, SecondsFormat, Utc};
+use strum::EnumString;
+
+use super::escape_xml;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+pub enum FeedFormat {
+    #[strum(serialize = "atom")]
+    Atom,
+    #[strum(serialize = "rss")]
+    Rss,
+}
+
+#[derive(Debug, Clone)]
+pub struct FeedMeta {
+    pub title: String,
+    pub author: String,
+    pub link: Option<String>,
+}
+
+fn entry_id(item: &Item) -> String {
+    format!("urn:folio:{}", item.id())
+}
+
+fn atom_time(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn atom_entry(item: &Item, finished_at: &DateTime<Utc>) -> String {
+    let mut entry = String::from("  <entry>\n");
+    entry.push_str(&format!("    <title>{}</title>\n", escape_xml(&item.name)));
+    entry.push_str(&format!("    <id>{}</id>\n", entry_id(item)));
+    if !item.link.is_empty() {
+        entry.push_str(&format!(
+            "    <link rel=\"alternate\" href=\"{}\"/>\n",
+            escape_xml(&item.link)
+        ));
+    }
+    entry.push_str(&format!(
+        "    <updated>{}</updated>\n",
+        atom_time(finished_at)
+    ));
+    if !item.author.is_empty() {
+        entry.push_str(&format!(
+            "    <author><name>{}</name></author>\n",
+            escape_xml(&item.author)
+        ));
+    }
+    for tag in &item.tags {
+        entry.push_str(&format!("    <category term=\"{}\"/>\n", escape_xml(tag)));
+    }
+    if !item.note.is_empty() {
+        entry.push_str(&format!(
+            "    <summary>{}</summary>\n",
+            escape_xml(&item.note)
+        ));
+    }
+    entry.push_str("  </entry>\n");
+    entry
+}
+
+fn to_atom(entries: &[(&Item, DateTime<Utc>)], meta: &FeedMeta, updated: DateTime<Utc>) -> String {
+    let mut feed = String::new();
+    feed.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    feed.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    feed.push_str(&format!("  <title>{}</title>\n", escape_xml(&meta.title)));
+    match &meta.link {
+        Some(link) => {
+            feed.push_str(&format!("  <id>{}</id>\n", escape_xml(link)));
+            feed.push_str(&format!(
+                "  <link rel=\"self\" href=\"{}\"/>\n",
+                escape_xml(link)
+            ));
+        }
+        None => feed.push_str("  <id>urn:folio:feed</id>\n"),
+    }
+    feed.push_str(&format!("  <updated>{}</updated>\n", atom_time(&updated)));
+    feed.push_str(&format!(
+        "  <author><name>{}</name></author>\n",
+        escape_xml(&meta.author)
+    ));
+    feed.push_str("  <generator>folio</generator>\n");
+
+    for (item, finished_at) in entries {
+        feed.push_str(&atom_entry(item, finished_at));
+    }
+
+    feed.push_str("</feed>\n");
+    feed
+}
+
+fn rss_item(item: &Item, finished_at: &DateTime<Utc>) -> String {
+    let mut entry = String::from("    <item>\n");
+    entry.push_str(&format!(
+        "      <title>{}</title>\n",
+        escape_xml(&item.name)
+    ));
+    if !item.link.is_empty() {
+        entry.push_str(&format!("      <link>{}</link>\n", escape_xml(&item.link)));
+    }
+    entry.push_str(&format!(
+        "      <guid isPermaLink=\"false\">{}</guid>\n",
+        entry_id(item)
+    ));
+    entry.push_str(&format!(
+        "      <pubDate>{}</pubDate>\n",
+        finished_at.to_rfc2822()
+    ));
+    if !item.author.is_empty() {
+        entry.push_str(&format!(
+            "      <dc:creator>{}</dc:creator>\n",
+            escape_xml(&item.author)
+        ));
+    }
+    for tag in &item.tags {
+        entry.push_str(&format!("      <category>{}</category>\n", escape_xml(tag)));
+    }
+    if !item.note.is_empty() {
+        entry.push_str(&format!(
+            "      <description>{}</description>\n",
+            escape_xml(&item.note)
+        ));
+    }
+    entry.push_str("    </item>\n");
+    entry
+}
+
+fn to_rss(entries: &[(&Item, DateTime<Utc>)], meta: &FeedMeta, updated: DateTime<Utc>) -> String {
+    let mut feed = String::new();
+    feed.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    feed.push_str("<rss version=\"2.0\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n");
+    feed.push_str("  <channel>\n");
+    feed.push_str(&format!("    <title>{}</title>\n", escape_xml(&meta.title)));
+    feed.push_str(&format!(
+        "    <link>{}</link>\n",
+        escape_xml(meta.link.as_deref().unwrap_or_default())
+    ));
+    feed.push_str(&format!(
+        "    <description>{}</description>\n",
+        escape_xml(&meta.title)
+    ));
+    feed.push_str(&format!(
+        "    <lastBuildDate>{}</lastBuildDate>\n",
+        updated.to_rfc2822()
+    ));
+    feed.push_str("    <generator>folio</generator>\n");
+
+    for (item, finished_at) in entries {
+        feed.push_str(&rss_item(item, finished_at));
+    }
+
+    feed.push_str("  </channel>\n");
+    feed.push_str("</rss>\n");
+    feed
+}
+
+// Entries are dated by finished_at and the feed by its newest entry, so re-exporting an
+// unchanged archive produces an identical file.
+pub fn to_feed(items: &[Item], format: FeedFormat, meta: &FeedMeta) -> String {
+    let mut entries: Vec<(&Item, DateTime<Utc>)> = items
+        .iter()
+        .filter_map(|item| item.finished_at.map(|finished_at| (item, finished_at)))
+        .collect();
+    entries.sort_by_key(|(_, finished_at)| std::cmp::Reverse(*finished_at));
+
+    let updated = entries
+        .first()
+        .map(|(_, finished_at)| *finished_at)
+        .unwrap_or(DateTime::UNIX_EPOCH);
+
+    match format {
+        FeedFormat::Atom => to_atom(&entries, meta, updated),
+        FeedFormat::Rss => to_rss(&entries, meta, updated),
+    }
+}
@@ -0,0 +1,87 @@
+use crate::Item;
+use chrono::{DateTime, SecondsFormat, Utc};
+
+const ID_KEY: &str = "folio_id";
+
+fn yaml_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn yaml_time(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+pub fn markdown_note(item: &Item) -> String {
+    let mut note = String::from("---\n");
+    note.push_str(&format!("{}: {}\n", ID_KEY, item.id()));
+    note.push_str(&format!("title: {}\n", yaml_string(&item.name)));
+    note.push_str(&format!("type: {}\n", item.item_type.as_string()));
+    if !item.author.is_empty() {
+        note.push_str(&format!("author: {}\n", yaml_string(&item.author)));
+    }
+    if !item.link.is_empty() {
+        note.push_str(&format!("link: {}\n", yaml_string(&item.link)));
+    }
+    note.push_str(&format!("added: {}\n", yaml_time(&item.added_at)));
+    if let Some(started_at) = &item.started_at {
+        note.push_str(&format!("started: {}\n", yaml_time(started_at)));
+    }
+    if let Some(finished_at) = &item.finished_at {
+        note.push_str(&format!("finished: {}\n", yaml_time(finished_at)));
+    }
+    if !item.tags.is_empty() {
+        note.push_str("tags:\n");
+        for tag in &item.tags {
+            note.push_str(&format!("  - {}\n", yaml_string(tag)));
+        }
+    }
+    note.push_str("---\n\n");
+
+    note.push_str(&format!("# {}\n", item.name));
+    if !item.note.is_empty() {
+        note.push('\n');
+        note.push_str(item.note.trim_end());
+        note.push('\n');
+    }
+
+    note
+}
+
+pub fn markdown_file_stem(item: &Item) -> String {
+    let stem: String = item
+        .name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']' => ' ',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let stem: String = stem.chars().take(100).collect();
+    let stem = stem.trim_matches(|c: char| c == '.' || c.is_whitespace());
+
+    if stem.is_empty() {
+        format!("folio-{}", item.id())
+    } else {
+        stem.to_string()
+    }
+}
+
+pub fn markdown_note_id(contents: &str) -> Option<usize> {
+    let front_matter = contents.strip_prefix("---")?;
+    let end = front_matter.find("\n---")?;
+
+    front_matter[..end].lines().find_map(|line| {
+        line.strip_prefix(ID_KEY)?
+            .trim_start()
+            .strip_prefix(':')?
+            .trim()
+            .trim_matches('"')
+            .parse()
+            .ok()
+    })
+}
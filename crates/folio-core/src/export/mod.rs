@@ -0,0 +1,35 @@
+mod bib;
+pub use bib::{CitedItem, to_bibtex, to_csl_json};
+
+mod feed;
+pub use feed::{FeedFormat, FeedMeta, to_feed};
+
+mod markdown;
+pub use markdown::{markdown_file_stem, markdown_note, markdown_note_id};
+
+mod opml;
+pub use opml::to_opml;
+
+mod org;
+pub use org::to_org;
+
+mod site;
+pub use site::to_site;
+
+mod table;
+pub use table::{DEFAULT_COLUMNS, ExportColumn, ExportFormat, ExportRow, export_table};
+
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
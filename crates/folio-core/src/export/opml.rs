@@ -0,0 +1,59 @@
+use crate::Item;
+use chrono::Utc;
+use std::collections::BTreeMap;
+
+use super::escape_xml;
+
+fn outline(item: &Item, indent: &str) -> String {
+    format!(
+        "{}<outline type=\"rss\" text=\"{}\" title=\"{}\" xmlUrl=\"{}\"/>\n",
+        indent,
+        escape_xml(&item.name),
+        escape_xml(&item.name),
+        escape_xml(&item.link)
+    )
+}
+
+pub fn to_opml(items: &[Item], title: &str) -> String {
+    let mut by_category: BTreeMap<&str, Vec<&Item>> = BTreeMap::new();
+    let mut uncategorized = Vec::new();
+
+    for item in items.iter().filter(|item| !item.link.is_empty()) {
+        match item.tags.first() {
+            Some(tag) => by_category.entry(tag).or_default().push(item),
+            None => uncategorized.push(item),
+        }
+    }
+
+    let mut opml = String::new();
+    opml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    opml.push_str("<opml version=\"2.0\">\n");
+    opml.push_str("  <head>\n");
+    opml.push_str(&format!("    <title>{}</title>\n", escape_xml(title)));
+    opml.push_str(&format!(
+        "    <dateCreated>{}</dateCreated>\n",
+        Utc::now().to_rfc2822()
+    ));
+    opml.push_str("  </head>\n");
+    opml.push_str("  <body>\n");
+
+    for item in uncategorized {
+        opml.push_str(&outline(item, "    "));
+    }
+
+    for (category, items) in by_category {
+        opml.push_str(&format!(
+            "    <outline text=\"{}\" title=\"{}\">\n",
+            escape_xml(category),
+            escape_xml(category)
+        ));
+        for item in items {
+            opml.push_str(&outline(item, "      "));
+        }
+        opml.push_str("    </outline>\n");
+    }
+
+    opml.push_str("  </body>\n");
+    opml.push_str("</opml>\n");
+    opml
+}
@@ -0,0 +1,85 @@
+use crate::{Item, Status};
+use chrono::{DateTime, Utc};
+
+fn org_keyword(status: &Status) -> &'static str {
+    match status {
+        Status::Todo => "TODO",
+        Status::Doing => "DOING",
+        Status::Done => "DONE",
+    }
+}
+
+fn org_time(time: &DateTime<Utc>) -> String {
+    time.format("[%Y-%m-%d %a %H:%M]").to_string()
+}
+
+// Org tags may only contain letters, digits, '_', '@', '#' and '%'.
+fn org_tag(tag: &str) -> String {
+    tag.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '_' | '@' | '#' | '%') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn headline(item: &Item) -> String {
+    let mut org = format!(
+        "** {} {}",
+        org_keyword(&item.status),
+        item.name.replace('\n', " ")
+    );
+    if !item.tags.is_empty() {
+        let tags: Vec<String> = item.tags.iter().map(|tag| org_tag(tag)).collect();
+        org.push_str(&format!(" :{}:", tags.join(":")));
+    }
+    org.push('\n');
+
+    org.push_str(":PROPERTIES:\n");
+    org.push_str(&format!(":FOLIO_ID: {}\n", item.id()));
+    org.push_str(&format!(":TYPE: {}\n", item.item_type.as_string()));
+    org.push_str(&format!(":KIND: {}\n", item.kind.as_string()));
+    if !item.author.is_empty() {
+        org.push_str(&format!(":AUTHOR: {}\n", item.author));
+    }
+    if !item.link.is_empty() {
+        org.push_str(&format!(":LINK: {}\n", item.link));
+    }
+    org.push_str(&format!(":ADDED: {}\n", org_time(&item.added_at)));
+    if let Some(started_at) = &item.started_at {
+        org.push_str(&format!(":STARTED: {}\n", org_time(started_at)));
+    }
+    if let Some(finished_at) = &item.finished_at {
+        org.push_str(&format!(":FINISHED: {}\n", org_time(finished_at)));
+    }
+    org.push_str(":END:\n");
+
+    // A body line starting with '*' would read as a headline, so it gets Org's ',' escape.
+    for line in item.note.lines() {
+        if line.starts_with('*') || line.starts_with(",*") {
+            org.push(',');
+        }
+        org.push_str(line);
+        org.push('\n');
+    }
+
+    org
+}
+
+pub fn to_org(sections: &[(&str, &[Item])]) -> String {
+    let mut org = String::new();
+    org.push_str("#+TITLE: folio\n");
+    org.push_str("#+TODO: TODO DOING | DONE\n");
+
+    for (title, items) in sections {
+        org.push_str(&format!("\n* {}\n", title));
+        for item in items.iter() {
+            org.push_str(&headline(item));
+        }
+    }
+
+    org
+}
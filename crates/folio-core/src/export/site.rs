@@ -0,0 +1,153 @@
+use crate::{CoreError, Item};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::escape_xml;
+
+const TEMPLATE: &str = include_str!("site.html");
+
+#[derive(Serialize)]
+struct SiteItem<'a> {
+    name: &'a str,
+    link: &'a str,
+    #[serde(rename = "type")]
+    item_type: &'static str,
+    author: &'a str,
+    note: &'a str,
+    tags: &'a [String],
+    month: String,
+    date: String,
+}
+
+// Pages get shared, so only plain web links become clickable.
+fn web_link(link: &str) -> &str {
+    let lower = link.trim_start().to_ascii_lowercase();
+    if lower.starts_with("http://") || lower.starts_with("https://") {
+        link
+    } else {
+        ""
+    }
+}
+
+fn item_date(item: &Item) -> DateTime<Utc> {
+    item.finished_at.unwrap_or(item.added_at)
+}
+
+fn render_item(item: &SiteItem) -> String {
+    let title = if item.link.is_empty() {
+        format!("<strong>{}</strong>", escape_xml(item.name))
+    } else {
+        format!(
+            "<a href=\"{}\">{}</a>",
+            escape_xml(item.link),
+            escape_xml(item.name)
+        )
+    };
+
+    let meta: Vec<String> = [item.author, &item.item_type.replace('_', " "), &item.date]
+        .iter()
+        .filter(|part| !part.is_empty())
+        .map(|part| escape_xml(part))
+        .chain(
+            item.tags
+                .iter()
+                .map(|tag| format!("<span class=\"tag\">{}</span>", escape_xml(tag))),
+        )
+        .collect();
+
+    let mut li = format!(
+        "<li>{}<div class=\"meta\">{}</div>",
+        title,
+        meta.join(" · ")
+    );
+    if !item.note.is_empty() {
+        li.push_str(&format!("<p class=\"note\">{}</p>", escape_xml(item.note)));
+    }
+    li.push_str("</li>\n");
+    li
+}
+
+// The page is usable without JavaScript: items are pre-rendered by month, and the script
+// re-renders them from the embedded JSON when filters change.
+fn render_by_month(items: &[SiteItem]) -> String {
+    let mut by_month: BTreeMap<&str, Vec<&SiteItem>> = BTreeMap::new();
+    for item in items {
+        by_month.entry(&item.month).or_default().push(item);
+    }
+
+    let mut html = String::new();
+    for (month, items) in by_month.iter().rev() {
+        html.push_str(&format!(
+            "<h2>{} <small>({})</small></h2>\n<ul>\n",
+            month,
+            items.len()
+        ));
+        for item in items {
+            html.push_str(&render_item(item));
+        }
+        html.push_str("</ul>\n");
+    }
+    html
+}
+
+fn fill_template(values: &[(&str, &str)]) -> String {
+    let mut html = String::with_capacity(TEMPLATE.len());
+    let mut rest = TEMPLATE;
+
+    while let Some(start) = rest.find("{{") {
+        html.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        match values.iter().find(|(name, _)| {
+            rest[2..].starts_with(name) && rest[2 + name.len()..].starts_with("}}")
+        }) {
+            Some((name, value)) => {
+                html.push_str(value);
+                rest = &rest[name.len() + 4..];
+            }
+            None => {
+                html.push_str("{{");
+                rest = &rest[2..];
+            }
+        }
+    }
+    html.push_str(rest);
+    html
+}
+
+pub fn to_site(items: &[Item], title: &str) -> Result<String, CoreError> {
+    let mut items: Vec<&Item> = items.iter().collect();
+    items.sort_by_key(|item| std::cmp::Reverse(item_date(item)));
+
+    let site_items: Vec<SiteItem> = items
+        .iter()
+        .map(|item| SiteItem {
+            name: &item.name,
+            link: web_link(&item.link),
+            item_type: item.item_type.as_string(),
+            author: &item.author,
+            note: &item.note,
+            tags: &item.tags,
+            month: item_date(item).format("%Y-%m").to_string(),
+            date: item_date(item).format("%Y-%m-%d").to_string(),
+        })
+        .collect();
+
+    // "</" would end the JSON script block early if a note happened to contain "</script>".
+    let json = serde_json::to_string(&site_items)
+        .map_err(|e| CoreError::Export(e.to_string()))?
+        .replace("</", "<\\/");
+
+    let summary = match site_items.len() {
+        1 => "1 item".to_string(),
+        count => format!("{} items", count),
+    };
+
+    Ok(fill_template(&[
+        ("title", &escape_xml(title)),
+        ("summary", &summary),
+        ("content", &render_by_month(&site_items)),
+        ("items", &json),
+    ]))
+}
@@ -0,0 +1,181 @@
+use crate::{CoreError, Item};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::Value;
+use strum::EnumString;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+pub enum ExportFormat {
+    #[strum(serialize = "csv")]
+    Csv,
+    #[strum(serialize = "json")]
+    Json,
+    #[strum(serialize = "jsonl")]
+    Jsonl,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+pub enum ExportColumn {
+    #[strum(serialize = "list")]
+    List,
+    #[strum(serialize = "name")]
+    Name,
+    #[strum(serialize = "type")]
+    Type,
+    #[strum(serialize = "status")]
+    Status,
+    #[strum(serialize = "author")]
+    Author,
+    #[strum(serialize = "link")]
+    Link,
+    #[strum(serialize = "note")]
+    Note,
+    #[strum(serialize = "kind")]
+    Kind,
+    #[strum(serialize = "tags")]
+    Tags,
+    #[strum(serialize = "added_at")]
+    AddedAt,
+    #[strum(serialize = "started_at")]
+    StartedAt,
+    #[strum(serialize = "finished_at")]
+    FinishedAt,
+    #[strum(serialize = "estimated_minutes")]
+    EstimatedMinutes,
+}
+
+pub const DEFAULT_COLUMNS: &[ExportColumn] = &[
+    ExportColumn::List,
+    ExportColumn::Name,
+    ExportColumn::Type,
+    ExportColumn::Status,
+    ExportColumn::Author,
+    ExportColumn::Link,
+    ExportColumn::Note,
+    ExportColumn::Kind,
+    ExportColumn::Tags,
+    ExportColumn::AddedAt,
+    ExportColumn::StartedAt,
+    ExportColumn::FinishedAt,
+    ExportColumn::EstimatedMinutes,
+];
+
+fn iso(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+impl ExportColumn {
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            ExportColumn::List => "list",
+            ExportColumn::Name => "name",
+            ExportColumn::Type => "type",
+            ExportColumn::Status => "status",
+            ExportColumn::Author => "author",
+            ExportColumn::Link => "link",
+            ExportColumn::Note => "note",
+            ExportColumn::Kind => "kind",
+            ExportColumn::Tags => "tags",
+            ExportColumn::AddedAt => "added_at",
+            ExportColumn::StartedAt => "started_at",
+            ExportColumn::FinishedAt => "finished_at",
+            ExportColumn::EstimatedMinutes => "estimated_minutes",
+        }
+    }
+
+    fn value(&self, row: &ExportRow) -> Value {
+        let item = row.item;
+        match self {
+            ExportColumn::List => Value::from(row.list),
+            ExportColumn::Name => Value::from(item.name.as_str()),
+            ExportColumn::Type => Value::from(item.item_type.as_string()),
+            ExportColumn::Status => Value::from(item.status.as_string()),
+            ExportColumn::Author => Value::from(item.author.as_str()),
+            ExportColumn::Link => Value::from(item.link.as_str()),
+            ExportColumn::Note => Value::from(item.note.as_str()),
+            ExportColumn::Kind => Value::from(item.kind.as_string()),
+            ExportColumn::Tags => Value::from(item.tags.clone()),
+            ExportColumn::AddedAt => Value::from(iso(&item.added_at)),
+            ExportColumn::StartedAt => item.started_at.as_ref().map(iso).into(),
+            ExportColumn::FinishedAt => item.finished_at.as_ref().map(iso).into(),
+            ExportColumn::EstimatedMinutes => item.estimated_minutes.into(),
+        }
+    }
+
+    fn cell(&self, row: &ExportRow) -> String {
+        match self.value(row) {
+            Value::Null => String::new(),
+            Value::String(text) => text,
+            Value::Array(values) => values
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join("; "),
+            other => other.to_string(),
+        }
+    }
+}
+
+pub struct ExportRow<'a> {
+    pub list: &'static str,
+    pub item: &'a Item,
+}
+
+struct JsonRow<'a> {
+    row: &'a ExportRow<'a>,
+    columns: &'a [ExportColumn],
+}
+
+impl Serialize for JsonRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+        for column in self.columns {
+            map.serialize_entry(column.as_string(), &column.value(self.row))?;
+        }
+        map.end()
+    }
+}
+
+pub fn export_table(
+    rows: &[ExportRow],
+    columns: &[ExportColumn],
+    format: ExportFormat,
+) -> Result<String, CoreError> {
+    let json_rows = rows.iter().map(|row| JsonRow { row, columns });
+    let json_error = |e: serde_json::Error| CoreError::Export(e.to_string());
+
+    match format {
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            let csv_error = |e: csv::Error| CoreError::Export(e.to_string());
+
+            writer
+                .write_record(columns.iter().map(ExportColumn::as_string))
+                .map_err(csv_error)?;
+            for row in rows {
+                writer
+                    .write_record(columns.iter().map(|column| column.cell(row)))
+                    .map_err(csv_error)?;
+            }
+
+            let bytes = writer
+                .into_inner()
+                .map_err(|e| CoreError::Export(e.to_string()))?;
+            Ok(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        ExportFormat::Json => {
+            let rows: Vec<_> = json_rows.collect();
+            let mut json = serde_json::to_string_pretty(&rows).map_err(json_error)?;
+            json.push('\n');
+            Ok(json)
+        }
+        ExportFormat::Jsonl => {
+            let mut jsonl = String::new();
+            for row in json_rows {
+                jsonl.push_str(&serde_json::to_string(&row).map_err(json_error)?);
+                jsonl.push('\n');
+            }
+            Ok(jsonl)
+        }
+    }
+}
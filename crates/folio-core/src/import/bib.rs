@@ -0,0 +1,575 @@
+use crate::validation::{fit_author, fit_name};
+use crate::{BibFields, CoreError, Item, ItemType, create_item};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default)]
+pub struct BibRecord {
+    pub title: String,
+    pub authors: Vec<String>,
+    pub year: Option<i32>,
+    pub venue: Option<String>,
+    pub doi: Option<String>,
+    pub arxiv_id: Option<String>,
+    pub url: Option<String>,
+    pub keywords: Vec<String>,
+}
+
+fn non_empty(text: String) -> Option<String> {
+    Some(text).filter(|text| !text.is_empty())
+}
+
+fn clean_doi(doi: &str) -> Option<String> {
+    let doi = doi.trim();
+    let doi = [
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "doi:",
+    ]
+    .iter()
+    .find_map(|prefix| doi.strip_prefix(prefix))
+    .unwrap_or(doi);
+    non_empty(doi.trim().to_string())
+}
+
+fn arxiv_from_text(text: &str) -> Option<String> {
+    let lower = text.to_ascii_lowercase();
+    let start = ["arxiv.org/abs/", "arxiv.org/pdf/", "arxiv:"]
+        .iter()
+        .find_map(|marker| lower.find(marker).map(|index| index + marker.len()))?;
+
+    let id: String = text[start..]
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '/' | '-'))
+        .collect();
+    let id = id.trim_end_matches(".pdf").trim_end_matches('.');
+    non_empty(id.to_string())
+}
+
+fn parse_year(text: &str) -> Option<i32> {
+    let digits: String = text
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits
+        .parse()
+        .ok()
+        .filter(|year| (1000..=9999).contains(year))
+}
+
+fn split_keywords(text: &str) -> Vec<String> {
+    text.split([',', ';'])
+        .map(str::trim)
+        .filter(|keyword| !keyword.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn combining_accent(accent: char) -> char {
+    match accent {
+        '\'' => '\u{301}',
+        '"' => '\u{308}',
+        '`' => '\u{300}',
+        '^' => '\u{302}',
+        '~' => '\u{303}',
+        '=' => '\u{304}',
+        _ => '\u{307}',
+    }
+}
+
+fn clean_latex(value: &str) -> String {
+    let mut cleaned = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' => {}
+            '~' => cleaned.push(' '),
+            '\\' => match chars.peek().copied() {
+                Some(next @ ('&' | '%' | '$' | '#' | '_' | '{' | '}')) => {
+                    cleaned.push(next);
+                    chars.next();
+                }
+                Some(accent @ ('\'' | '"' | '`' | '^' | '~' | '=' | '.')) => {
+                    chars.next();
+                    while chars.peek() == Some(&'{') {
+                        chars.next();
+                    }
+                    if let Some(letter) = chars.next_if(char::is_ascii_alphabetic) {
+                        cleaned.push(letter);
+                        cleaned.push(combining_accent(accent));
+                    }
+                }
+                Some(next) if next.is_ascii_alphabetic() => {
+                    while chars.peek().is_some_and(char::is_ascii_alphabetic) {
+                        chars.next();
+                    }
+                    if chars.peek() == Some(&' ') {
+                        chars.next();
+                    }
+                }
+                _ => {}
+            },
+            _ => cleaned.push(c),
+        }
+    }
+
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Splits on " and " outside braces, so "{Barnes and Noble}" stays a single author.
+fn split_bibtex_authors(value: &str) -> Vec<String> {
+    let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    let value = value.as_str();
+    let mut authors = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (index, c) in value.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            _ if depth == 0
+                && index >= start
+                && value
+                    .get(index..index + " and ".len())
+                    .is_some_and(|word| word.eq_ignore_ascii_case(" and ")) =>
+            {
+                authors.push(&value[start..index]);
+                start = index + " and ".len();
+            }
+            _ => {}
+        }
+    }
+    authors.push(&value[start..]);
+
+    authors
+        .into_iter()
+        .map(|author| {
+            let parts: Vec<String> = author.split(',').map(clean_latex).collect();
+            match parts.as_slice() {
+                [family, suffix, given] => format!("{} {} {}", given, family, suffix),
+                [family, given] => format!("{} {}", given, family),
+                _ => clean_latex(author),
+            }
+        })
+        .map(|author| author.trim().to_string())
+        .filter(|author| !author.is_empty() && author != "others")
+        .collect()
+}
+
+struct BibtexParser<'a> {
+    input: &'a str,
+    pos: usize,
+    macros: HashMap<String, String>,
+}
+
+impl<'a> BibtexParser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn word(&mut self) -> String {
+        let start = self.pos;
+        while let Some(c) = self.peek().filter(|c| {
+            !c.is_whitespace() && !matches!(c, '{' | '}' | '(' | ')' | ',' | '=' | '#' | '"')
+        }) {
+            self.pos += c.len_utf8();
+        }
+        self.input[start..self.pos].to_string()
+    }
+
+    // Reads up to the delimiter matching the one at the current position, keeping nested braces.
+    fn delimited(&mut self, close: char) -> Result<String, CoreError> {
+        let open = self.peek().unwrap_or_default();
+        self.pos += open.len_utf8();
+        let start = self.pos;
+        let mut depth = 0usize;
+
+        while let Some(c) = self.peek() {
+            if depth == 0 && c == close {
+                let value = self.input[start..self.pos].to_string();
+                self.pos += c.len_utf8();
+                return Ok(value);
+            }
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            self.pos += c.len_utf8();
+        }
+
+        Err(CoreError::Import(format!(
+            "Unterminated value starting at byte {}",
+            start
+        )))
+    }
+
+    fn value(&mut self) -> Result<String, CoreError> {
+        let mut value = String::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('{') => value.push_str(&self.delimited('}')?),
+                Some('"') => value.push_str(&self.delimited('"')?),
+                _ => {
+                    let word = self.word();
+                    match self.macros.get(&word.to_lowercase()) {
+                        Some(expansion) => value.push_str(expansion),
+                        None => value.push_str(&word),
+                    }
+                }
+            }
+            self.skip_whitespace();
+            if self.peek() == Some('#') {
+                self.pos += 1;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn fields(&mut self, close: char) -> Result<HashMap<String, String>, CoreError> {
+        let mut fields = HashMap::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => return Err(CoreError::Import("Unterminated BibTeX entry".into())),
+                Some(c) if c == close => {
+                    self.pos += 1;
+                    return Ok(fields);
+                }
+                Some(',') => self.pos += 1,
+                _ => {
+                    let name = self.word().to_lowercase();
+                    self.skip_whitespace();
+                    if name.is_empty() || self.peek() != Some('=') {
+                        return Err(CoreError::Import(format!(
+                            "Expected 'field = value' near byte {}",
+                            self.pos
+                        )));
+                    }
+                    self.pos += 1;
+                    let value = self.value()?;
+                    fields.insert(name, value);
+                }
+            }
+        }
+    }
+
+    fn entries(&mut self) -> Result<Vec<HashMap<String, String>>, CoreError> {
+        let mut entries = Vec::new();
+
+        while let Some(at) = self.input[self.pos..].find('@') {
+            self.pos += at + 1;
+            let entry_type = self.word().to_lowercase();
+            self.skip_whitespace();
+            let close = match self.peek() {
+                Some('{') => '}',
+                Some('(') => ')',
+                _ => continue,
+            };
+
+            match entry_type.as_str() {
+                "comment" | "preamble" => {
+                    self.delimited(close)?;
+                }
+                "string" => {
+                    self.pos += 1;
+                    for (name, value) in self.fields(close)? {
+                        self.macros.insert(name, value);
+                    }
+                }
+                _ => {
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    self.word();
+                    entries.push(self.fields(close)?);
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+pub fn parse_bibtex(data: &str) -> Result<Vec<BibRecord>, CoreError> {
+    let mut parser = BibtexParser {
+        input: data,
+        pos: 0,
+        macros: HashMap::new(),
+    };
+
+    Ok(parser
+        .entries()?
+        .into_iter()
+        .map(|fields| {
+            let get = |name: &str| fields.get(name).map(|value| clean_latex(value));
+            let is_arxiv = ["archiveprefix", "eprinttype"]
+                .iter()
+                .any(|name| get(name).is_some_and(|value| value.eq_ignore_ascii_case("arxiv")));
+
+            let venue = get("journal")
+                .or_else(|| get("journaltitle"))
+                .or_else(|| get("booktitle"))
+                .and_then(non_empty);
+
+            BibRecord {
+                title: get("title").unwrap_or_default(),
+                authors: fields
+                    .get("author")
+                    .map(|authors| split_bibtex_authors(authors))
+                    .unwrap_or_default(),
+                year: get("year")
+                    .or_else(|| get("date"))
+                    .and_then(|year| parse_year(&year)),
+                doi: get("doi").and_then(|doi| clean_doi(&doi)),
+                arxiv_id: get("eprint")
+                    .filter(|_| is_arxiv)
+                    .and_then(non_empty)
+                    .or_else(|| venue.as_deref().and_then(arxiv_from_text))
+                    .or_else(|| get("url").and_then(|url| arxiv_from_text(&url))),
+                venue: venue.filter(|venue| arxiv_from_text(venue).is_none()),
+                url: get("url").and_then(non_empty),
+                keywords: get("keywords")
+                    .map(|keywords| split_keywords(&keywords))
+                    .unwrap_or_default(),
+            }
+        })
+        .collect())
+}
+
+fn csl_str(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+fn csl_author(author: &Value) -> Option<String> {
+    if let Some(literal) = csl_str(author, "literal") {
+        return Some(literal);
+    }
+    let given = csl_str(author, "given").unwrap_or_default();
+    let family = csl_str(author, "family")?;
+    Some(format!("{} {}", given, family).trim().to_string())
+}
+
+fn csl_year(issued: &Value) -> Option<i32> {
+    issued
+        .get("date-parts")
+        .and_then(|parts| parts.get(0))
+        .and_then(|parts| parts.get(0))
+        .and_then(|year| {
+            year.as_i64()
+                .map(|year| year as i32)
+                .or_else(|| year.as_str().and_then(parse_year))
+        })
+        .or_else(|| {
+            issued
+                .get("raw")
+                .and_then(Value::as_str)
+                .and_then(parse_year)
+        })
+}
+
+pub fn parse_csl_json(data: &str) -> Result<Vec<BibRecord>, CoreError> {
+    let value: Value = serde_json::from_str(data)
+        .map_err(|e| CoreError::Import(format!("Invalid JSON: {}", e)))?;
+    let entries = value.as_array().ok_or_else(|| {
+        CoreError::Import("Expected a JSON array of items. Is this a CSL-JSON file?".into())
+    })?;
+
+    Ok(entries
+        .iter()
+        .map(|entry| {
+            let url = csl_str(entry, "URL");
+            let arxiv_id = ["number", "note", "container-title"]
+                .iter()
+                .filter_map(|key| csl_str(entry, key))
+                .chain(url.clone())
+                .find_map(|text| arxiv_from_text(&text));
+
+            BibRecord {
+                title: csl_str(entry, "title").unwrap_or_default(),
+                authors: entry
+                    .get("author")
+                    .and_then(Value::as_array)
+                    .map(|authors| authors.iter().filter_map(csl_author).collect())
+                    .unwrap_or_default(),
+                year: entry.get("issued").and_then(csl_year),
+                venue: csl_str(entry, "container-title")
+                    .filter(|venue| arxiv_from_text(venue).is_none()),
+                doi: csl_str(entry, "DOI").and_then(|doi| clean_doi(&doi)),
+                arxiv_id,
+                url,
+                keywords: csl_str(entry, "keyword")
+                    .map(|keywords| split_keywords(&keywords))
+                    .unwrap_or_default(),
+            }
+        })
+        .collect())
+}
+
+pub fn bib_record_to_item(record: &BibRecord) -> Result<Item, CoreError> {
+    let link = record
+        .url
+        .clone()
+        .or_else(|| {
+            record
+                .doi
+                .as_ref()
+                .map(|doi| format!("https://doi.org/{}", doi))
+        })
+        .or_else(|| {
+            record
+                .arxiv_id
+                .as_ref()
+                .map(|id| format!("https://arxiv.org/abs/{}", id))
+        });
+
+    let name = match (&record.title, &link) {
+        (title, _) if !title.is_empty() => title.clone(),
+        (_, Some(link)) => link.clone(),
+        _ => {
+            return Err(CoreError::Import(
+                "Found an entry with neither a title nor a link".to_string(),
+            ));
+        }
+    };
+
+    let mut item = create_item(
+        fit_name(&name),
+        Some(ItemType::AcademicPaper.as_string().to_string()),
+        Some(fit_author(&record.authors.join(", "))),
+        link,
+        None,
+        None,
+    )?;
+    item.tags = record.keywords.clone();
+
+    let bib = BibFields {
+        doi: record.doi.clone(),
+        arxiv_id: record.arxiv_id.clone(),
+        venue: record.venue.clone(),
+        year: record.year,
+        authors: record.authors.clone(),
+    };
+    item.bib = Some(bib).filter(|bib| !bib.is_empty());
+
+    Ok(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BIBTEX: &str = r#"
+@string{jacm = "Journal of the {ACM}"}
+@comment{ exported from somewhere }
+
+@article{godel1931,
+  title     = {{\"U}ber formal unentscheidbare S{\"a}tze},
+  author    = {G{\"o}del, Kurt and {Barnes and Noble} and others},
+  journal   = jacm # " (reprint)",
+  year      = 1931,
+  doi       = {https://doi.org/10.1007/BF01700692},
+  keywords  = {logic; incompleteness},
+}
+
+@misc(vaswani2017,
+  title = "Attention Is All You Need",
+  author = "Vaswani, Ashish and Shazeer, Noam",
+  eprint = {1706.03762},
+  archivePrefix = {arXiv},
+  date = {2017-06-12}
+)
+"#;
+
+    #[test]
+    fn bibtex_entries_are_cleaned_up() {
+        let records = parse_bibtex(BIBTEX).unwrap();
+        assert_eq!(records.len(), 2);
+
+        let godel = &records[0];
+        assert_eq!(
+            godel.title,
+            "U\u{308}ber formal unentscheidbare Sa\u{308}tze"
+        );
+        assert_eq!(godel.authors, ["Kurt Go\u{308}del", "Barnes and Noble"]);
+        assert_eq!(godel.venue.as_deref(), Some("Journal of the ACM (reprint)"));
+        assert_eq!(godel.year, Some(1931));
+        assert_eq!(godel.doi.as_deref(), Some("10.1007/BF01700692"));
+        assert_eq!(godel.keywords, ["logic", "incompleteness"]);
+
+        let attention = &records[1];
+        assert_eq!(attention.authors, ["Ashish Vaswani", "Noam Shazeer"]);
+        assert_eq!(attention.arxiv_id.as_deref(), Some("1706.03762"));
+        assert_eq!(attention.year, Some(2017));
+        assert!(attention.venue.is_none());
+    }
+
+    #[test]
+    fn records_link_to_their_doi_or_arxiv_page() {
+        let records = parse_bibtex(BIBTEX).unwrap();
+
+        let item = bib_record_to_item(&records[0]).unwrap();
+        assert_eq!(item.link, "https://doi.org/10.1007/BF01700692");
+        assert_eq!(item.item_type, ItemType::AcademicPaper);
+        assert_eq!(item.author, "Kurt Go\u{308}del, Barnes and Noble");
+
+        let item = bib_record_to_item(&records[1]).unwrap();
+        assert_eq!(item.link, "https://arxiv.org/abs/1706.03762");
+        assert_eq!(item.bib.unwrap().year, Some(2017));
+
+        assert!(bib_record_to_item(&BibRecord::default()).is_err());
+    }
+
+    #[test]
+    fn malformed_bibtex_is_an_error() {
+        assert!(parse_bibtex("@article{key, title = {unterminated").is_err());
+        assert!(parse_bibtex("@article{key, = {no name}}").is_err());
+        assert!(parse_bibtex("@article{key, title = {x}").is_err());
+        assert!(parse_bibtex("no entries at all").unwrap().is_empty());
+    }
+
+    #[test]
+    fn csl_json_items_are_read() {
+        let json = r#"[
+            {"title": "Les Misérables en chiffres", "author": [{"family": "Hugo", "given": "Victor"},
+             {"literal": "Équipe de recherche"}], "issued": {"date-parts": [["2019", 5]]},
+             "container-title": "Revue", "DOI": "doi:10.1000/xyz", "keyword": "stats, books"},
+            {"title": "A preprint", "URL": "https://arxiv.org/pdf/2101.00001v2.pdf",
+             "issued": {"raw": "circa 2021"}}
+        ]"#;
+        let records = parse_csl_json(json).unwrap();
+
+        assert_eq!(records[0].authors, ["Victor Hugo", "Équipe de recherche"]);
+        assert_eq!(records[0].year, Some(2019));
+        assert_eq!(records[0].venue.as_deref(), Some("Revue"));
+        assert_eq!(records[0].doi.as_deref(), Some("10.1000/xyz"));
+        assert_eq!(records[0].keywords, ["stats", "books"]);
+
+        assert_eq!(records[1].arxiv_id.as_deref(), Some("2101.00001v2"));
+        assert_eq!(records[1].year, Some(2021));
+    }
+
+    #[test]
+    fn malformed_csl_json_is_an_error() {
+        assert!(parse_csl_json("{\"title\": \"not a list\"}").is_err());
+        assert!(parse_csl_json("[{\"title\": ").is_err());
+        let records = parse_csl_json("[{\"issued\": {\"date-parts\": \"nope\"}}]").unwrap();
+        assert!(records[0].year.is_none());
+    }
+}
@@ -0,0 +1,152 @@
+use super::lines::looks_like_url;
+use super::netscape::Bookmark;
+use crate::CoreError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use strum::EnumString;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+pub enum Browser {
+    #[strum(serialize = "firefox")]
+    Firefox,
+    #[strum(serialize = "chromium")]
+    Chromium,
+}
+
+impl Browser {
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            Browser::Firefox => "firefox",
+            Browser::Chromium => "chromium",
+        }
+    }
+
+    pub fn bookmarks_file(&self) -> &'static str {
+        match self {
+            Browser::Firefox => "places.sqlite",
+            Browser::Chromium => "Bookmarks",
+        }
+    }
+}
+
+// A bookmark from a browser profile, keyed by the browser's own stable GUID so a folder can
+// be synced repeatedly even after its links were edited or removed in folio.
+#[derive(Debug, Clone)]
+pub struct BrowserBookmark {
+    pub guid: String,
+    pub bookmark: Bookmark,
+}
+
+// Chromium stores times as microseconds since 1601-01-01.
+fn chromium_time(value: Option<&Value>) -> Option<DateTime<Utc>> {
+    const UNIX_OFFSET_MICROS: i64 = 11_644_473_600_000_000;
+    let micros: i64 = value?.as_str()?.parse().ok()?;
+    DateTime::from_timestamp_micros(micros.checked_sub(UNIX_OFFSET_MICROS)?).filter(|_| micros > 0)
+}
+
+fn walk_chromium(node: &Value, folders: &mut Vec<String>, bookmarks: &mut Vec<BrowserBookmark>) {
+    let name = node["name"].as_str().unwrap_or_default().trim().to_string();
+
+    match node["type"].as_str() {
+        Some("url") => {
+            let url = node["url"].as_str().unwrap_or_default();
+            if !looks_like_url(url) {
+                return;
+            }
+            bookmarks.push(BrowserBookmark {
+                guid: node["guid"]
+                    .as_str()
+                    .or(node["id"].as_str())
+                    .unwrap_or(url)
+                    .to_string(),
+                bookmark: Bookmark {
+                    title: name,
+                    url: url.to_string(),
+                    add_date: chromium_time(node.get("date_added")),
+                    folders: folders.clone(),
+                },
+            });
+        }
+        Some("folder") => {
+            folders.push(name);
+            for child in node["children"].as_array().into_iter().flatten() {
+                walk_chromium(child, folders, bookmarks);
+            }
+            folders.pop();
+        }
+        _ => {}
+    }
+}
+
+// The roots ("Bookmarks bar", "Other bookmarks", "Mobile bookmarks") are not part of the
+// folder path, matching how Firefox bookmarks are read.
+pub fn parse_chromium_bookmarks(json: &str) -> Result<Vec<BrowserBookmark>, CoreError> {
+    let data: Value = serde_json::from_str(json)
+        .map_err(|e| CoreError::Import(format!("Invalid Chromium bookmarks file: {}", e)))?;
+    let roots = data["roots"].as_object().ok_or_else(|| {
+        CoreError::Import("Invalid Chromium bookmarks file: missing 'roots'".to_string())
+    })?;
+
+    let mut bookmarks = Vec::new();
+    for root in roots.values() {
+        for child in root["children"].as_array().into_iter().flatten() {
+            walk_chromium(child, &mut Vec::new(), &mut bookmarks);
+        }
+    }
+
+    Ok(bookmarks)
+}
+
+// "Read later" matches that folder at any depth, "Reading/Read later" a nested one.
+// Subfolders of a matching folder are included.
+pub fn in_folder(folders: &[String], wanted: &str) -> bool {
+    let wanted: Vec<String> = wanted
+        .split('/')
+        .map(|part| part.trim().to_lowercase())
+        .filter(|part| !part.is_empty())
+        .collect();
+    if wanted.is_empty() {
+        return true;
+    }
+
+    let folders: Vec<String> = folders.iter().map(|folder| folder.to_lowercase()).collect();
+    folders.windows(wanted.len()).any(|window| window == wanted)
+}
+
+pub fn folder_counts(bookmarks: &[BrowserBookmark]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for bookmark in bookmarks {
+        let folders = &bookmark.bookmark.folders;
+        for depth in 1..=folders.len() {
+            *counts.entry(folders[..depth].join("/")).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BrowserSyncState {
+    #[serde(default)]
+    pub sources: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl BrowserSyncState {
+    pub fn source_key(browser: Browser, profile: &str) -> String {
+        format!("{}:{}", browser.as_string(), profile)
+    }
+
+    pub fn is_imported(&self, source: &str, guid: &str) -> bool {
+        self.sources
+            .get(source)
+            .is_some_and(|guids| guids.contains(guid))
+    }
+
+    pub fn mark_imported<'a>(&mut self, source: &str, guids: impl IntoIterator<Item = &'a str>) {
+        self.sources
+            .entry(source.to_string())
+            .or_default()
+            .extend(guids.into_iter().map(str::to_string));
+    }
+}
@@ -0,0 +1,354 @@
+use crate::validation::{fit_author, fit_name};
+use crate::{CoreError, Highlight, Item, ItemType, Kind, Status, create_item, update_timestamps};
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+const SEPARATOR: &str = "==========";
+
+#[derive(Debug, Clone)]
+pub struct ClippingBook {
+    pub title: String,
+    pub author: String,
+    pub highlights: Vec<Highlight>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClippingKind {
+    Highlight,
+    Note,
+    Bookmark,
+}
+
+struct Clipping {
+    title: String,
+    author: String,
+    kind: ClippingKind,
+    location: Option<String>,
+    added_at: Option<DateTime<Utc>>,
+    text: String,
+}
+
+// "Title (Last, First)" -> ("Title", "First Last"). Only the last parenthesised group is the
+// author, since titles often carry their own parentheses.
+fn split_title_line(line: &str) -> (String, String) {
+    let line = line.trim_start_matches('\u{feff}').trim();
+
+    let Some(open) = line.rfind('(').filter(|_| line.ends_with(')')) else {
+        return (line.to_string(), String::new());
+    };
+    let title = line[..open].trim().to_string();
+    let author = line[open + 1..line.len() - 1].trim();
+
+    let author = match author.split_once(',') {
+        Some((family, given)) if !author.contains(';') && !given.contains(',') => {
+            format!("{} {}", given.trim(), family.trim())
+        }
+        _ => author.to_string(),
+    };
+
+    if title.is_empty() {
+        (line.to_string(), String::new())
+    } else {
+        (title, author)
+    }
+}
+
+fn parse_added_on(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    ["%A, %B %d, %Y %I:%M:%S %p", "%A, %d %B %Y %H:%M:%S"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .map(|time| time.and_utc())
+}
+
+fn parse_meta_line(line: &str) -> (ClippingKind, Option<String>, Option<DateTime<Utc>>) {
+    let mut kind = ClippingKind::Highlight;
+    let mut location = None;
+    let mut page = None;
+    let mut added_at = None;
+
+    for part in line.trim_start_matches('-').split('|').map(str::trim) {
+        let lower = part.to_ascii_lowercase();
+
+        if lower.contains("bookmark") {
+            kind = ClippingKind::Bookmark;
+        } else if lower.contains("your note") {
+            kind = ClippingKind::Note;
+        }
+
+        if let Some(added) = part.strip_prefix("Added on ") {
+            added_at = parse_added_on(added);
+        } else if let Some(index) = lower.find("location ") {
+            location = Some(format!(
+                "location {}",
+                part[index + "location ".len()..].trim()
+            ));
+        } else if let Some(index) = lower.find("page ") {
+            page = Some(format!("page {}", part[index + "page ".len()..].trim()));
+        }
+    }
+
+    (kind, location.or(page), added_at)
+}
+
+fn parse_clipping(block: &str) -> Option<Clipping> {
+    let mut lines = block.trim_matches(['\r', '\n']).lines();
+    let (title, author) = split_title_line(lines.next()?);
+    let (kind, location, added_at) = parse_meta_line(lines.next()?);
+
+    let text = lines
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+
+    Some(Clipping {
+        title,
+        author,
+        kind,
+        location,
+        added_at,
+        text,
+    })
+}
+
+fn location_range(location: &Option<String>) -> Option<(u32, u32)> {
+    let range = location.as_deref()?.strip_prefix("location ")?;
+    let (start, end) = range.split_once('-').unwrap_or((range, range));
+    let start: u32 = start.trim().parse().ok()?;
+    let end = end.trim();
+
+    // Kindle abbreviates ranges, so "1234-56" means 1234 to 1256.
+    let start_digits = start.to_string();
+    let end = match end.parse::<u32>().ok()? {
+        end if end >= start => end,
+        _ if end.len() < start_digits.len() => {
+            let prefix = &start_digits[..start_digits.len() - end.len()];
+            format!("{}{}", prefix, end).parse().ok()?
+        }
+        _ => start,
+    };
+    Some((start, end))
+}
+
+pub fn parse_clippings(data: &str) -> Vec<ClippingBook> {
+    let mut books: Vec<ClippingBook> = Vec::new();
+
+    for clipping in data.split(SEPARATOR).filter_map(parse_clipping) {
+        if clipping.kind == ClippingKind::Bookmark || clipping.text.is_empty() {
+            continue;
+        }
+
+        let index = match books
+            .iter()
+            .position(|book| book.title == clipping.title && book.author == clipping.author)
+        {
+            Some(index) => index,
+            None => {
+                books.push(ClippingBook {
+                    title: clipping.title.clone(),
+                    author: clipping.author.clone(),
+                    highlights: Vec::new(),
+                });
+                books.len() - 1
+            }
+        };
+        let highlights = &mut books[index].highlights;
+
+        // Kindle stores a note as its own clipping at the location where the highlight ends.
+        if clipping.kind == ClippingKind::Note
+            && let Some((location, _)) = location_range(&clipping.location)
+            && let Some(highlight) = highlights.iter_mut().rev().find(|highlight| {
+                highlight.note.is_empty()
+                    && location_range(&highlight.location)
+                        .is_some_and(|(start, end)| (start..=end).contains(&location))
+            })
+        {
+            highlight.note = clipping.text;
+            continue;
+        }
+
+        let (text, note) = match clipping.kind {
+            ClippingKind::Note => (String::new(), clipping.text),
+            _ => (clipping.text, String::new()),
+        };
+
+        highlights.push(Highlight {
+            text,
+            note,
+            location: clipping.location,
+            added_at: clipping.added_at,
+        });
+    }
+
+    books
+}
+
+fn title_key(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// E-readers often append a subtitle or edition to the title, so a match on the part
+// before ':' or '(' also counts.
+pub fn title_matches(item_name: &str, book_title: &str) -> bool {
+    let item = title_key(item_name);
+    if item.is_empty() {
+        return false;
+    }
+
+    let main_title = book_title
+        .split([':', '(', '['])
+        .next()
+        .unwrap_or(book_title);
+
+    item == title_key(book_title) || item == title_key(main_title)
+}
+
+pub fn merge_highlights(item: &mut Item, highlights: &[Highlight]) -> usize {
+    let mut added = 0;
+    for highlight in highlights {
+        let exists = item.highlights.iter().any(|existing| {
+            existing.text == highlight.text && existing.location == highlight.location
+        });
+
+        if !exists {
+            item.highlights.push(highlight.clone());
+            added += 1;
+        } else if !highlight.note.is_empty()
+            && let Some(existing) = item.highlights.iter_mut().find(|existing| {
+                existing.text == highlight.text
+                    && existing.location == highlight.location
+                    && existing.note.is_empty()
+            })
+        {
+            existing.note = highlight.note.clone();
+        }
+    }
+    added
+}
+
+pub fn book_to_item(book: &ClippingBook) -> Result<Item, CoreError> {
+    let mut item = create_item(
+        fit_name(&book.title),
+        Some(ItemType::Other.as_string().to_string()),
+        Some(fit_author(&book.author)),
+        None,
+        None,
+        Some(Kind::Reference.as_string().to_string()),
+    )?;
+    item.highlights = book.highlights.clone();
+
+    let dates = book
+        .highlights
+        .iter()
+        .filter_map(|highlight| highlight.added_at);
+    if let Some(first) = dates.clone().min() {
+        item.added_at = first;
+    }
+    item.finished_at = dates.max();
+    item.status = Status::Done;
+    update_timestamps(&mut item);
+
+    Ok(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIPPINGS: &str = "\u{feff}Dune (Herbert, Frank)\r\n\
+        - Your Highlight on page 3 | Location 1234-36 | Added on Monday, March 4, 2024 9:15:02 PM\r\n\
+        \r\n\
+        Fear is the mind-killer.\r\n\
+        ==========\r\n\
+        Dune (Herbert, Frank)\r\n\
+        - Your Note on page 3 | Location 1236 | Added on Monday, March 4, 2024 9:16:00 PM\r\n\
+        \r\n\
+        The litany\r\n\
+        ==========\r\n\
+        Dune (Herbert, Frank)\r\n\
+        - Your Bookmark on page 9 | Location 2000 | Added on Monday, March 4, 2024 9:20:00 PM\r\n\
+        \r\n\
+        \r\n\
+        ==========\r\n\
+        Der Zauberberg (Roman) (Mann, Thomas)\r\n\
+        - Ihre Markierung auf Seite 12 | page 12 | Added on Dienstag, 5 März 2024 10:00:00\r\n\
+        \r\n\
+        Über die Zeit.\r\n\
+        ==========\r\n";
+
+    #[test]
+    fn highlights_are_grouped_by_book_with_their_notes() {
+        let books = parse_clippings(CLIPPINGS);
+        assert_eq!(books.len(), 2);
+
+        let dune = &books[0];
+        assert_eq!(dune.title, "Dune");
+        assert_eq!(dune.author, "Frank Herbert");
+        assert_eq!(dune.highlights.len(), 1);
+        assert_eq!(dune.highlights[0].text, "Fear is the mind-killer.");
+        assert_eq!(dune.highlights[0].note, "The litany");
+        assert_eq!(
+            dune.highlights[0].location.as_deref(),
+            Some("location 1234-36")
+        );
+        assert!(dune.highlights[0].added_at.is_some());
+
+        let mann = &books[1];
+        assert_eq!(mann.title, "Der Zauberberg (Roman)");
+        assert_eq!(mann.author, "Thomas Mann");
+        assert_eq!(mann.highlights[0].text, "Über die Zeit.");
+        assert_eq!(mann.highlights[0].location.as_deref(), Some("page 12"));
+        assert!(mann.highlights[0].added_at.is_none());
+    }
+
+    #[test]
+    fn books_become_done_references() {
+        let books = parse_clippings(CLIPPINGS);
+        let item = book_to_item(&books[0]).unwrap();
+
+        assert_eq!(item.kind, Kind::Reference);
+        assert_eq!(item.status, Status::Done);
+        assert_eq!(item.added_at, books[0].highlights[0].added_at.unwrap());
+        assert_eq!(item.highlights, books[0].highlights);
+    }
+
+    #[test]
+    fn merging_skips_highlights_already_saved() {
+        let books = parse_clippings(CLIPPINGS);
+        let mut item = book_to_item(&books[0]).unwrap();
+        item.highlights[0].note.clear();
+
+        assert_eq!(merge_highlights(&mut item, &books[0].highlights), 0);
+        assert_eq!(item.highlights[0].note, "The litany");
+
+        let mut extra = books[1].highlights.clone();
+        extra.extend(books[1].highlights.clone());
+        assert_eq!(merge_highlights(&mut item, &extra), 1);
+    }
+
+    #[test]
+    fn titles_match_without_subtitles() {
+        assert!(title_matches("Dune", "Dune: Deluxe Edition"));
+        assert!(title_matches("der zauberberg", "Der Zauberberg (Roman)"));
+        assert!(title_matches("Über Kaffee", "ÜBER KAFFEE"));
+        assert!(!title_matches("Dune Messiah", "Dune"));
+        assert!(!title_matches("", "Dune"));
+    }
+
+    #[test]
+    fn malformed_clippings_are_skipped() {
+        assert!(parse_clippings("").is_empty());
+        assert!(parse_clippings("Only a title line\r\n==========").is_empty());
+        assert!(parse_clippings("==========\r\n==========").is_empty());
+
+        let books = parse_clippings("Untitled\n- Your Highlight | Location x-y\n\ntext\n");
+        assert_eq!(books[0].title, "Untitled");
+        assert!(location_range(&books[0].highlights[0].location).is_none());
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{CoreError, Item, ItemType, TypeRules, create_item, is_supported_link, link_error};
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub struct LineError {
+    pub line: usize,
+    pub message: String,
+}
+
+pub(super) fn looks_like_url(text: &str) -> bool {
+    text.contains("://") && is_supported_link(text)
+}
+
+fn parse_line(
+    line: &str,
+    default_type: Option<&ItemType>,
+    rules: &TypeRules,
+) -> Result<Item, String> {
+    let columns: Vec<&str> = line.split('|').map(str::trim).collect();
+
+    let (title, url, item_type) = match columns.as_slice() {
+        [url] => ("", *url, None),
+        [title, url] => (*title, *url, None),
+        [title, url, item_type] => (*title, *url, Some(*item_type)),
+        _ => return Err("expected `url` or `title | url | type`".to_string()),
+    };
+
+    if url.is_empty() {
+        return Err("missing URL".to_string());
+    }
+    if let Some(error) = link_error(url) {
+        return Err(format!("link {}", error));
+    }
+
+    let item_type = match item_type.filter(|t| !t.is_empty()) {
+        Some(t) => {
+            ItemType::from_str(&t.to_lowercase()).map_err(|_| format!("unknown type '{}'", t))?
+        }
+        None => default_type
+            .cloned()
+            .unwrap_or_else(|| rules.infer_or_default(url)),
+    };
+
+    let name = if title.is_empty() { url } else { title };
+
+    create_item(
+        name.to_string(),
+        Some(item_type.as_string().to_string()),
+        None,
+        Some(url.to_string()),
+        None,
+        None,
+    )
+    .map_err(|e: CoreError| e.to_string())
+}
+
+// Blank lines and lines starting with '#' are skipped, so link lists can carry comments.
+// A type column wins over the default type, which wins over the type guessed from the link.
+pub fn parse_link_lines(
+    text: &str,
+    default_type: Option<&ItemType>,
+    rules: &TypeRules,
+) -> (Vec<Item>, Vec<LineError>) {
+    let mut items = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match parse_line(line, default_type, rules) {
+            Ok(item) => items.push(item),
+            Err(message) => errors.push(LineError {
+                line: index + 1,
+                message,
+            }),
+        }
+    }
+
+    (items, errors)
+}
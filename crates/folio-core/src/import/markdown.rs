@@ -0,0 +1,271 @@
+use super::lines::looks_like_url;
+use crate::validation::fit_name;
+use crate::{CoreError, Item, ItemType, TypeRules, create_item};
+
+#[derive(Debug, Clone)]
+pub struct MarkdownLink {
+    pub name: String,
+    pub url: String,
+    pub heading: Option<String>,
+}
+
+fn parse_heading(line: &str) -> Option<(usize, String)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    Some((level, rest.trim().trim_end_matches('#').trim().to_string()))
+}
+
+// "- [ ] text" -> Some((false, "text")), "- [x] text" -> Some((true, "text")).
+fn parse_task(line: &str) -> Option<(bool, &str)> {
+    let rest = line
+        .strip_prefix(['-', '*', '+'])
+        .or_else(|| {
+            let digits = line.chars().take_while(char::is_ascii_digit).count();
+            line.get(digits..)
+                .filter(|_| digits > 0)
+                .and_then(|rest| rest.strip_prefix(['.', ')']))
+        })?
+        .strip_prefix(' ')?
+        .trim_start();
+
+    let checked = match rest.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    Some((checked, &rest[3..]))
+}
+
+fn closing(text: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in text.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            if depth == 0 {
+                return Some(index);
+            }
+            depth -= 1;
+        }
+    }
+    None
+}
+
+fn clean_link_text(text: &str) -> String {
+    // A linked image, [![alt](src)](url), is named after its alt text.
+    let text = match text.trim().strip_prefix("![") {
+        Some(rest) => closing(rest, '[', ']').map_or(rest, |end| &rest[..end]),
+        None => text,
+    };
+
+    let text = ["**", "__", "~~", "`"]
+        .iter()
+        .fold(text.to_string(), |text, marker| text.replace(marker, ""));
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Inline links only; images and relative links to other notes are skipped.
+fn inline_links(line: &str) -> Vec<(String, String)> {
+    let mut links = Vec::new();
+    let mut rest = line;
+
+    while let Some(start) = rest.find('[') {
+        let is_image = rest[..start].ends_with('!');
+        let after = &rest[start + 1..];
+
+        let Some(text_end) = closing(after, '[', ']') else {
+            break;
+        };
+        let text = &after[..text_end];
+        let tail = &after[text_end + 1..];
+
+        let Some(target) = tail.strip_prefix('(') else {
+            rest = after;
+            continue;
+        };
+        let Some(target_end) = closing(target, '(', ')') else {
+            rest = after;
+            continue;
+        };
+        rest = &target[target_end + 1..];
+
+        let url = target[..target_end]
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .trim_start_matches('<')
+            .trim_end_matches('>');
+
+        if !is_image && looks_like_url(url) {
+            links.push((clean_link_text(text), url.to_string()));
+        }
+    }
+
+    links
+}
+
+// With a heading, only links in sections whose heading contains that text (case-insensitive)
+// are taken, including their subsections. With tasks_only, only open checkbox tasks count.
+pub fn parse_markdown_links(
+    text: &str,
+    heading: Option<&str>,
+    tasks_only: bool,
+) -> Vec<MarkdownLink> {
+    let wanted = heading.map(str::to_lowercase);
+    let mut links = Vec::new();
+    let mut in_code = false;
+    let mut current_heading: Option<String> = None;
+    let mut matched_level: Option<usize> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+
+        if let Some((level, title)) = parse_heading(trimmed) {
+            if matched_level.is_some_and(|matched| level <= matched) {
+                matched_level = None;
+            }
+            if matched_level.is_none()
+                && let Some(wanted) = &wanted
+                && title.to_lowercase().contains(wanted.as_str())
+            {
+                matched_level = Some(level);
+            }
+            current_heading = Some(title);
+            continue;
+        }
+
+        if wanted.is_some() && matched_level.is_none() {
+            continue;
+        }
+
+        let content = match parse_task(trimmed) {
+            Some((false, content)) => content,
+            Some((true, _)) if tasks_only => continue,
+            _ if tasks_only => continue,
+            _ => trimmed,
+        };
+
+        for (name, url) in inline_links(content) {
+            links.push(MarkdownLink {
+                name,
+                url,
+                heading: current_heading.clone(),
+            });
+        }
+    }
+
+    links
+}
+
+pub fn markdown_link_to_item(
+    link: &MarkdownLink,
+    item_type: Option<&ItemType>,
+    rules: &TypeRules,
+) -> Result<Item, CoreError> {
+    let name = if link.name.is_empty() {
+        &link.url
+    } else {
+        &link.name
+    };
+
+    let item_type = item_type
+        .cloned()
+        .unwrap_or_else(|| rules.infer_or_default(&link.url));
+
+    create_item(
+        fit_name(name),
+        Some(item_type.as_string().to_string()),
+        None,
+        Some(link.url.clone()),
+        None,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTE: &str = "\
+# Reading list
+
+- [ ] [**Über** `Lesen`](https://example.de/lesen) and [another](<https://example.com/b>)
+- [x] [Already read](https://example.com/read)
+1. [ ] [東京の本屋](https://example.jp/a \"title\")
+
+## Videos ##
+* [![Talk thumbnail](https://img.example.com/t.png)](https://example.com/talk)
+![just an image](https://img.example.com/i.png)
+[relative note](other-note.md)
+
+```
+[in a code block](https://example.com/code)
+```
+
+# Elsewhere
+[Wiki (disambiguation)](https://en.wikipedia.org/wiki/Foo_(bar))
+";
+
+    #[test]
+    fn inline_links_are_collected_with_their_heading() {
+        let links = parse_markdown_links(NOTE, None, false);
+        let names: Vec<&str> = links.iter().map(|link| link.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "Über Lesen",
+                "another",
+                "Already read",
+                "東京の本屋",
+                "Talk thumbnail",
+                "Wiki (disambiguation)"
+            ]
+        );
+
+        assert_eq!(links[1].url, "https://example.com/b");
+        assert_eq!(links[3].url, "https://example.jp/a");
+        assert_eq!(links[4].heading.as_deref(), Some("Videos"));
+        assert_eq!(links[5].url, "https://en.wikipedia.org/wiki/Foo_(bar)");
+    }
+
+    #[test]
+    fn headings_and_tasks_narrow_the_links() {
+        let section = parse_markdown_links(NOTE, Some("READING"), false);
+        assert_eq!(section.len(), 5);
+        assert!(section.iter().all(|link| link.heading.is_some()));
+
+        let open_tasks = parse_markdown_links(NOTE, None, true);
+        let names: Vec<&str> = open_tasks.iter().map(|link| link.name.as_str()).collect();
+        assert_eq!(names, ["Über Lesen", "another", "東京の本屋"]);
+    }
+
+    #[test]
+    fn malformed_markdown_does_not_panic() {
+        let text = "- [ü] [unclosed](https://example.com\n[no target] here\n#NoSpace\n- [";
+        assert!(parse_markdown_links(text, None, false).is_empty());
+        assert!(parse_markdown_links("", Some("x"), true).is_empty());
+    }
+
+    #[test]
+    fn links_become_items() {
+        let links = parse_markdown_links("[](https://example.com/empty)", None, false);
+        let item = markdown_link_to_item(&links[0], Some(&ItemType::Video), &TypeRules::builtin())
+            .unwrap();
+        assert_eq!(item.name, "https://example.com/empty");
+        assert_eq!(item.item_type, ItemType::Video);
+    }
+}
@@ -0,0 +1,81 @@
+pub(crate) fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find(';').filter(|end| *end <= 10) else {
+            decoded.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+
+        let entity = &rest[1..end];
+        let replacement = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+
+        match replacement {
+            Some(c) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+pub(crate) fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search_from = 0;
+
+    while let Some(found) = lower[search_from..].find(name) {
+        let start = search_from + found;
+        search_from = start + name.len();
+
+        let preceded_by_space = lower[..start]
+            .chars()
+            .last()
+            .is_some_and(char::is_whitespace);
+        let after = lower[search_from..].trim_start();
+        if !preceded_by_space || !after.starts_with('=') {
+            continue;
+        }
+
+        let value_start = tag.len() - after.len() + 1;
+        let value = tag[value_start..].trim_start();
+
+        return Some(match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let value = &value[1..];
+                decode_entities(&value[..value.find(quote).unwrap_or(value.len())])
+            }
+            _ => decode_entities(
+                &value[..value
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .unwrap_or(value.len())],
+            ),
+        });
+    }
+
+    None
+}
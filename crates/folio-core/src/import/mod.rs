@@ -0,0 +1,270 @@
+use crate::validation::truncate;
+use crate::{
+    Config, CoreError, Item, OverflowStrategy, Status, add_item_to_inbox, add_item_to_someday,
+    link_key,
+};
+use std::collections::HashSet;
+use strum::EnumString;
+
+mod bib;
+pub use bib::{BibRecord, bib_record_to_item, parse_bibtex, parse_csl_json};
+
+mod browser;
+pub use browser::{
+    Browser, BrowserBookmark, BrowserSyncState, folder_counts, in_folder, parse_chromium_bookmarks,
+};
+
+mod clippings;
+pub use clippings::{ClippingBook, book_to_item, merge_highlights, parse_clippings, title_matches};
+
+mod lines;
+pub use lines::{LineError, parse_link_lines};
+
+mod markdown;
+pub use markdown::{MarkdownLink, markdown_link_to_item, parse_markdown_links};
+
+pub(crate) mod markup;
+mod netscape;
+pub use netscape::{Bookmark, FolderMode, bookmark_to_item, parse_netscape_bookmarks};
+
+mod opml;
+mod org;
+pub use opml::{Outline, outline_to_item, parse_opml};
+pub use org::{OrgEntry, OrgPlan, OrgSection, parse_org, plan_org_import};
+
+mod services;
+pub use services::{Service, ServiceEntry, entry_to_item, parse_service_export};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+pub enum ImportOverflow {
+    #[strum(serialize = "archive")]
+    Archive,
+    #[strum(serialize = "someday")]
+    Someday,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportPlan {
+    pub inbox_items: Vec<Item>,
+    pub someday_items: Vec<Item>,
+    pub to_archive: Vec<Item>,
+    pub added_to_inbox: usize,
+    pub added_to_someday: usize,
+    pub added_to_archive: usize,
+    pub already_read: usize,
+    pub duplicates: usize,
+}
+
+// Why a record was left out of an import, for the summary.
+pub fn skip_reason(label: &str, error: &CoreError) -> String {
+    let reason = match error {
+        CoreError::InvalidFields(errors) => errors.to_string(),
+        error => error.to_string(),
+    };
+    if label.trim().is_empty() {
+        reason
+    } else {
+        format!("'{}': {}", truncate(label.trim().to_string(), 60), reason)
+    }
+}
+
+// Turns parsed records into items. A record folio can't take (no usable title or link) is
+// reported and left out instead of failing the whole import.
+pub fn convert_records<T>(
+    records: &[T],
+    label: impl Fn(&T) -> String,
+    convert: impl Fn(&T) -> Result<Item, CoreError>,
+) -> (Vec<Item>, Vec<String>) {
+    let mut items = Vec::with_capacity(records.len());
+    let mut skipped = Vec::new();
+    for record in records {
+        match convert(record) {
+            Ok(item) => items.push(item),
+            Err(e) => skipped.push(skip_reason(&label(record), &e)),
+        }
+    }
+    (items, skipped)
+}
+
+// Drops candidates whose link is already known (or repeated within the batch) and bumps
+// added_at until every candidate has an unused id. Returns the kept items and the number
+// of duplicates.
+pub fn dedup_candidates<'a>(
+    existing: impl IntoIterator<Item = &'a Item>,
+    candidates: Vec<Item>,
+) -> (Vec<Item>, usize) {
+    let mut seen_links = HashSet::new();
+    let mut seen_ids = HashSet::new();
+    for item in existing {
+        if !item.link.is_empty() {
+            seen_links.insert(link_key(&item.link));
+        }
+        seen_ids.insert(item.id());
+    }
+
+    let mut kept = Vec::with_capacity(candidates.len());
+    let mut duplicates = 0;
+
+    for mut item in candidates {
+        if !item.link.is_empty() && !seen_links.insert(link_key(&item.link)) {
+            duplicates += 1;
+            continue;
+        }
+
+        // Item ids are derived from added_at, and bookmarks often share a timestamp.
+        while !seen_ids.insert(item.id()) {
+            item.added_at += chrono::Duration::microseconds(1);
+        }
+        kept.push(item);
+    }
+
+    (kept, duplicates)
+}
+
+pub fn plan_import(
+    inbox: Vec<Item>,
+    someday: Vec<Item>,
+    archive: &[Item],
+    mut candidates: Vec<Item>,
+    overflow: ImportOverflow,
+    config: &Config,
+) -> Result<ImportPlan, CoreError> {
+    candidates.sort_by_key(|item| item.added_at);
+    let (candidates, duplicates) =
+        dedup_candidates(inbox.iter().chain(&someday).chain(archive), candidates);
+
+    // An import only fills free slots. What doesn't fit goes to the overflow target, rather
+    // than pushing out items that are already there or were imported earlier in the run.
+    let config = &Config {
+        archive_on_overflow: OverflowStrategy::Abort,
+        someday_on_overflow: OverflowStrategy::Abort,
+        ..config.clone()
+    };
+
+    let mut plan = ImportPlan {
+        inbox_items: inbox,
+        someday_items: someday,
+        duplicates,
+        ..ImportPlan::default()
+    };
+
+    for item in candidates {
+        if item.status == Status::Done {
+            plan.to_archive.push(item);
+            plan.already_read += 1;
+            continue;
+        }
+
+        // Candidates were already checked against the inbox and archive by dedup_candidates.
+        match add_item_to_inbox(plan.inbox_items.clone(), item.clone(), &[], &[], config) {
+            Ok((inbox_items, _)) => {
+                plan.inbox_items = inbox_items;
+                plan.added_to_inbox += 1;
+                continue;
+            }
+            Err(CoreError::InboxFull) => {}
+            Err(e) => return Err(e),
+        }
+
+        if overflow == ImportOverflow::Someday {
+            match add_item_to_someday(plan.someday_items.clone(), item.clone(), config) {
+                Ok((someday_items, _)) => {
+                    plan.someday_items = someday_items;
+                    plan.added_to_someday += 1;
+                    continue;
+                }
+                Err(CoreError::SomedayFull) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        plan.to_archive.push(item);
+        plan.added_to_archive += 1;
+    }
+
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_item;
+
+    fn items(prefix: &str, count: usize) -> Vec<Item> {
+        (0..count)
+            .map(|i| {
+                let mut item = create_item(
+                    format!("{} {}", prefix, i),
+                    None,
+                    None,
+                    Some(format!("https://example.com/{}/{}", prefix, i)),
+                    None,
+                    None,
+                )
+                .unwrap();
+                item.added_at += chrono::Duration::seconds(i as i64);
+                item
+            })
+            .collect()
+    }
+
+    fn names(items: &[Item]) -> Vec<&str> {
+        items.iter().map(|item| item.name.as_str()).collect()
+    }
+
+    fn config(max_items: u32, someday_max_items: u32) -> Config {
+        Config {
+            max_items,
+            archive_on_overflow: OverflowStrategy::Any,
+            someday_max_items,
+            someday_on_overflow: OverflowStrategy::Any,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn a_full_inbox_is_left_alone_under_any() {
+        let inbox = items("saved", 2);
+        let plan = plan_import(
+            inbox.clone(),
+            Vec::new(),
+            &[],
+            items("imported", 3),
+            ImportOverflow::Archive,
+            &config(2, 10),
+        )
+        .unwrap();
+
+        assert_eq!(plan.inbox_items, inbox);
+        assert_eq!(
+            names(&plan.to_archive),
+            ["imported 0", "imported 1", "imported 2"]
+        );
+        assert_eq!((plan.added_to_inbox, plan.added_to_archive), (0, 3));
+    }
+
+    #[test]
+    fn free_slots_fill_first_and_the_rest_overflow() {
+        let plan = plan_import(
+            items("saved", 1),
+            items("later", 1),
+            &[],
+            items("imported", 4),
+            ImportOverflow::Someday,
+            &config(2, 2),
+        )
+        .unwrap();
+
+        assert_eq!(names(&plan.inbox_items), ["saved 0", "imported 0"]);
+        assert_eq!(names(&plan.someday_items), ["later 0", "imported 1"]);
+        assert_eq!(names(&plan.to_archive), ["imported 2", "imported 3"]);
+        assert_eq!(
+            (
+                plan.added_to_inbox,
+                plan.added_to_someday,
+                plan.added_to_archive
+            ),
+            (1, 1, 2)
+        );
+    }
+}
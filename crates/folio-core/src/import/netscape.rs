@@ -0,0 +1,203 @@
+use crate::validation::fit_name;
+use crate::{CoreError, Item, TypeRules, create_item, is_supported_link};
+use chrono::{DateTime, Utc};
+use strum::EnumString;
+
+use super::markup::{attribute, decode_entities};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+pub enum FolderMode {
+    #[strum(serialize = "tags")]
+    Tags,
+    #[strum(serialize = "note")]
+    Note,
+}
+
+#[derive(Debug, Clone)]
+pub struct Bookmark {
+    pub title: String,
+    pub url: String,
+    pub add_date: Option<DateTime<Utc>>,
+    pub folders: Vec<String>,
+}
+
+fn element_text<'a>(html: &'a str, close_tag: &str) -> (&'a str, usize) {
+    let mut search_from = 0;
+
+    while let Some(found) = html[search_from..].find("</") {
+        let end = search_from + found;
+        let candidate = html.get(end..end + close_tag.len());
+        if candidate.is_some_and(|candidate| candidate.eq_ignore_ascii_case(close_tag)) {
+            return (&html[..end], end + close_tag.len());
+        }
+        search_from = end + 2;
+    }
+
+    (html, html.len())
+}
+
+pub fn parse_netscape_bookmarks(html: &str) -> Vec<Bookmark> {
+    let mut bookmarks = Vec::new();
+    let mut folders: Vec<Option<String>> = Vec::new();
+    let mut pending_folder: Option<String> = None;
+    let mut rest = html;
+
+    while let Some(open) = rest.find('<') {
+        rest = &rest[open + 1..];
+        let Some(close) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..close];
+        rest = &rest[close + 1..];
+
+        let name = tag
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match name.as_str() {
+            "h3" => {
+                let (text, consumed) = element_text(rest, "</h3>");
+                pending_folder = Some(decode_entities(text.trim()));
+                rest = &rest[consumed..];
+            }
+            "dl" => folders.push(pending_folder.take()),
+            "/dl" => {
+                folders.pop();
+            }
+            "a" => {
+                let (text, consumed) = element_text(rest, "</a>");
+                rest = &rest[consumed..];
+
+                // Firefox exports smart folders as place: links, bookmarklets are javascript:.
+                let Some(url) = attribute(tag, "href").filter(|url| is_supported_link(url)) else {
+                    continue;
+                };
+
+                let add_date = attribute(tag, "add_date")
+                    .and_then(|date| date.trim().parse::<i64>().ok())
+                    .and_then(|seconds| DateTime::from_timestamp(seconds, 0));
+
+                bookmarks.push(Bookmark {
+                    title: decode_entities(text.trim()),
+                    url: url.trim().to_string(),
+                    add_date,
+                    folders: folders.iter().flatten().cloned().collect(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    bookmarks
+}
+
+pub fn bookmark_to_item(
+    bookmark: &Bookmark,
+    folder_mode: FolderMode,
+    rules: &TypeRules,
+) -> Result<Item, CoreError> {
+    let name = if bookmark.title.is_empty() {
+        bookmark.url.clone()
+    } else {
+        bookmark.title.clone()
+    };
+
+    let note = match folder_mode {
+        FolderMode::Note if !bookmark.folders.is_empty() => Some(bookmark.folders.join(" / ")),
+        _ => None,
+    };
+
+    let item_type = rules.infer_or_default(&bookmark.url);
+    let mut item = create_item(
+        fit_name(&name),
+        Some(item_type.as_string().to_string()),
+        None,
+        Some(bookmark.url.clone()),
+        note,
+        None,
+    )?;
+
+    if let Some(add_date) = bookmark.add_date {
+        item.added_at = add_date;
+    }
+    if folder_mode == FolderMode::Tags {
+        item.tags = bookmark.folders.clone();
+    }
+
+    Ok(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<TITLE>Bookmarks</TITLE>
+<DL><p>
+    <DT><H3 ADD_DATE="1700000000">Reading &amp; Notes</H3>
+    <DL><p>
+        <DT><A HREF="https://example.com/essay" ADD_DATE="1700000100">An essay</A>
+        <DT><H3>Café</H3>
+        <DL><p>
+            <DT><A HREF="https://example.com/kaffee">Über Kaffee — eine Geschichte</A>
+        </DL><p>
+    </DL><p>
+    <DT><A HREF="javascript:alert(1)">Bookmarklet</A>
+    <DT><A HREF="place:sort=8">Recent tags</A>
+    <DT><a href="https://example.com/lower" add_date="nonsense"></a>
+</DL><p>"#;
+
+    #[test]
+    fn bookmarks_keep_their_folder_path() {
+        let bookmarks = parse_netscape_bookmarks(EXPORT);
+        assert_eq!(bookmarks.len(), 3);
+
+        assert_eq!(bookmarks[0].title, "An essay");
+        assert_eq!(bookmarks[0].folders, ["Reading & Notes"]);
+        assert_eq!(
+            bookmarks[0].add_date,
+            DateTime::from_timestamp(1_700_000_100, 0)
+        );
+
+        assert_eq!(bookmarks[1].title, "Über Kaffee — eine Geschichte");
+        assert_eq!(bookmarks[1].folders, ["Reading & Notes", "Café"]);
+
+        assert!(bookmarks[2].folders.is_empty());
+        assert!(bookmarks[2].add_date.is_none());
+    }
+
+    #[test]
+    fn folders_become_tags_or_a_note() {
+        let bookmarks = parse_netscape_bookmarks(EXPORT);
+        let rules = TypeRules::builtin();
+
+        let tagged = bookmark_to_item(&bookmarks[1], FolderMode::Tags, &rules).unwrap();
+        assert_eq!(tagged.tags, ["Reading & Notes", "Café"]);
+        assert!(tagged.note.is_empty());
+
+        let noted = bookmark_to_item(&bookmarks[1], FolderMode::Note, &rules).unwrap();
+        assert_eq!(noted.note, "Reading & Notes / Café");
+        assert!(noted.tags.is_empty());
+
+        let untitled = bookmark_to_item(&bookmarks[2], FolderMode::Tags, &rules).unwrap();
+        assert_eq!(untitled.name, "https://example.com/lower");
+    }
+
+    #[test]
+    fn malformed_markup_does_not_panic() {
+        assert!(parse_netscape_bookmarks("").is_empty());
+        assert!(parse_netscape_bookmarks("<DL><p><DT><A HREF=\"https://example.com\"").is_empty());
+
+        let unclosed = parse_netscape_bookmarks("<DT><A HREF=\"https://example.com/a\">No end");
+        assert_eq!(unclosed.len(), 1);
+        assert_eq!(unclosed[0].title, "No end");
+
+        let stray =
+            parse_netscape_bookmarks("</DL></DL><DT><A HREF=\"https://example.com/b\">B</A>");
+        assert_eq!(stray.len(), 1);
+        assert!(stray[0].folders.is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+use crate::validation::fit_name;
+use crate::{CoreError, Item, ItemType, create_item};
+
+use super::markup::attribute;
+
+#[derive(Debug, Clone)]
+pub struct Outline {
+    pub title: String,
+    pub feed_url: String,
+    pub site_url: String,
+    pub categories: Vec<String>,
+}
+
+pub fn parse_opml(xml: &str) -> Vec<Outline> {
+    let mut outlines = Vec::new();
+    let mut categories: Vec<Option<String>> = Vec::new();
+    let mut rest = xml;
+
+    while let Some(open) = rest.find('<') {
+        rest = &rest[open + 1..];
+        let Some(close) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..close];
+        rest = &rest[close + 1..];
+
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        if let Some(closing) = tag.strip_prefix('/') {
+            if closing.trim().eq_ignore_ascii_case("outline") {
+                categories.pop();
+            }
+            continue;
+        }
+        if name != "outline" {
+            continue;
+        }
+
+        let self_closing = tag.trim_end().ends_with('/');
+        let title = attribute(tag, "title")
+            .or_else(|| attribute(tag, "text"))
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+
+        match attribute(tag, "xmlurl").filter(|url| !url.trim().is_empty()) {
+            Some(feed_url) => {
+                outlines.push(Outline {
+                    title,
+                    feed_url: feed_url.trim().to_string(),
+                    site_url: attribute(tag, "htmlurl").unwrap_or_default(),
+                    categories: categories.iter().flatten().cloned().collect(),
+                });
+                if !self_closing {
+                    categories.push(None);
+                }
+            }
+            None if !self_closing => {
+                categories.push(Some(title).filter(|title| !title.is_empty()));
+            }
+            None => {}
+        }
+    }
+
+    outlines
+}
+
+pub fn outline_to_item(outline: &Outline, item_type: &ItemType) -> Result<Item, CoreError> {
+    let name = if outline.title.is_empty() {
+        outline.feed_url.clone()
+    } else {
+        outline.title.clone()
+    };
+
+    let mut item = create_item(
+        fit_name(&name),
+        Some(item_type.as_string().to_string()),
+        None,
+        Some(outline.feed_url.clone()),
+        None,
+        None,
+    )?;
+    item.tags = outline.categories.clone();
+
+    Ok(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <head><title>Subscriptions</title></head>
+  <body>
+    <outline text="Tech" title="Tech">
+      <outline type="rss" text="A blog" xmlUrl="https://example.com/feed.xml" htmlUrl="https://example.com/"/>
+      <outline text="Deutsch">
+        <outline type="rss" title="Schöne Grüße" text="ignored" xmlUrl=" https://example.de/atom "/>
+      </outline>
+    </outline>
+    <outline type="rss" text="" xmlUrl="https://example.org/rss"></outline>
+    <outline text="No feed here"/>
+  </body>
+</opml>"#;
+
+    #[test]
+    fn feeds_carry_their_categories() {
+        let outlines = parse_opml(EXPORT);
+        assert_eq!(outlines.len(), 3);
+
+        assert_eq!(outlines[0].title, "A blog");
+        assert_eq!(outlines[0].site_url, "https://example.com/");
+        assert_eq!(outlines[0].categories, ["Tech"]);
+
+        assert_eq!(outlines[1].title, "Schöne Grüße");
+        assert_eq!(outlines[1].feed_url, "https://example.de/atom");
+        assert_eq!(outlines[1].categories, ["Tech", "Deutsch"]);
+
+        assert!(outlines[2].categories.is_empty());
+    }
+
+    #[test]
+    fn untitled_feeds_are_named_after_their_url() {
+        let outlines = parse_opml(EXPORT);
+        let item = outline_to_item(&outlines[2], &ItemType::BlogPost).unwrap();
+        assert_eq!(item.name, "https://example.org/rss");
+        assert_eq!(item.item_type, ItemType::BlogPost);
+
+        let item = outline_to_item(&outlines[1], &ItemType::BlogPost).unwrap();
+        assert_eq!(item.tags, ["Tech", "Deutsch"]);
+    }
+
+    #[test]
+    fn malformed_documents_do_not_panic() {
+        assert!(parse_opml("").is_empty());
+        assert!(parse_opml("<opml><body><outline xmlUrl=\"https://example.com/feed\"").is_empty());
+        assert!(parse_opml("</outline></outline>not xml at all").is_empty());
+    }
+}
@@ -0,0 +1,489 @@
+use super::skip_reason;
+use crate::validation::{fit_author, fit_name};
+use crate::{
+    Config, CoreError, Item, ItemType, Kind, Status, add_item_to_someday, add_items_to_inbox,
+    create_item, update_timestamps,
+};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrgSection {
+    Inbox,
+    Someday,
+    Archive,
+}
+
+#[derive(Debug, Clone)]
+pub struct OrgEntry {
+    pub folio_id: Option<usize>,
+    pub section: OrgSection,
+    pub name: String,
+    pub status: Status,
+    pub item_type: Option<ItemType>,
+    pub kind: Option<Kind>,
+    pub author: String,
+    pub link: String,
+    pub note: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct OrgPlan {
+    pub inbox_items: Vec<Item>,
+    pub someday_items: Vec<Item>,
+    pub archive_items: Vec<Item>,
+    pub updated: usize,
+    pub created: usize,
+    pub unchanged: usize,
+    pub over_cap: usize,
+    // Headlines that were left out, and why.
+    pub skipped: Vec<String>,
+}
+
+fn parse_section(title: &str) -> Option<OrgSection> {
+    match title.trim().to_lowercase().as_str() {
+        "inbox" => Some(OrgSection::Inbox),
+        "someday" => Some(OrgSection::Someday),
+        "archive" => Some(OrgSection::Archive),
+        _ => None,
+    }
+}
+
+fn parse_headline(text: &str, section: OrgSection) -> OrgEntry {
+    let mut rest = text.trim();
+
+    let (status, after) = match rest.split_once(' ').unwrap_or((rest, "")) {
+        ("TODO", after) => (Status::Todo, after),
+        ("DOING", after) => (Status::Doing, after),
+        ("DONE", after) => (Status::Done, after),
+        _ => (Status::Todo, rest),
+    };
+    rest = after.trim();
+
+    if rest.starts_with("[#") && rest.get(3..4) == Some("]") {
+        rest = rest[4..].trim_start();
+    }
+
+    let mut tags = Vec::new();
+    if let Some(start) = rest.rfind(" :")
+        && rest.ends_with(':')
+        && !rest[start + 2..rest.len() - 1].contains(char::is_whitespace)
+    {
+        tags = rest[start + 2..rest.len() - 1]
+            .split(':')
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect();
+        rest = rest[..start].trim_end();
+    }
+
+    OrgEntry {
+        folio_id: None,
+        section,
+        name: rest.to_string(),
+        status,
+        item_type: None,
+        kind: None,
+        author: String::new(),
+        link: String::new(),
+        note: String::new(),
+        tags,
+    }
+}
+
+fn finish_entry(entry: Option<OrgEntry>, body: &mut Vec<&str>, entries: &mut Vec<OrgEntry>) {
+    if let Some(mut entry) = entry {
+        let note: Vec<String> = body
+            .iter()
+            .map(|line| {
+                line.strip_prefix(',')
+                    .filter(|l| l.starts_with('*'))
+                    .unwrap_or(line)
+            })
+            .map(str::to_string)
+            .collect();
+        entry.note = note.join("\n").trim().to_string();
+        entries.push(entry);
+    }
+    body.clear();
+}
+
+pub fn parse_org(data: &str) -> Result<Vec<OrgEntry>, CoreError> {
+    let mut entries = Vec::new();
+    let mut section = OrgSection::Inbox;
+    let mut current: Option<OrgEntry> = None;
+    let mut body: Vec<&str> = Vec::new();
+    let mut in_properties = false;
+
+    for (index, line) in data.lines().enumerate() {
+        if let Some(title) = line.strip_prefix("* ") {
+            finish_entry(current.take(), &mut body, &mut entries);
+            section = parse_section(title).ok_or_else(|| {
+                CoreError::Import(format!(
+                    "Line {}: unknown section '{}'. Expected Inbox, Someday or Archive",
+                    index + 1,
+                    title.trim()
+                ))
+            })?;
+            continue;
+        }
+
+        if let Some(text) = line.strip_prefix("** ") {
+            finish_entry(current.take(), &mut body, &mut entries);
+            current = Some(parse_headline(text, section));
+            in_properties = false;
+            continue;
+        }
+
+        let Some(entry) = current.as_mut() else {
+            continue;
+        };
+        let trimmed = line.trim();
+
+        if in_properties {
+            if trimmed.eq_ignore_ascii_case(":END:") {
+                in_properties = false;
+                continue;
+            }
+            let Some((key, value)) = trimmed
+                .strip_prefix(':')
+                .and_then(|property| property.split_once(':'))
+            else {
+                continue;
+            };
+            let value = value.trim();
+
+            match key.to_uppercase().as_str() {
+                "FOLIO_ID" => entry.folio_id = value.parse().ok(),
+                "TYPE" => {
+                    entry.item_type = Some(ItemType::from_str(value).map_err(|_| {
+                        CoreError::Import(format!("Line {}: unknown type '{}'", index + 1, value))
+                    })?)
+                }
+                "KIND" => {
+                    entry.kind = Some(Kind::from_str(value).map_err(|_| {
+                        CoreError::Import(format!("Line {}: unknown kind '{}'", index + 1, value))
+                    })?)
+                }
+                "AUTHOR" => entry.author = value.to_string(),
+                "LINK" => entry.link = value.to_string(),
+                _ => {}
+            }
+            continue;
+        }
+
+        if body.is_empty() && trimmed.eq_ignore_ascii_case(":PROPERTIES:") {
+            in_properties = true;
+        } else if body.is_empty()
+            && ["CLOSED:", "SCHEDULED:", "DEADLINE:"]
+                .iter()
+                .any(|keyword| trimmed.starts_with(keyword))
+        {
+            continue;
+        } else if !line.starts_with("#+") {
+            body.push(line);
+        }
+    }
+    finish_entry(current, &mut body, &mut entries);
+
+    Ok(entries)
+}
+
+fn apply_entry(item: &mut Item, entry: &OrgEntry) {
+    item.name = fit_name(&entry.name);
+    item.author = fit_author(&entry.author);
+    item.link = entry.link.clone();
+    item.note = entry.note.clone();
+    item.tags = entry.tags.clone();
+    if let Some(item_type) = &entry.item_type {
+        item.item_type = item_type.clone();
+    }
+    if let Some(kind) = &entry.kind {
+        item.kind = kind.clone();
+    }
+    if item.status != entry.status {
+        item.status = entry.status.clone();
+        update_timestamps(item);
+    }
+}
+
+// Headlines stay in the section they are listed under, except that switching to DONE
+// archives an item and reopening an archived one brings it back to the inbox.
+fn target_list(entry: &OrgEntry, previous_status: Option<&Status>) -> OrgSection {
+    let changed = previous_status.is_none_or(|status| *status != entry.status);
+    match (&entry.status, entry.section) {
+        (Status::Done, _) if changed => OrgSection::Archive,
+        (_, OrgSection::Archive) if changed && previous_status.is_some() => OrgSection::Inbox,
+        (_, section) => section,
+    }
+}
+
+// Headlines with a known FOLIO_ID update that item in place, keeping fields Org doesn't
+// carry (bibliography, highlights, exact timestamps). Items moving into the inbox go through
+// the cap like any import. Items missing from the file are left alone.
+pub fn plan_org_import(
+    inbox: Vec<Item>,
+    someday: Vec<Item>,
+    archive: Vec<Item>,
+    entries: Vec<OrgEntry>,
+    config: &Config,
+) -> Result<OrgPlan, CoreError> {
+    let mut lists = [inbox, someday, archive];
+    let sections = [OrgSection::Inbox, OrgSection::Someday, OrgSection::Archive];
+
+    let mut seen_ids: HashSet<usize> = lists.iter().flatten().map(Item::id).collect();
+    let mut locations = HashMap::new();
+    for (list, items) in lists.iter().enumerate() {
+        for (position, item) in items.iter().enumerate() {
+            locations.insert(item.id(), (list, position));
+        }
+    }
+
+    let mut plan = OrgPlan::default();
+    let mut moving: Vec<(OrgSection, Item)> = Vec::new();
+    let mut removed: Vec<(usize, usize)> = Vec::new();
+
+    for entry in &entries {
+        if entry.name.is_empty() {
+            plan.skipped.push("a headline without a title".to_string());
+            continue;
+        }
+
+        // A headline copied within the file repeats its FOLIO_ID; only the first one updates.
+        let location = entry.folio_id.and_then(|id| locations.remove(&id));
+
+        match location {
+            Some((list, position)) => {
+                let before = &lists[list][position];
+                let mut item = before.clone();
+                apply_entry(&mut item, entry);
+
+                let errors = item.changed_field_errors(before);
+                if !errors.is_empty() {
+                    plan.skipped
+                        .push(skip_reason(&entry.name, &CoreError::InvalidFields(errors)));
+                    continue;
+                }
+
+                let target = target_list(entry, Some(&before.status));
+                if sections[list] != target {
+                    removed.push((list, position));
+                    moving.push((target, item));
+                    plan.updated += 1;
+                } else if item != *before {
+                    lists[list][position] = item;
+                    plan.updated += 1;
+                } else {
+                    plan.unchanged += 1;
+                }
+            }
+            None => {
+                let created = create_item(
+                    fit_name(&entry.name),
+                    entry
+                        .item_type
+                        .as_ref()
+                        .map(|item_type| item_type.as_string().to_string()),
+                    Some(entry.author.clone()),
+                    Some(entry.link.clone()),
+                    Some(entry.note.clone()),
+                    entry.kind.as_ref().map(|kind| kind.as_string().to_string()),
+                );
+                let mut item = match created {
+                    Ok(item) => item,
+                    Err(e) => {
+                        plan.skipped.push(skip_reason(&entry.name, &e));
+                        continue;
+                    }
+                };
+                item.tags = entry.tags.clone();
+                item.status = entry.status.clone();
+                update_timestamps(&mut item);
+
+                while !seen_ids.insert(item.id()) {
+                    item.added_at += chrono::Duration::microseconds(1);
+                }
+
+                moving.push((target_list(entry, None), item));
+                plan.created += 1;
+            }
+        }
+    }
+
+    removed.sort_unstable();
+    for (list, position) in removed.into_iter().rev() {
+        lists[list].remove(position);
+    }
+    let [inbox, mut someday, mut archive] = lists;
+
+    let mut to_inbox = Vec::new();
+
+    for (target, item) in moving {
+        match target {
+            OrgSection::Inbox => to_inbox.push(item),
+            OrgSection::Archive => archive.push(item),
+            OrgSection::Someday => {
+                match add_item_to_someday(someday.clone(), item.clone(), config) {
+                    Ok((someday_items, evicted)) => {
+                        someday = someday_items;
+                        archive.extend(evicted);
+                    }
+                    Err(CoreError::SomedayFull) => {
+                        archive.push(item);
+                        plan.over_cap += 1;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+    }
+
+    let outcome = add_items_to_inbox(inbox, to_inbox, config)?;
+    archive.extend(outcome.archived);
+    plan.over_cap += outcome.rejected.len();
+    archive.extend(outcome.rejected);
+
+    plan.inbox_items = outcome.inbox;
+    plan.someday_items = someday;
+    plan.archive_items = archive;
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::to_org;
+
+    fn item(name: &str, link: &str, offset: i64) -> Item {
+        let mut item = create_item(
+            name.to_string(),
+            None,
+            Some("Zoë Ångström".to_string()),
+            Some(link.to_string()),
+            Some("* starts like a headline\nsecond line".to_string()),
+            None,
+        )
+        .unwrap();
+        item.added_at += chrono::Duration::seconds(offset);
+        item
+    }
+
+    fn lists() -> (Vec<Item>, Vec<Item>, Vec<Item>) {
+        let mut read = item("Ein Buch über Bücher", "https://example.de/buch", 2);
+        read.status = Status::Done;
+        update_timestamps(&mut read);
+        read.tags = vec!["lesen".to_string()];
+
+        (
+            vec![item("東京の本屋", "https://example.jp/a", 0)],
+            vec![item("Someday essay", "https://example.com/s", 1)],
+            vec![read],
+        )
+    }
+
+    fn export(inbox: &[Item], someday: &[Item], archive: &[Item]) -> String {
+        to_org(&[("Inbox", inbox), ("Someday", someday), ("Archive", archive)])
+    }
+
+    #[test]
+    fn an_unedited_export_round_trips() {
+        let (inbox, someday, archive) = lists();
+        let org = export(&inbox, &someday, &archive);
+
+        let entries = parse_org(&org).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].folio_id, Some(inbox[0].id()));
+        assert_eq!(entries[0].name, "東京の本屋");
+        assert_eq!(entries[0].author, "Zoë Ångström");
+        assert_eq!(entries[0].note, "* starts like a headline\nsecond line");
+        assert_eq!(entries[2].tags, ["lesen"]);
+        assert_eq!(entries[2].section, OrgSection::Archive);
+
+        let plan = plan_org_import(
+            inbox.clone(),
+            someday.clone(),
+            archive.clone(),
+            entries,
+            &Config::default(),
+        )
+        .unwrap();
+        assert_eq!((plan.unchanged, plan.updated, plan.created), (3, 0, 0));
+        assert_eq!(plan.inbox_items, inbox);
+        assert_eq!(plan.someday_items, someday);
+        assert_eq!(plan.archive_items, archive);
+    }
+
+    #[test]
+    fn edits_update_items_by_folio_id() {
+        let (inbox, someday, archive) = lists();
+        let org = export(&inbox, &someday, &archive)
+            .replace("** TODO 東京の本屋", "** DONE 東京の本屋 — gelesen")
+            .replace(
+                "** DONE Ein Buch über Bücher",
+                "** TODO Ein Buch über Bücher",
+            )
+            .replace(
+                "* Someday\n",
+                "* Someday\n** DOING [#A] A new headline :neu:x:\n",
+            );
+
+        let entries = parse_org(&org).unwrap();
+        let plan = plan_org_import(
+            inbox.clone(),
+            someday,
+            archive.clone(),
+            entries,
+            &Config::default(),
+        )
+        .unwrap();
+        assert_eq!((plan.updated, plan.created, plan.unchanged), (2, 1, 1));
+
+        let finished = plan
+            .archive_items
+            .iter()
+            .find(|item| item.id() == inbox[0].id())
+            .unwrap();
+        assert_eq!(finished.name, "東京の本屋 — gelesen");
+        assert_eq!(finished.status, Status::Done);
+
+        let reopened = plan
+            .inbox_items
+            .iter()
+            .find(|item| item.id() == archive[0].id())
+            .unwrap();
+        assert_eq!(reopened.status, Status::Todo);
+
+        let created = plan
+            .someday_items
+            .iter()
+            .find(|item| item.name == "A new headline")
+            .unwrap();
+        assert_eq!(created.status, Status::Doing);
+        assert_eq!(created.tags, ["neu", "x"]);
+    }
+
+    #[test]
+    fn malformed_files_are_errors_or_skipped() {
+        let unknown_section = parse_org("* Later\n** TODO x\n");
+        assert!(
+            matches!(unknown_section, Err(CoreError::Import(message)) if message.contains("Line 1"))
+        );
+
+        let bad_type = parse_org("* Inbox\n** TODO x\n:PROPERTIES:\n:TYPE: scroll\n:END:\n");
+        assert!(bad_type.is_err());
+
+        let entries =
+            parse_org("text before any headline\n* Inbox\n** TODO\n:PROPERTIES:\n").unwrap();
+        assert_eq!(entries.len(), 1);
+        let plan = plan_org_import(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            entries,
+            &Config::default(),
+        )
+        .unwrap();
+        assert_eq!(plan.skipped, ["a headline without a title"]);
+    }
+}
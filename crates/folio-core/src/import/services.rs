@@ -0,0 +1,325 @@
+use crate::validation::fit_name;
+use crate::{CoreError, Item, Status, TypeRules, create_item, is_supported_link};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use strum::EnumString;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+pub enum Service {
+    #[strum(serialize = "pocket")]
+    Pocket,
+    #[strum(serialize = "instapaper")]
+    Instapaper,
+    #[strum(serialize = "omnivore")]
+    Omnivore,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServiceEntry {
+    pub title: String,
+    pub url: String,
+    pub added_at: Option<DateTime<Utc>>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+    pub read: bool,
+}
+
+struct CsvColumns {
+    headers: Vec<String>,
+}
+
+impl CsvColumns {
+    fn new(headers: &csv::StringRecord) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.trim().to_lowercase()).collect(),
+        }
+    }
+
+    fn require(&self, name: &str, service: &str) -> Result<usize, CoreError> {
+        self.find(name).ok_or_else(|| {
+            CoreError::Import(format!(
+                "Missing '{}' column. Is this a {} export?",
+                name, service
+            ))
+        })
+    }
+
+    fn find(&self, name: &str) -> Option<usize> {
+        self.headers.iter().position(|h| h == name)
+    }
+}
+
+fn field(record: &csv::StringRecord, index: Option<usize>) -> String {
+    index
+        .and_then(|index| record.get(index))
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+fn unix_time(value: &str) -> Option<DateTime<Utc>> {
+    value
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+}
+
+fn read_csv(data: &str) -> Result<(CsvColumns, Vec<csv::StringRecord>), CoreError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(data.as_bytes());
+
+    let columns = CsvColumns::new(
+        reader
+            .headers()
+            .map_err(|e| CoreError::Import(format!("Invalid CSV: {}", e)))?,
+    );
+    let records = reader
+        .records()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| CoreError::Import(format!("Invalid CSV: {}", e)))?;
+
+    Ok((columns, records))
+}
+
+fn parse_pocket_csv(data: &str) -> Result<Vec<ServiceEntry>, CoreError> {
+    let (columns, records) = read_csv(data)?;
+    let url = columns.require("url", "Pocket")?;
+    let title = columns.find("title");
+    let time_added = columns.find("time_added");
+    let tags = columns.find("tags");
+    let status = columns.find("status");
+
+    Ok(records
+        .iter()
+        .map(|record| ServiceEntry {
+            title: field(record, title),
+            url: field(record, Some(url)),
+            added_at: unix_time(&field(record, time_added)),
+            read_at: None,
+            tags: field(record, tags)
+                .split('|')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_string)
+                .collect(),
+            read: field(record, status) == "archive",
+        })
+        .filter(|entry| is_supported_link(&entry.url))
+        .collect())
+}
+
+fn parse_instapaper_csv(data: &str) -> Result<Vec<ServiceEntry>, CoreError> {
+    let (columns, records) = read_csv(data)?;
+    let url = columns.require("url", "Instapaper")?;
+    let title = columns.find("title");
+    let folder = columns.find("folder");
+    let timestamp = columns.find("timestamp");
+    let tags = columns.find("tags");
+
+    Ok(records
+        .iter()
+        .map(|record| {
+            let folder = field(record, folder);
+            let mut entry_tags: Vec<String> =
+                serde_json::from_str(&field(record, tags)).unwrap_or_default();
+            if !matches!(folder.as_str(), "" | "Unread" | "Archive" | "Starred") {
+                entry_tags.insert(0, folder.clone());
+            }
+
+            ServiceEntry {
+                title: field(record, title),
+                url: field(record, Some(url)),
+                added_at: unix_time(&field(record, timestamp)),
+                read_at: None,
+                tags: entry_tags,
+                read: folder == "Archive",
+            }
+        })
+        .filter(|entry| is_supported_link(&entry.url))
+        .collect())
+}
+
+fn json_time(value: &Value, key: &str) -> Option<DateTime<Utc>> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+        .map(|time| time.with_timezone(&Utc))
+}
+
+fn json_str(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+fn parse_omnivore_json(data: &str) -> Result<Vec<ServiceEntry>, CoreError> {
+    let value: Value = serde_json::from_str(data)
+        .map_err(|e| CoreError::Import(format!("Invalid JSON: {}", e)))?;
+    let articles = value.as_array().ok_or_else(|| {
+        CoreError::Import("Expected a JSON array of articles. Is this an Omnivore export?".into())
+    })?;
+
+    Ok(articles
+        .iter()
+        .map(|article| {
+            let url = match json_str(article, "url") {
+                url if url.is_empty() => json_str(article, "originalArticleUrl"),
+                url => url,
+            };
+
+            let progress = article
+                .get("readingProgress")
+                .and_then(Value::as_f64)
+                .unwrap_or_default();
+            let read = json_str(article, "state") == "Archived" || progress >= 100.0;
+
+            let tags = article
+                .get("labels")
+                .and_then(Value::as_array)
+                .map(|labels| {
+                    labels
+                        .iter()
+                        .filter_map(|label| {
+                            label
+                                .as_str()
+                                .or_else(|| label.get("name").and_then(Value::as_str))
+                        })
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            ServiceEntry {
+                title: json_str(article, "title"),
+                url,
+                added_at: json_time(article, "savedAt"),
+                read_at: json_time(article, "archivedAt")
+                    .or_else(|| json_time(article, "updatedAt")),
+                tags,
+                read,
+            }
+        })
+        .filter(|entry| is_supported_link(&entry.url))
+        .collect())
+}
+
+pub fn parse_service_export(service: Service, data: &str) -> Result<Vec<ServiceEntry>, CoreError> {
+    match service {
+        Service::Pocket => parse_pocket_csv(data),
+        Service::Instapaper => parse_instapaper_csv(data),
+        Service::Omnivore => parse_omnivore_json(data),
+    }
+}
+
+pub fn entry_to_item(entry: &ServiceEntry, rules: &TypeRules) -> Result<Item, CoreError> {
+    let name = if entry.title.is_empty() {
+        entry.url.clone()
+    } else {
+        entry.title.clone()
+    };
+
+    let item_type = rules.infer_or_default(&entry.url);
+    let mut item = create_item(
+        fit_name(&name),
+        Some(item_type.as_string().to_string()),
+        None,
+        Some(entry.url.clone()),
+        None,
+        None,
+    )?;
+
+    if let Some(added_at) = entry.added_at {
+        item.added_at = added_at;
+    }
+    item.tags = entry.tags.clone();
+
+    if entry.read {
+        item.status = Status::Done;
+        item.finished_at = Some(entry.read_at.unwrap_or(item.added_at).max(item.added_at));
+    }
+
+    Ok(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pocket_rows_keep_tags_and_read_state() {
+        let csv = "title,url,time_added,tags,status\n\
+                   Ünïcödé — title,https://example.com/a,1700000000,rust|web,archive\n\
+                   ,https://example.com/b,not a time,,unread\n\
+                   Bookmarklet,javascript:void(0),1700000000,,unread\n";
+        let entries = parse_service_export(Service::Pocket, csv).unwrap();
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].title, "Ünïcödé — title");
+        assert_eq!(entries[0].tags, ["rust", "web"]);
+        assert_eq!(
+            entries[0].added_at,
+            DateTime::from_timestamp(1_700_000_000, 0)
+        );
+        assert!(entries[0].read);
+
+        assert!(entries[1].added_at.is_none());
+        assert!(!entries[1].read);
+        let item = entry_to_item(&entries[1], &TypeRules::builtin()).unwrap();
+        assert_eq!(item.name, "https://example.com/b");
+        assert_eq!(item.status, Status::Todo);
+    }
+
+    #[test]
+    fn instapaper_folders_become_the_first_tag() {
+        let csv = "URL,Title,Selection,Folder,Timestamp,Tags\n\
+                   https://example.com/a,A,,Archive,1700000000,\"[\"\"x\"\"]\"\n\
+                   https://example.com/b,B,,Lectures,1700000000,\n";
+        let entries = parse_service_export(Service::Instapaper, csv).unwrap();
+
+        assert!(entries[0].read);
+        assert_eq!(entries[0].tags, ["x"]);
+        assert!(!entries[1].read);
+        assert_eq!(entries[1].tags, ["Lectures"]);
+    }
+
+    #[test]
+    fn omnivore_articles_read_progress_and_labels() {
+        let json = r#"[
+            {"title": "東京の本屋", "url": "", "originalArticleUrl": "https://example.jp/a",
+             "savedAt": "2024-03-01T10:00:00Z", "archivedAt": "2024-03-02T10:00:00Z",
+             "state": "Archived", "labels": [{"name": "books"}, "japan"]},
+            {"title": "Half read", "url": "https://example.com/b", "readingProgress": 40.0},
+            {"title": "Done by progress", "url": "https://example.com/c", "readingProgress": 100}
+        ]"#;
+        let entries = parse_service_export(Service::Omnivore, json).unwrap();
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(entries[0].url, "https://example.jp/a");
+        assert_eq!(entries[0].tags, ["books", "japan"]);
+        let item = entry_to_item(&entries[0], &TypeRules::builtin()).unwrap();
+        assert_eq!(item.name, "東京の本屋");
+        assert_eq!(item.status, Status::Done);
+        assert_eq!(item.finished_at, entries[0].read_at);
+
+        assert!(!entries[1].read);
+        assert!(entries[2].read);
+    }
+
+    #[test]
+    fn malformed_exports_are_errors() {
+        let missing_url = parse_service_export(Service::Pocket, "title,tags\nA,b\n");
+        assert!(
+            matches!(missing_url, Err(CoreError::Import(message)) if message.contains("'url'"))
+        );
+
+        assert!(parse_service_export(Service::Omnivore, "{\"not\": \"a list\"}").is_err());
+        assert!(parse_service_export(Service::Omnivore, "[{").is_err());
+        assert!(parse_service_export(Service::Instapaper, "").is_err());
+    }
+}
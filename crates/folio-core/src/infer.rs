@@ -0,0 +1,146 @@
+use crate::{Config, ItemType};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const BUILTIN_RULES: &[(&str, ItemType)] = &[
+    ("youtube.com", ItemType::Video),
+    ("youtu.be", ItemType::Video),
+    ("vimeo.com", ItemType::Video),
+    ("twitch.tv", ItemType::Video),
+    ("arxiv.org", ItemType::AcademicPaper),
+    ("doi.org", ItemType::AcademicPaper),
+    ("acm.org", ItemType::AcademicPaper),
+    ("openreview.net", ItemType::AcademicPaper),
+    ("biorxiv.org", ItemType::AcademicPaper),
+    ("semanticscholar.org", ItemType::AcademicPaper),
+    ("ieeexplore.ieee.org", ItemType::AcademicPaper),
+    ("twitter.com", ItemType::Thread),
+    ("x.com", ItemType::Thread),
+    ("bsky.app", ItemType::Thread),
+    ("threads.net", ItemType::Thread),
+    ("news.ycombinator.com", ItemType::Thread),
+    ("podcasts.apple.com", ItemType::Podcast),
+    ("open.spotify.com/episode", ItemType::Podcast),
+    ("open.spotify.com/show", ItemType::Podcast),
+    ("overcast.fm", ItemType::Podcast),
+    ("pca.st", ItemType::Podcast),
+    ("pocketcasts.com", ItemType::Podcast),
+    ("castbox.fm", ItemType::Podcast),
+    ("podbean.com", ItemType::Podcast),
+    ("buzzsprout.com", ItemType::Podcast),
+    ("simplecast.com", ItemType::Podcast),
+    ("transistor.fm", ItemType::Podcast),
+    ("libsyn.com", ItemType::Podcast),
+    ("anchor.fm", ItemType::Podcast),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeRule {
+    pub pattern: String,
+    #[serde(rename = "type")]
+    pub item_type: ItemType,
+}
+
+impl TypeRule {
+    // "example.com" or "example.com/podcasts"; subdomains of the domain match too.
+    pub fn new(pattern: &str, item_type: ItemType) -> Self {
+        let pattern = pattern.trim().to_lowercase();
+        let pattern = pattern
+            .split_once("://")
+            .map_or(pattern.as_str(), |(_, rest)| rest);
+
+        Self {
+            pattern: pattern
+                .trim_start_matches("www.")
+                .trim_end_matches('/')
+                .to_string(),
+            item_type,
+        }
+    }
+
+    fn matches(&self, host: &str, path: &str) -> bool {
+        let (domain, prefix) = self
+            .pattern
+            .find('/')
+            .map_or((self.pattern.as_str(), ""), |index| {
+                self.pattern.split_at(index)
+            });
+
+        let host_matches = host == domain
+            || host
+                .strip_suffix(domain)
+                .is_some_and(|subdomain| subdomain.ends_with('.'));
+
+        host_matches
+            && path.starts_with(prefix)
+            && matches!(path.as_bytes().get(prefix.len()), None | Some(b'/'))
+    }
+}
+
+// Host (lowercased, without "www." or a port) and path of a link, with or without a scheme.
+fn split_link(link: &str) -> Option<(String, String)> {
+    let link = link.trim();
+    let rest = link.split_once("://").map_or(link, |(_, rest)| rest);
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (authority, tail) = rest.split_at(end);
+
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    let host = host.split(':').next().unwrap_or(host).to_lowercase();
+    let host = host.trim_start_matches("www.").to_string();
+    if host.is_empty() || !host.contains('.') {
+        return None;
+    }
+
+    let path = tail.split(['?', '#']).next().unwrap_or_default();
+    Some((host, path.to_lowercase()))
+}
+
+pub fn parse_type_rule(pair: &str) -> Option<TypeRule> {
+    let (pattern, item_type) = pair.split_once('=')?;
+    let item_type = ItemType::from_str(item_type.trim().to_lowercase().as_str()).ok()?;
+    let rule = TypeRule::new(pattern, item_type);
+    (!rule.pattern.is_empty() && !rule.pattern.starts_with('/')).then_some(rule)
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeRules {
+    user: Vec<TypeRule>,
+    builtin: Vec<TypeRule>,
+}
+
+impl TypeRules {
+    pub fn builtin() -> Self {
+        Self {
+            user: Vec::new(),
+            builtin: BUILTIN_RULES
+                .iter()
+                .map(|(pattern, item_type)| TypeRule::new(pattern, item_type.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            user: config.type_rules.clone(),
+            ..Self::builtin()
+        }
+    }
+
+    // User rules win over the built-in ones; within each set the most specific match wins,
+    // so "open.spotify.com/episode" beats "spotify.com".
+    pub fn infer(&self, link: &str) -> Option<ItemType> {
+        let (host, path) = split_link(link)?;
+
+        [&self.user, &self.builtin].into_iter().find_map(|rules| {
+            rules
+                .iter()
+                .filter(|rule| rule.matches(&host, &path))
+                .max_by_key(|rule| rule.pattern.len())
+                .map(|rule| rule.item_type.clone())
+        })
+    }
+
+    pub fn infer_or_default(&self, link: &str) -> ItemType {
+        self.infer(link).unwrap_or(ItemType::BlogPost)
+    }
+}
@@ -1,19 +1,60 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
 use strum::EnumString;
 
 mod error;
-pub use error::{CapError, CoreError};
+pub use error::{CapError, ConfigError, CoreError};
 
 mod config;
-pub use config::{Config, OverflowStrategy};
+pub use config::{
+    CONFIG_SCHEMA, Config, ConfigField, ConfigValueKind, OverflowStrategy, config_field,
+    parse_override,
+};
 
 mod status;
 pub use status::{StatusTransitionResult, change_item_status, update_timestamps};
 
 mod cap;
-pub use cap::add_with_cap;
+pub use cap::{
+    BatchOutcome, DROPPED_TAG, EvictAction, Eviction, EvictionOutcome, add_batch_with_cap,
+    add_with_cap, add_with_evictions,
+};
+
+mod validation;
+pub use validation::{
+    Field, FieldError, MAX_AUTHOR_LENGTH, MAX_NAME_LENGTH, ValidationErrors, author_error,
+    is_supported_link, link_error, name_error, parse_item_type_field, parse_kind_field,
+};
+
+mod canonical;
+pub use canonical::{
+    Duplicate, DuplicateAction, DuplicateLocation, canonicalize_link, find_duplicate, link_key,
+    requeue_inbox_item,
+};
+
+mod metadata;
+pub use metadata::{PageMetadata, parse_page_metadata};
+
+mod readability;
+pub use readability::{Article, Snapshot, extract_article};
+
+mod link_check;
+pub use link_check::{LinkCheck, LinkHealth};
+
+mod estimate;
+pub use estimate::{
+    TimeTotal, WORDS_PER_MINUTE, estimate_minutes, format_minutes, minutes_from_seconds,
+    parse_iso8601_duration, parse_minutes_field, pick_for_time_slot, reading_minutes,
+};
+
+mod infer;
+pub use infer::{TypeRule, TypeRules, parse_type_rule};
+
+mod bib;
+pub use bib::{BibFields, citation_keys, item_authors, split_name};
+
+pub mod export;
+pub mod import;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, EnumString)]
 pub enum ItemType {
@@ -70,9 +111,16 @@ impl Kind {
             Kind::Reference => "🔖",
         }
     }
+
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            Kind::Normal => "normal",
+            Kind::Reference => "reference",
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Item {
     pub name: String,
     #[serde(rename = "type")]
@@ -85,10 +133,31 @@ pub struct Item {
     pub finished_at: Option<DateTime<Utc>>,
     pub note: String,
     pub kind: Kind,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bib: Option<BibFields>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<Highlight>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_check: Option<LinkCheck>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_minutes: Option<u32>,
     #[serde(rename = "_v")]
     pub version: u8,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Highlight {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added_at: Option<DateTime<Utc>>,
+}
+
 impl ItemType {
     pub fn abbreviation(&self) -> &'static str {
         match self {
@@ -146,24 +215,46 @@ impl Item {
         self.added_at.timestamp_micros() as usize
     }
 
-    pub fn validate(&self) -> Result<(), CoreError> {
-        if self.name.is_empty() {
-            return Err(CoreError::ValidationError(
-                "Name cannot be empty".to_string(),
-            ));
+    pub fn field_errors(&self) -> ValidationErrors {
+        let mut errors = ValidationErrors::default();
+        if let Some(message) = name_error(&self.name) {
+            errors.add(Field::Name, message);
+        }
+        if let Some(message) = author_error(&self.author) {
+            errors.add(Field::Author, message);
+        }
+        if let Some(message) = link_error(&self.link) {
+            errors.add(Field::Link, message);
         }
-        Ok(())
+        errors
+    }
+
+    pub fn validate(&self) -> Result<(), CoreError> {
+        self.field_errors().into_result()
+    }
+
+    // Only the fields that differ from `before` are checked, so editing a note isn't blocked
+    // by a link that was saved before the current rules.
+    pub fn changed_field_errors(&self, before: &Item) -> ValidationErrors {
+        let mut errors = self.field_errors();
+        errors.errors.retain(|error| match error.field {
+            Field::Name => self.name != before.name,
+            Field::Author => self.author != before.author,
+            Field::Link => self.link != before.link,
+            _ => true,
+        });
+        errors
     }
 
     pub fn format_for_list(&self, index: usize) -> String {
-        let name_display = if self.name.len() > 28 {
-            format!("{}..", &self.name[..26])
+        let name_display = if self.name.chars().count() > 28 {
+            format!("{}..", self.name.chars().take(26).collect::<String>())
         } else {
             self.name.clone()
         };
 
-        let author_display = if self.author.len() > 13 {
-            format!("{}..", &self.author[..11])
+        let author_display = if self.author.chars().count() > 13 {
+            format!("{}..", self.author.chars().take(11).collect::<String>())
         } else {
             self.author.clone()
         };
@@ -201,20 +292,30 @@ pub fn create_item(
     note: Option<String>,
     kind: Option<String>,
 ) -> Result<Item, CoreError> {
-    if name.is_empty() {
-        return Err(CoreError::ValidationError(
-            "Name cannot be empty".to_string(),
-        ));
-    }
+    let mut errors = ValidationErrors::default();
+    let link = link.unwrap_or_default();
+    let link = if link_error(&link).is_none() {
+        canonicalize_link(&link)
+    } else {
+        link
+    };
 
-    let parsed_type = match item_type.as_deref() {
-        Some(t) => ItemType::from_str(t).unwrap_or(ItemType::BlogPost),
-        None => ItemType::BlogPost,
+    // Without an explicit type only the built-in link rules apply; callers that have the
+    // config resolve the type with `TypeRules::from_config` first.
+    let parsed_type = match item_type.as_deref().map(str::trim) {
+        Some(t) if !t.is_empty() => parse_item_type_field(t).unwrap_or_else(|error| {
+            errors.errors.push(error);
+            ItemType::BlogPost
+        }),
+        _ => TypeRules::builtin().infer_or_default(&link),
     };
 
-    let parsed_kind = match kind.as_deref() {
-        Some(k) => Kind::from_str(k).unwrap_or(Kind::Normal),
-        None => Kind::Normal,
+    let parsed_kind = match kind.as_deref().map(str::trim) {
+        Some(k) if !k.is_empty() => parse_kind_field(k).unwrap_or_else(|error| {
+            errors.errors.push(error);
+            Kind::Normal
+        }),
+        _ => Kind::Normal,
     };
 
     let item = Item {
@@ -222,25 +323,47 @@ pub fn create_item(
         item_type: parsed_type,
         status: Status::Todo,
         author: author.unwrap_or_default(),
-        link: link.unwrap_or_default(),
+        link,
         added_at: Utc::now(),
         started_at: None,
         finished_at: None,
         note: note.unwrap_or_default(),
         kind: parsed_kind,
+        tags: Vec::new(),
+        bib: None,
+        highlights: Vec::new(),
+        link_check: None,
+        estimated_minutes: None,
         version: 1,
     };
 
-    item.validate()?;
+    errors.errors.extend(item.field_errors().errors);
+    errors.into_result()?;
     Ok(item)
 }
 
+fn check_duplicate(
+    inbox: &[Item],
+    someday: &[Item],
+    archive: &[Item],
+    item: &Item,
+) -> Result<(), CoreError> {
+    match find_duplicate(inbox, someday, archive, item) {
+        Some(duplicate) => Err(CoreError::Duplicate(Box::new(duplicate))),
+        None => Ok(()),
+    }
+}
+
+// Fields are checked where they are entered (`create_item`, edits), not here: items moving
+// between lists may have been saved under looser rules and must still be able to move.
 pub fn add_item_to_inbox(
     inbox: Vec<Item>,
     new_item: Item,
+    someday: &[Item],
+    archive: &[Item],
     config: &Config,
 ) -> Result<(Vec<Item>, Vec<Item>), CoreError> {
-    new_item.validate()?;
+    check_duplicate(&inbox, someday, archive, &new_item)?;
 
     add_with_cap(
         inbox,
@@ -251,6 +374,91 @@ pub fn add_item_to_inbox(
     .map_err(|_| CoreError::InboxFull)
 }
 
+// Saved items coming back to the inbox, reopened from the archive or promoted from someday,
+// are only compared with the inbox. They are already in folio once, and another archived copy
+// of the link doesn't make this one a new duplicate. The item's own copy never counts.
+pub fn move_item_to_inbox(
+    inbox: Vec<Item>,
+    item: Item,
+    config: &Config,
+) -> Result<(Vec<Item>, Vec<Item>), CoreError> {
+    add_item_to_inbox(inbox, item, &[], &[], config)
+}
+
+pub fn add_item_to_inbox_with_evictions(
+    inbox: Vec<Item>,
+    new_item: Item,
+    someday: &[Item],
+    archive: &[Item],
+    evictions: &[Eviction],
+    config: &Config,
+) -> Result<EvictionOutcome, CoreError> {
+    check_duplicate(&inbox, someday, archive, &new_item)?;
+
+    add_with_evictions(inbox, new_item, evictions, config.max_items as usize).map_err(|e| match e {
+        CapError::Full => CoreError::InboxFull,
+        CapError::InvalidEviction { position } => {
+            CoreError::ValidationError(format!("There is no inbox item #{}", position + 1))
+        }
+    })
+}
+
+pub fn add_items_to_inbox(
+    inbox: Vec<Item>,
+    new_items: Vec<Item>,
+    config: &Config,
+) -> Result<BatchOutcome, CoreError> {
+    Ok(add_batch_with_cap(
+        inbox,
+        new_items,
+        config.max_items as usize,
+        config.archive_on_overflow.clone(),
+    ))
+}
+
+pub fn add_item_to_someday(
+    someday: Vec<Item>,
+    new_item: Item,
+    config: &Config,
+) -> Result<(Vec<Item>, Vec<Item>), CoreError> {
+    add_with_cap(
+        someday,
+        new_item,
+        config.someday_max_items as usize,
+        config.someday_on_overflow.clone(),
+    )
+    .map_err(|_| CoreError::SomedayFull)
+}
+
+#[derive(Debug)]
+pub struct PromoteResult {
+    pub inbox_items: Vec<Item>,
+    pub someday_items: Vec<Item>,
+    pub promoted: Item,
+    pub overflow_items: Vec<Item>,
+}
+
+pub fn promote_from_someday(
+    inbox: Vec<Item>,
+    mut someday: Vec<Item>,
+    position: usize,
+    config: &Config,
+) -> Result<PromoteResult, CoreError> {
+    if position >= someday.len() {
+        return Err(CoreError::ItemNotFound);
+    }
+
+    let promoted = someday.remove(position);
+    let (inbox_items, overflow_items) = move_item_to_inbox(inbox, promoted.clone(), config)?;
+
+    Ok(PromoteResult {
+        inbox_items,
+        someday_items: someday,
+        promoted,
+        overflow_items,
+    })
+}
+
 #[derive(Debug)]
 pub struct StatusUpdateResult {
     pub inbox_items: Vec<Item>,
@@ -306,7 +514,7 @@ pub fn update_item_status(
             let item_to_move = archive_items.remove(item_index);
             result.moved_to_inbox = true;
 
-            match add_item_to_inbox(inbox_items.clone(), item_to_move.clone(), config) {
+            match move_item_to_inbox(inbox_items.clone(), item_to_move.clone(), config) {
                 Ok((new_inbox, to_archive)) => {
                     inbox_items = new_inbox;
                     result.overflow_items = to_archive.clone();
@@ -315,12 +523,12 @@ pub fn update_item_status(
                         archive_items.push(overflow_item);
                     }
                 }
-                Err(_) => {
+                Err(e) => {
                     // Rollback - put item back in archive
                     archive_items.insert(item_index, item_to_move);
                     result.inbox_items = inbox_items;
                     result.archive_items = archive_items;
-                    return Err(CoreError::InboxFull);
+                    return Err(e);
                 }
             }
         }
@@ -332,3 +540,56 @@ pub fn update_item_status(
 
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_rows_cut_long_names_on_character_boundaries() {
+        let mut item = create_item(
+            "Ça ira — über die Zukunft des Lesens im Netz".to_string(),
+            None,
+            Some("Gabriel García Márquez".to_string()),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let row = item.format_for_list(1);
+        assert!(row.contains("Ça ira — über die Zukunft .."));
+        assert!(row.contains("Gabriel Gar.."));
+
+        item.name = "東京の本屋".to_string();
+        item.author = "村上春樹".to_string();
+        let row = item.format_for_list(2);
+        assert!(row.contains("東京の本屋"));
+        assert!(row.contains("村上春樹"));
+    }
+
+    fn linked(name: &str, link: &str) -> Item {
+        create_item(
+            name.to_string(),
+            None,
+            None,
+            Some(link.to_string()),
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn adding_a_link_saved_for_someday_is_a_duplicate() {
+        let someday = vec![linked("Later", "https://example.com/later")];
+        let mut new_item = linked("Again", "http://www.example.com/later/?utm_source=feed");
+        new_item.added_at += chrono::Duration::seconds(1);
+
+        let result = add_item_to_inbox(Vec::new(), new_item, &someday, &[], &Config::default());
+        let Err(CoreError::Duplicate(duplicate)) = result else {
+            panic!("expected a duplicate, got {:?}", result);
+        };
+        assert_eq!(duplicate.location, DuplicateLocation::Someday(0));
+        assert_eq!(duplicate.display_id(5), 1);
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkHealth {
+    #[serde(rename = "ok")]
+    Ok,
+    #[serde(rename = "not_found")]
+    NotFound,
+    #[serde(rename = "dns_failure")]
+    DnsFailure,
+    #[serde(rename = "http_error")]
+    HttpError,
+    #[serde(rename = "timeout")]
+    Timeout,
+    #[serde(rename = "unreachable")]
+    Unreachable,
+}
+
+impl LinkHealth {
+    // A 404/410 or a domain that no longer resolves; anything else may just be a bad day.
+    pub fn is_dead(&self) -> bool {
+        matches!(self, LinkHealth::NotFound | LinkHealth::DnsFailure)
+    }
+
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            LinkHealth::Ok => "ok",
+            LinkHealth::NotFound => "not found",
+            LinkHealth::DnsFailure => "DNS failure",
+            LinkHealth::HttpError => "HTTP error",
+            LinkHealth::Timeout => "timed out",
+            LinkHealth::Unreachable => "unreachable",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkCheck {
+    pub checked_at: DateTime<Utc>,
+    pub health: LinkHealth,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    // Where the link redirected to, when that's a different page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl LinkCheck {
+    pub fn new(health: LinkHealth) -> Self {
+        Self {
+            checked_at: Utc::now(),
+            health,
+            http_status: None,
+            final_url: None,
+            archived_url: None,
+            message: None,
+        }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health.is_dead()
+    }
+
+    // "404", "DNS", "timeout"... short enough for a table column.
+    pub fn short_label(&self) -> String {
+        match (self.health, self.http_status) {
+            (LinkHealth::DnsFailure, _) => "DNS".to_string(),
+            (LinkHealth::Timeout, _) => "timeout".to_string(),
+            (LinkHealth::Unreachable, _) => "down".to_string(),
+            (_, Some(status)) => status.to_string(),
+            (health, None) => health.as_string().to_string(),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let mut description = match (self.health, self.http_status) {
+            (LinkHealth::Ok, _) => "ok".to_string(),
+            (health, Some(status)) => format!("{} (HTTP {})", health.as_string(), status),
+            (health, None) => health.as_string().to_string(),
+        };
+        if let Some(message) = &self.message {
+            description.push_str(&format!(": {}", message));
+        }
+        if let Some(final_url) = &self.final_url {
+            description.push_str(&format!(", redirects to {}", final_url));
+        }
+        description
+    }
+}
@@ -0,0 +1,370 @@
+use crate::import::markup::{attribute, decode_entities};
+use crate::validation::truncate;
+use crate::{ItemType, MAX_AUTHOR_LENGTH, MAX_NAME_LENGTH, parse_iso8601_duration};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub site_name: Option<String>,
+    pub item_type: Option<ItemType>,
+    // How long the video or episode runs, or the publisher's own reading time.
+    pub duration_seconds: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+struct JsonLd {
+    item_type: Option<ItemType>,
+    headline: Option<String>,
+    authors: Vec<String>,
+    duration_seconds: Option<u64>,
+}
+
+fn clean_text(text: &str) -> String {
+    decode_entities(text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Every `<name ...>` opening tag, found case-insensitively, with the text that follows it.
+fn tags<'a>(
+    html: &'a str,
+    lower: &'a str,
+    name: &'a str,
+) -> impl Iterator<Item = (&'a str, &'a str)> {
+    let open = format!("<{}", name);
+    let mut search_from = 0;
+
+    std::iter::from_fn(move || {
+        loop {
+            let start = search_from + lower[search_from..].find(&open)?;
+            let after_name = start + open.len();
+            search_from = after_name;
+
+            if !lower[after_name..].starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            {
+                continue;
+            }
+            let end = after_name + lower[after_name..].find('>')?;
+            search_from = end + 1;
+            return Some((&html[start..=end], &html[end + 1..]));
+        }
+    })
+}
+
+fn element_text<'a>(rest: &'a str, closing: &str) -> &'a str {
+    let lower = rest.to_ascii_lowercase();
+    &rest[..lower.find(closing).unwrap_or(rest.len())]
+}
+
+fn meta_values(html: &str, lower: &str) -> Vec<(String, String)> {
+    tags(html, lower, "meta")
+        .filter_map(|(tag, _)| {
+            let key = attribute(tag, "property")
+                .or_else(|| attribute(tag, "name"))
+                .or_else(|| attribute(tag, "itemprop"))?;
+            let content = clean_text(&attribute(tag, "content")?);
+            (!content.is_empty()).then(|| (key.trim().to_lowercase(), content))
+        })
+        .collect()
+}
+
+fn schema_type(name: &str) -> Option<ItemType> {
+    match name.rsplit('/').next().unwrap_or(name) {
+        "NewsArticle" | "ReportageNewsArticle" | "AnalysisNewsArticle" => Some(ItemType::News),
+        "Article" | "BlogPosting" | "TechArticle" | "Report" => Some(ItemType::BlogPost),
+        "VideoObject" | "Movie" | "Clip" => Some(ItemType::Video),
+        "PodcastEpisode" | "PodcastSeries" | "AudioObject" | "RadioEpisode" => {
+            Some(ItemType::Podcast)
+        }
+        "ScholarlyArticle" | "MedicalScholarlyArticle" => Some(ItemType::AcademicPaper),
+        "SocialMediaPosting" | "DiscussionForumPosting" => Some(ItemType::Thread),
+        _ => None,
+    }
+}
+
+fn og_type(value: &str) -> Option<ItemType> {
+    match value {
+        value if value.starts_with("video") => Some(ItemType::Video),
+        "article" => Some(ItemType::BlogPost),
+        _ => None,
+    }
+}
+
+// OpenGraph gives plain seconds, schema.org an ISO 8601 duration.
+fn duration_seconds(value: &str) -> Option<u64> {
+    let value = value.trim();
+    match value.parse::<f64>() {
+        Ok(seconds) if seconds > 0.0 => Some(seconds.round() as u64),
+        Ok(_) => None,
+        Err(_) => parse_iso8601_duration(value).filter(|seconds| *seconds > 0),
+    }
+}
+
+fn json_names(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(name) => vec![clean_text(name)],
+        Value::Object(object) => object
+            .get("name")
+            .and_then(Value::as_str)
+            .map(clean_text)
+            .into_iter()
+            .collect(),
+        Value::Array(values) => values.iter().flat_map(json_names).collect(),
+        _ => Vec::new(),
+    }
+}
+
+// Walks plain objects, arrays and "@graph" lists until it finds a node describing the page's
+// content (an article, video, episode...), which is what the title and author come from.
+fn find_content_node(value: &Value) -> Option<JsonLd> {
+    match value {
+        Value::Array(values) => values.iter().find_map(find_content_node),
+        Value::Object(object) => {
+            let types: Vec<&str> = match object.get("@type") {
+                Some(Value::String(name)) => vec![name.as_str()],
+                Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+
+            if let Some(item_type) = types.iter().find_map(|name| schema_type(name)) {
+                let headline = ["headline", "name"]
+                    .iter()
+                    .find_map(|key| object.get(*key).and_then(Value::as_str))
+                    .map(clean_text);
+                let authors = object
+                    .get("author")
+                    .or_else(|| object.get("creator"))
+                    .map(json_names)
+                    .unwrap_or_default();
+                let duration_seconds = ["duration", "timeRequired"]
+                    .iter()
+                    .filter_map(|key| object.get(*key).and_then(Value::as_str))
+                    .find_map(duration_seconds);
+                return Some(JsonLd {
+                    item_type: Some(item_type),
+                    headline,
+                    authors,
+                    duration_seconds,
+                });
+            }
+
+            object.get("@graph").and_then(find_content_node)
+        }
+        _ => None,
+    }
+}
+
+fn json_ld(html: &str, lower: &str) -> JsonLd {
+    tags(html, lower, "script")
+        .filter(|(tag, _)| {
+            attribute(tag, "type")
+                .is_some_and(|kind| kind.trim().eq_ignore_ascii_case("application/ld+json"))
+        })
+        .filter_map(|(_, rest)| serde_json::from_str(element_text(rest, "</script>").trim()).ok())
+        .find_map(|value: Value| find_content_node(&value))
+        .unwrap_or_default()
+}
+
+// "Title | Site", "Title - Site" and "Title — Site" lose the site name when it is known.
+fn strip_site_name(title: String, site_name: Option<&str>) -> String {
+    let Some(site_name) = site_name.map(str::to_lowercase) else {
+        return title;
+    };
+    for separator in [" | ", " - ", " — ", " – ", " · ", " :: "] {
+        if let Some((head, tail)) = title.rsplit_once(separator)
+            && tail.trim().to_lowercase() == site_name
+            && !head.trim().is_empty()
+        {
+            return head.trim().to_string();
+        }
+    }
+    title
+}
+
+fn looks_like_link(text: &str) -> bool {
+    text.contains("://") || text.starts_with("www.")
+}
+
+// Title, author, duration and a suggested type from OpenGraph, Twitter card, Highwire (citation_*),
+// JSON-LD and <title> data, preferring the more specific sources.
+pub fn parse_page_metadata(html: &str) -> PageMetadata {
+    let lower = html.to_ascii_lowercase();
+    let meta = meta_values(html, &lower);
+    let ld = json_ld(html, &lower);
+
+    let first = |keys: &[&str]| -> Option<String> {
+        keys.iter().find_map(|key| {
+            meta.iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, content)| content.clone())
+        })
+    };
+
+    let site_name = first(&["og:site_name", "application-name"]);
+
+    let title_tag = tags(html, &lower, "title")
+        .next()
+        .map(|(_, rest)| clean_text(element_text(rest, "</title>")))
+        .filter(|title| !title.is_empty());
+    let title = first(&["og:title", "twitter:title", "citation_title", "dc.title"])
+        .or(ld.headline.clone())
+        .or(title_tag)
+        .map(|title| strip_site_name(title, site_name.as_deref()))
+        .map(|title| truncate(title, MAX_NAME_LENGTH));
+
+    let citation_authors: Vec<&str> = meta
+        .iter()
+        .filter(|(name, _)| name == "citation_author")
+        .map(|(_, content)| content.as_str())
+        .collect();
+    let author = if !ld.authors.is_empty() {
+        Some(ld.authors.join(", "))
+    } else if !citation_authors.is_empty() {
+        Some(citation_authors.join(", "))
+    } else {
+        first(&["author", "article:author", "dc.creator", "parsely-author"])
+            .filter(|author| !looks_like_link(author))
+            .or_else(|| first(&["twitter:creator"]))
+    }
+    .map(|author| truncate(author, MAX_AUTHOR_LENGTH));
+
+    let item_type = if citation_authors.is_empty() && first(&["citation_title"]).is_none() {
+        ld.item_type
+            .or_else(|| first(&["og:type"]).and_then(|value| og_type(&value)))
+    } else {
+        Some(ItemType::AcademicPaper)
+    };
+
+    let duration_seconds = ld.duration_seconds.or_else(|| {
+        first(&[
+            "og:video:duration",
+            "video:duration",
+            "music:duration",
+            "duration",
+        ])
+        .and_then(|value| duration_seconds(&value))
+    });
+
+    PageMetadata {
+        title,
+        author,
+        site_name,
+        item_type,
+        duration_seconds,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_content_node_from_a_json_ld_graph() {
+        let html = r#"<html><head>
+            <title>Ignored | Example</title>
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@graph": [
+                {"@type": "WebSite", "name": "Example"},
+                {"@type": "BreadcrumbList", "itemListElement": []},
+                {"@type": ["Thing", "BlogPosting"], "headline": "Writing &amp; Reading",
+                 "author": [{"@type": "Person", "name": "Ada Lovelace"}, "Charles Babbage"],
+                 "timeRequired": "PT12M"}
+            ]}
+            </script></head><body></body></html>"#;
+
+        let metadata = parse_page_metadata(html);
+        assert_eq!(metadata.title.as_deref(), Some("Writing & Reading"));
+        assert_eq!(
+            metadata.author.as_deref(),
+            Some("Ada Lovelace, Charles Babbage")
+        );
+        assert_eq!(metadata.item_type, Some(ItemType::BlogPost));
+        assert_eq!(metadata.duration_seconds, Some(720));
+    }
+
+    #[test]
+    fn skips_malformed_json_ld_blocks() {
+        let html = r#"<script type="application/ld+json">{"@type": "NewsArticle",</script>
+            <script type="application/ld+json">{"@type": "VideoObject", "name": "Talk",
+            "duration": "PT1H2M"}</script>"#;
+
+        let metadata = parse_page_metadata(html);
+        assert_eq!(metadata.title.as_deref(), Some("Talk"));
+        assert_eq!(metadata.item_type, Some(ItemType::Video));
+        assert_eq!(metadata.duration_seconds, Some(3720));
+    }
+
+    #[test]
+    fn citation_tags_make_an_academic_paper() {
+        let html = r#"<head>
+            <meta name="citation_title" content="On Computable Numbers">
+            <meta name="citation_author" content="Turing, A. M.">
+            <meta name="citation_author" content="Church, A.">
+            <meta property="og:type" content="article">
+            <title>On Computable Numbers - Journal</title></head>"#;
+
+        let metadata = parse_page_metadata(html);
+        assert_eq!(metadata.title.as_deref(), Some("On Computable Numbers"));
+        assert_eq!(
+            metadata.author.as_deref(),
+            Some("Turing, A. M., Church, A.")
+        );
+        assert_eq!(metadata.item_type, Some(ItemType::AcademicPaper));
+    }
+
+    #[test]
+    fn strips_the_site_name_from_the_title() {
+        for (title, expected) in [
+            ("A Long Read | The Site", "A Long Read"),
+            ("A Long Read - the site", "A Long Read"),
+            ("A Long Read — The Site", "A Long Read"),
+            ("Part one - Part two", "Part one - Part two"),
+            (" | The Site", "| The Site"),
+        ] {
+            let html = format!(
+                r#"<meta property="og:site_name" content="The Site"><title>{}</title>"#,
+                title
+            );
+            let metadata = parse_page_metadata(&html);
+            assert_eq!(metadata.title.as_deref(), Some(expected), "{}", title);
+            assert_eq!(metadata.site_name.as_deref(), Some("The Site"));
+        }
+    }
+
+    #[test]
+    fn keeps_the_title_without_a_site_name() {
+        let metadata = parse_page_metadata("<TITLE>\n  Notes  | Blog\n</TITLE>");
+        assert_eq!(metadata.title.as_deref(), Some("Notes | Blog"));
+    }
+
+    #[test]
+    fn ignores_author_links_in_favour_of_the_twitter_creator() {
+        let html = r#"<meta name="author" content="https://example.com/people/ada">
+            <meta name="twitter:creator" content="@ada">"#;
+
+        assert_eq!(parse_page_metadata(html).author.as_deref(), Some("@ada"));
+    }
+
+    #[test]
+    fn fits_long_and_non_ascii_titles() {
+        let title = "長い記事のタイトル".repeat(40);
+        let html = format!(r#"<meta property="og:title" content="{}">"#, title);
+
+        let metadata = parse_page_metadata(&html);
+        let fitted = metadata.title.unwrap();
+        assert!(fitted.chars().count() <= MAX_NAME_LENGTH);
+        assert!(title.starts_with(fitted.trim_end_matches('…')));
+    }
+
+    #[test]
+    fn empty_page_has_no_metadata() {
+        assert_eq!(parse_page_metadata(""), PageMetadata::default());
+        assert_eq!(
+            parse_page_metadata("<html><title>   </title></html>"),
+            PageMetadata::default()
+        );
+    }
+}
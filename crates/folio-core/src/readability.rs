@@ -0,0 +1,680 @@
+use crate::import::markup::{attribute, decode_entities};
+use crate::parse_page_metadata;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+// Content of these is dropped while parsing.
+const RAW_TAGS: &[&str] = &["script", "style", "template", "textarea", "title"];
+// Page furniture that never holds the article.
+const SKIP_TAGS: &[&str] = &[
+    "head", "noscript", "nav", "aside", "footer", "header", "form", "iframe", "svg", "button",
+    "select", "figure", "object", "canvas", "dialog", "menu",
+];
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "blockquote",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "li",
+    "main",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "td",
+    "th",
+    "tr",
+    "ul",
+];
+
+const POSITIVE_HINTS: &[&str] = &[
+    "article", "body", "content", "entry", "main", "page", "post", "text", "blog", "story",
+];
+const NEGATIVE_HINTS: &[&str] = &[
+    "comment",
+    "footer",
+    "footnote",
+    "masthead",
+    "media",
+    "meta",
+    "outbrain",
+    "promo",
+    "related",
+    "share",
+    "sidebar",
+    "sponsor",
+    "shopping",
+    "tags",
+    "tool",
+    "widget",
+    "nav",
+    "menu",
+    "banner",
+    "cookie",
+    "newsletter",
+    "subscribe",
+    "social",
+    "popup",
+    "modal",
+];
+
+const MIN_PARAGRAPH_CHARS: usize = 25;
+
+#[derive(Debug)]
+enum NodeKind {
+    Element { name: String, hints: String },
+    Text(String),
+}
+
+#[derive(Debug)]
+struct Node {
+    kind: NodeKind,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+// A forgiving HTML tree: unknown or unbalanced closing tags are ignored, and paragraphs and
+// list items close themselves the way browsers close them.
+struct Dom {
+    nodes: Vec<Node>,
+}
+
+impl Dom {
+    fn parse(html: &str) -> Self {
+        let mut dom = Dom {
+            nodes: vec![Node {
+                kind: NodeKind::Element {
+                    name: "#root".to_string(),
+                    hints: String::new(),
+                },
+                parent: None,
+                children: Vec::new(),
+            }],
+        };
+        let mut stack = vec![0];
+        let mut rest = html;
+
+        while !rest.is_empty() {
+            let Some(start) = rest.find('<') else {
+                dom.push(&stack, NodeKind::Text(rest.to_string()));
+                break;
+            };
+            if start > 0 {
+                dom.push(&stack, NodeKind::Text(rest[..start].to_string()));
+            }
+            rest = &rest[start..];
+
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+                continue;
+            }
+            if rest.starts_with("<!") || rest.starts_with("<?") {
+                rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+                continue;
+            }
+
+            if let Some(closing) = rest.strip_prefix("</") {
+                let end = closing.find('>').unwrap_or(closing.len());
+                let name = closing[..end].trim().to_ascii_lowercase();
+                if let Some(depth) = stack.iter().rposition(|&index| dom.name(index) == name)
+                    && depth > 0
+                {
+                    stack.truncate(depth);
+                }
+                rest = closing.get(end + 1..).unwrap_or("");
+                continue;
+            }
+
+            let name_len = rest[1..]
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+                .unwrap_or(rest.len() - 1);
+            let Some(end) = rest.find('>').filter(|_| name_len > 0) else {
+                dom.push(&stack, NodeKind::Text("<".to_string()));
+                rest = &rest[1..];
+                continue;
+            };
+
+            let tag = &rest[..=end];
+            let name = rest[1..=name_len].to_ascii_lowercase();
+            rest = &rest[end + 1..];
+
+            if RAW_TAGS.contains(&name.as_str()) {
+                let lower = rest.to_ascii_lowercase();
+                rest = lower
+                    .find(&format!("</{}", name))
+                    .and_then(|close| rest[close..].find('>').map(|end| &rest[close + end + 1..]))
+                    .unwrap_or("");
+                continue;
+            }
+
+            dom.close_implicitly(&mut stack, &name);
+
+            let hints = [attribute(tag, "class"), attribute(tag, "id")]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase();
+            let index = dom.push(
+                &stack,
+                NodeKind::Element {
+                    name: name.clone(),
+                    hints,
+                },
+            );
+
+            if !VOID_TAGS.contains(&name.as_str()) && !tag.ends_with("/>") {
+                stack.push(index);
+            }
+        }
+
+        dom
+    }
+
+    fn push(&mut self, stack: &[usize], kind: NodeKind) -> usize {
+        let parent = *stack.last().unwrap_or(&0);
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            kind,
+            parent: Some(parent),
+            children: Vec::new(),
+        });
+        self.nodes[parent].children.push(index);
+        index
+    }
+
+    fn close_implicitly(&self, stack: &mut Vec<usize>, opening: &str) {
+        let Some(&top) = stack.last() else {
+            return;
+        };
+        let open = self.name(top);
+        let closes = match opening {
+            "li" => open == "li",
+            "dt" | "dd" => matches!(open, "dt" | "dd"),
+            "tr" => matches!(open, "tr" | "td" | "th"),
+            "td" | "th" => matches!(open, "td" | "th"),
+            "option" => open == "option",
+            name if BLOCK_TAGS.contains(&name) => open == "p",
+            _ => false,
+        };
+        if closes && stack.len() > 1 {
+            stack.pop();
+        }
+    }
+
+    fn name(&self, index: usize) -> &str {
+        match &self.nodes[index].kind {
+            NodeKind::Element { name, .. } => name,
+            NodeKind::Text(_) => "#text",
+        }
+    }
+
+    fn hints(&self, index: usize) -> &str {
+        match &self.nodes[index].kind {
+            NodeKind::Element { hints, .. } => hints,
+            NodeKind::Text(_) => "",
+        }
+    }
+
+    fn is_skipped(&self, index: usize) -> bool {
+        SKIP_TAGS.contains(&self.name(index))
+            || NEGATIVE_HINTS
+                .iter()
+                .any(|hint| self.hints(index).contains(hint))
+                && !POSITIVE_HINTS
+                    .iter()
+                    .any(|hint| self.hints(index).contains(hint))
+                && !matches!(self.name(index), "body" | "article" | "main")
+    }
+
+    fn has_skipped_ancestor(&self, index: usize) -> bool {
+        let mut current = self.nodes[index].parent;
+        while let Some(parent) = current {
+            if self.is_skipped(parent) {
+                return true;
+            }
+            current = self.nodes[parent].parent;
+        }
+        false
+    }
+
+    // Visible text of a subtree and how much of it sits inside links.
+    fn text_stats(&self, index: usize) -> (String, usize) {
+        let mut text = String::new();
+        let mut link_chars = 0;
+        self.collect_text(index, false, &mut text, &mut link_chars);
+        (text, link_chars)
+    }
+
+    fn collect_text(&self, index: usize, in_link: bool, text: &mut String, link_chars: &mut usize) {
+        match &self.nodes[index].kind {
+            NodeKind::Text(raw) => {
+                let decoded = decode_entities(raw);
+                if in_link {
+                    *link_chars += decoded.trim().chars().count();
+                }
+                text.push_str(&decoded);
+            }
+            NodeKind::Element { name, .. } => {
+                if SKIP_TAGS.contains(&name.as_str()) {
+                    return;
+                }
+                let in_link = in_link || name == "a";
+                for &child in &self.nodes[index].children {
+                    self.collect_text(child, in_link, text, link_chars);
+                }
+                if BLOCK_TAGS.contains(&name.as_str()) || name == "br" {
+                    text.push(' ');
+                }
+            }
+        }
+    }
+
+    fn link_density(&self, index: usize) -> f64 {
+        let (text, link_chars) = self.text_stats(index);
+        let total = text
+            .split_whitespace()
+            .map(|word| word.chars().count())
+            .sum::<usize>();
+        if total == 0 {
+            0.0
+        } else {
+            (link_chars as f64 / total as f64).min(1.0)
+        }
+    }
+
+    fn has_block_children(&self, index: usize) -> bool {
+        self.nodes[index]
+            .children
+            .iter()
+            .any(|&child| BLOCK_TAGS.contains(&self.name(child)))
+    }
+}
+
+fn tag_weight(name: &str) -> f64 {
+    match name {
+        "article" | "main" => 10.0,
+        "div" | "section" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "address" | "ol" | "ul" | "dl" | "dd" | "dt" | "li" | "form" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    }
+}
+
+fn hint_weight(hints: &str) -> f64 {
+    let mut weight = 0.0;
+    if POSITIVE_HINTS.iter().any(|hint| hints.contains(hint)) {
+        weight += 25.0;
+    }
+    if NEGATIVE_HINTS.iter().any(|hint| hints.contains(hint)) {
+        weight -= 25.0;
+    }
+    weight
+}
+
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Scores containers by the paragraphs inside them (longer, comma-rich text counts more, and
+// link-heavy text counts less), in the spirit of Mozilla's Readability.
+fn find_article(dom: &Dom) -> Vec<usize> {
+    let mut scores: HashMap<usize, f64> = HashMap::new();
+
+    for index in 0..dom.nodes.len() {
+        let name = dom.name(index);
+        let is_paragraph =
+            matches!(name, "p" | "pre" | "td") || (name == "div" && !dom.has_block_children(index));
+        if !is_paragraph || dom.is_skipped(index) || dom.has_skipped_ancestor(index) {
+            continue;
+        }
+
+        let (text, _) = dom.text_stats(index);
+        let text = collapse(&text);
+        let length = text.chars().count();
+        if length < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+
+        let score = 1.0 + text.matches(',').count() as f64 + (length as f64 / 100.0).min(3.0);
+        let parent = dom.nodes[index].parent;
+        let grandparent = parent.and_then(|parent| dom.nodes[parent].parent);
+
+        for (ancestor, share) in [(parent, 1.0), (grandparent, 0.5)] {
+            if let Some(ancestor) = ancestor {
+                *scores.entry(ancestor).or_insert_with(|| {
+                    tag_weight(dom.name(ancestor)) + hint_weight(dom.hints(ancestor))
+                }) += score * share;
+            }
+        }
+    }
+
+    let best = scores
+        .iter()
+        .map(|(&index, &score)| (index, score * (1.0 - dom.link_density(index))))
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+    let Some((top, top_score)) = best else {
+        return vec![0];
+    };
+
+    // Siblings that score well, or read like prose, belong to the article too (e.g. an intro
+    // paragraph outside the main container).
+    let Some(parent) = dom.nodes[top].parent else {
+        return vec![top];
+    };
+    let threshold = (top_score * 0.2).max(10.0);
+    dom.nodes[parent]
+        .children
+        .iter()
+        .copied()
+        .filter(|&sibling| {
+            if sibling == top {
+                return true;
+            }
+            if dom.is_skipped(sibling) {
+                return false;
+            }
+            let scored = scores
+                .get(&sibling)
+                .is_some_and(|score| score * (1.0 - dom.link_density(sibling)) >= threshold);
+            let prose = dom.name(sibling) == "p" && {
+                let (text, _) = dom.text_stats(sibling);
+                collapse(&text).chars().count() > 80 && dom.link_density(sibling) < 0.25
+            };
+            scored || prose
+        })
+        .collect()
+}
+
+struct Renderer<'a> {
+    dom: &'a Dom,
+    blocks: Vec<String>,
+    inline: String,
+}
+
+impl Renderer<'_> {
+    fn flush(&mut self, prefix: &str) {
+        let lines: Vec<String> = self
+            .inline
+            .split('\n')
+            .map(collapse)
+            .filter(|line| !line.is_empty())
+            .collect();
+        if !lines.is_empty() {
+            self.blocks.push(format!("{}{}", prefix, lines.join("\n")));
+        }
+        self.inline.clear();
+    }
+
+    fn block(&mut self, index: usize, prefix: &str) {
+        self.flush("");
+        for &child in &self.dom.nodes[index].children {
+            self.render(child);
+        }
+        self.flush(prefix);
+    }
+
+    fn render(&mut self, index: usize) {
+        let dom = self.dom;
+        let name = match &dom.nodes[index].kind {
+            NodeKind::Text(raw) => {
+                self.inline
+                    .push_str(&decode_entities(raw).replace('\n', " "));
+                return;
+            }
+            NodeKind::Element { name, .. } => name.as_str(),
+        };
+        if dom.is_skipped(index) {
+            return;
+        }
+
+        match name {
+            "br" => self.inline.push('\n'),
+            "hr" => {
+                self.flush("");
+                self.blocks.push("---".to_string());
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = name[1..].parse().unwrap_or(2);
+                self.block(index, &format!("{} ", "#".repeat(level)));
+            }
+            "li" => self.block(index, "- "),
+            "pre" => {
+                self.flush("");
+                let (text, _) = dom.text_stats(index);
+                let code: Vec<String> = text
+                    .trim_matches('\n')
+                    .lines()
+                    .map(|line| format!("    {}", line.trim_end()))
+                    .collect();
+                if !code.is_empty() {
+                    self.blocks.push(code.join("\n"));
+                }
+            }
+            "blockquote" => {
+                let mut quote = Renderer {
+                    dom,
+                    blocks: Vec::new(),
+                    inline: String::new(),
+                };
+                for &child in &dom.nodes[index].children {
+                    quote.render(child);
+                }
+                quote.flush("");
+                self.flush("");
+                for block in quote.blocks {
+                    let quoted: Vec<String> =
+                        block.lines().map(|line| format!("> {}", line)).collect();
+                    self.blocks.push(quoted.join("\n"));
+                }
+            }
+            "img" => {}
+            name if BLOCK_TAGS.contains(&name) => self.block(index, ""),
+            _ => {
+                for &child in &dom.nodes[index].children {
+                    self.render(child);
+                }
+            }
+        }
+    }
+}
+
+// Blank lines between blocks, except within a run of list items.
+fn join_blocks(blocks: &[String]) -> String {
+    let mut text = String::new();
+    for (index, block) in blocks.iter().enumerate() {
+        if index > 0 {
+            let in_list = block.starts_with("- ") && blocks[index - 1].starts_with("- ");
+            text.push_str(if in_list { "\n" } else { "\n\n" });
+        }
+        text.push_str(block);
+    }
+    text
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Article {
+    pub title: Option<String>,
+    pub byline: Option<String>,
+    pub text: String,
+}
+
+// The readable part of a page as plain text: paragraphs separated by blank lines, with
+// "#" headings, "- " list items, "> " quotes and indented code.
+pub fn extract_article(html: &str) -> Article {
+    let metadata = parse_page_metadata(html);
+    let dom = Dom::parse(html);
+
+    let mut renderer = Renderer {
+        dom: &dom,
+        blocks: Vec::new(),
+        inline: String::new(),
+    };
+    for index in find_article(&dom) {
+        renderer.render(index);
+    }
+    renderer.flush("");
+
+    // The page title often repeats as the article's first heading.
+    if let (Some(title), Some(first)) = (&metadata.title, renderer.blocks.first())
+        && collapse(first.trim_start_matches('#')) == *title
+    {
+        renderer.blocks.remove(0);
+    }
+
+    Article {
+        title: metadata.title,
+        byline: metadata.author,
+        text: join_blocks(&renderer.blocks),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub url: String,
+    pub title: Option<String>,
+    pub byline: Option<String>,
+    pub fetched_at: DateTime<Utc>,
+    pub text: String,
+}
+
+impl Snapshot {
+    pub fn new(url: String, article: Article) -> Self {
+        Self {
+            url,
+            title: article.title,
+            byline: article.byline,
+            fetched_at: Utc::now(),
+            text: article.text,
+        }
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.text.split_whitespace().count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LONGREAD: &str = include_str!("../tests/fixtures/readability/longread.html");
+    const DIV_SOUP: &str = include_str!("../tests/fixtures/readability/div-soup.html");
+
+    #[test]
+    fn keeps_the_article_and_its_structure() {
+        let article = extract_article(LONGREAD);
+
+        assert_eq!(article.title.as_deref(), Some("How Tides Work"));
+        assert_eq!(article.byline.as_deref(), Some("Marina Reyes"));
+        assert!(article.text.starts_with("The tides are caused by"));
+        assert!(article.text.contains("\n\n## Spring and neap tides\n\n"));
+        assert!(
+            article
+                .text
+                .contains("- Spring tides: larger range\n- Neap tides: smaller range")
+        );
+        assert!(article.text.contains("> Time and tide wait for no man."));
+        assert!(
+            article
+                .text
+                .contains("    high = mean + amplitude\n    low  = mean")
+        );
+        assert!(
+            article
+                .text
+                .ends_with("the largest in the world & a popular sight.")
+        );
+    }
+
+    #[test]
+    fn drops_page_furniture() {
+        let text = extract_article(LONGREAD).text;
+
+        for boilerplate in [
+            "Home",
+            "cookies",
+            "not content",
+            "color: red",
+            "Related posts",
+            "Great article",
+            "Copyright",
+        ] {
+            assert!(!text.contains(boilerplate), "{}", boilerplate);
+        }
+        // The page title isn't repeated as the first heading.
+        assert!(!text.contains("# How Tides Work"));
+    }
+
+    #[test]
+    fn finds_the_story_among_plain_divs() {
+        let article = extract_article(DIV_SOUP);
+        let text = &article.text;
+
+        assert_eq!(article.title.as_deref(), Some("Notes on Compilers"));
+        assert!(text.starts_with("Compilers turn the text"));
+        assert!(text.contains("### Optimisation"));
+        assert!(text.contains("- Constant folding\n- Dead code elimination"));
+        assert!(text.contains("        println!(\"hi\");"));
+        for boilerplate in ["Sport", "Share on", "Subscribe", "Ten facts", "newsletter"] {
+            assert!(!text.contains(boilerplate), "{}", boilerplate);
+        }
+    }
+
+    #[test]
+    fn counts_the_words_of_the_article_only() {
+        let snapshot = Snapshot::new(
+            "https://example.com/tides".to_string(),
+            extract_article(LONGREAD),
+        );
+
+        // The article's own words, the code block and the list and heading markers; none of
+        // the navigation, banner, sidebar, comments or footer.
+        assert_eq!(snapshot.word_count(), 173);
+        assert_eq!(
+            Snapshot::new(String::new(), extract_article("")).word_count(),
+            0
+        );
+    }
+
+    #[test]
+    fn keeps_non_ascii_text() {
+        let html = "<article><p>Les marées sont causées par la lune, dit l’océanographe — 潮汐は月の引力によって起こる。</p></article>";
+        let text = extract_article(html).text;
+
+        assert_eq!(
+            text,
+            "Les marées sont causées par la lune, dit l’océanographe — 潮汐は月の引力によって起こる。"
+        );
+    }
+
+    #[test]
+    fn pages_without_prose_have_no_text() {
+        for html in [
+            "",
+            "<html><head><title>Empty</title></head><body></body></html>",
+            "<nav><a href=\"/\">Home</a></nav><footer><p>Copyright 2026, all rights reserved.</p></footer>",
+            "<p>unclosed <b>tags <i>everywhere",
+        ] {
+            let text = extract_article(html).text;
+            assert!(text.len() < 30 && !text.contains("Copyright"), "{:?}", text);
+        }
+    }
+}
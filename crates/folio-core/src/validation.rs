@@ -0,0 +1,282 @@
+use crate::{CoreError, ItemType, Kind};
+use std::fmt;
+use std::str::FromStr;
+
+pub const MAX_NAME_LENGTH: usize = 300;
+pub const MAX_AUTHOR_LENGTH: usize = 200;
+
+const SUPPORTED_SCHEMES: &[&str] = &["http", "https", "ftp"];
+const ITEM_TYPES: &[&str] = &[
+    "blog_post",
+    "video",
+    "podcast",
+    "news",
+    "thread",
+    "academic_paper",
+    "other",
+];
+const KINDS: &[&str] = &["normal", "reference"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Field {
+    Name,
+    Type,
+    Kind,
+    Author,
+    Link,
+    Note,
+    Minutes,
+}
+
+impl Field {
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            Field::Name => "name",
+            Field::Type => "type",
+            Field::Kind => "kind",
+            Field::Author => "author",
+            Field::Link => "link",
+            Field::Note => "note",
+            Field::Minutes => "minutes",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: Field,
+    pub message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field.as_string(), self.message)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    pub errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn add(&mut self, field: Field, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            field,
+            message: message.into(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn message_for(&self, field: Field) -> Option<&str> {
+        self.errors
+            .iter()
+            .find(|error| error.field == field)
+            .map(|error| error.message.as_str())
+    }
+
+    // Errors are reported in form order, whichever check found them first.
+    pub fn into_result(mut self) -> Result<(), CoreError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            self.errors.sort_by_key(|error| error.field);
+            Err(CoreError::InvalidFields(self))
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors: Vec<String> = self.errors.iter().map(FieldError::to_string).collect();
+        write!(f, "{}", errors.join("; "))
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+// A typo (roughly one edit per three letters), or an unambiguous prefix like "acad".
+fn closest<'a>(input: &str, options: &[&'a str]) -> Option<&'a str> {
+    let prefixed: Vec<&str> = options
+        .iter()
+        .copied()
+        .filter(|option| input.len() >= 2 && option.starts_with(input))
+        .collect();
+    if let [option] = prefixed.as_slice() {
+        return Some(option);
+    }
+
+    options
+        .iter()
+        .map(|option| (edit_distance(input, option), *option))
+        .filter(|(distance, _)| *distance <= input.chars().count().div_ceil(3))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, option)| option)
+}
+
+fn unknown_choice(what: &str, value: &str, options: &[&str]) -> String {
+    match closest(&value.to_lowercase(), options) {
+        Some(suggestion) => format!(
+            "unknown {} '{}', did you mean '{}'?",
+            what, value, suggestion
+        ),
+        None => format!(
+            "unknown {} '{}'. Valid options are: {}",
+            what,
+            value,
+            options.join(", ")
+        ),
+    }
+}
+
+pub fn parse_item_type_field(value: &str) -> Result<ItemType, FieldError> {
+    ItemType::from_str(&value.trim().to_lowercase()).map_err(|_| FieldError {
+        field: Field::Type,
+        message: unknown_choice("type", value.trim(), ITEM_TYPES),
+    })
+}
+
+pub fn parse_kind_field(value: &str) -> Result<Kind, FieldError> {
+    Kind::from_str(&value.trim().to_lowercase()).map_err(|_| FieldError {
+        field: Field::Kind,
+        message: unknown_choice("kind", value.trim(), KINDS),
+    })
+}
+
+fn host_error(host: &str) -> Option<String> {
+    let host = host.rsplit('@').next().unwrap_or(host);
+    if host.starts_with('[') {
+        return None;
+    }
+
+    let (host, port) = host.split_once(':').unwrap_or((host, ""));
+    if host.is_empty() {
+        return Some("is missing a host".to_string());
+    }
+    if !port.chars().all(|c| c.is_ascii_digit()) {
+        return Some(format!("has an invalid port '{}'", port));
+    }
+
+    let valid = host
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '.' | '_'))
+        && !host.starts_with(['.', '-'])
+        && !host.ends_with(['-'])
+        && (host.contains('.') || host.eq_ignore_ascii_case("localhost"));
+
+    (!valid).then(|| format!("has an invalid host '{}'", host))
+}
+
+// None for an empty link or a full http(s)/ftp URL, otherwise why the link was rejected.
+pub fn link_error(link: &str) -> Option<String> {
+    let link = link.trim();
+    if link.is_empty() {
+        return None;
+    }
+    if link.contains(char::is_whitespace) {
+        return Some("cannot contain spaces".to_string());
+    }
+
+    let Some((scheme, rest)) = link.split_once("://") else {
+        return match link.split_once(':') {
+            Some((scheme, _))
+                if !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphabetic()) =>
+            {
+                Some(format!(
+                    "unsupported scheme '{}'. Use {}",
+                    scheme,
+                    SUPPORTED_SCHEMES.join(", ")
+                ))
+            }
+            _ if host_error(link.split('/').next().unwrap_or(link)).is_none() => Some(format!(
+                "'{}' is not a full URL, did you mean 'https://{}'?",
+                link, link
+            )),
+            _ => Some(format!("'{}' is not a URL", link)),
+        };
+    };
+
+    let scheme = scheme.to_lowercase();
+    if !SUPPORTED_SCHEMES.contains(&scheme.as_str()) {
+        return Some(format!(
+            "unsupported scheme '{}'. Use {}",
+            scheme,
+            SUPPORTED_SCHEMES.join(", ")
+        ));
+    }
+
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    host_error(authority)
+}
+
+pub fn is_supported_link(link: &str) -> bool {
+    !link.trim().is_empty() && link_error(link).is_none()
+}
+
+pub fn name_error(name: &str) -> Option<String> {
+    let length = name.chars().count();
+    if name.trim().is_empty() {
+        Some("cannot be empty".to_string())
+    } else if name.contains(['\n', '\r']) {
+        Some("cannot contain line breaks".to_string())
+    } else if length > MAX_NAME_LENGTH {
+        Some(format!(
+            "is too long ({} characters, at most {})",
+            length, MAX_NAME_LENGTH
+        ))
+    } else {
+        None
+    }
+}
+
+pub(crate) fn truncate(text: String, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text;
+    }
+    let mut truncated: String = text.chars().take(max - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+// Imported titles and authors are made to fit rather than rejected, so one overlong bookmark
+// title doesn't sink an import of thousands.
+pub(crate) fn fit_name(name: &str) -> String {
+    truncate(
+        name.split_whitespace().collect::<Vec<_>>().join(" "),
+        MAX_NAME_LENGTH,
+    )
+}
+
+pub(crate) fn fit_author(author: &str) -> String {
+    truncate(
+        author.split_whitespace().collect::<Vec<_>>().join(" "),
+        MAX_AUTHOR_LENGTH,
+    )
+}
+
+pub fn author_error(author: &str) -> Option<String> {
+    let length = author.chars().count();
+    (length > MAX_AUTHOR_LENGTH).then(|| {
+        format!(
+            "is too long ({} characters, at most {})",
+            length, MAX_AUTHOR_LENGTH
+        )
+    })
+}
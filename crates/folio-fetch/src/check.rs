@@ -0,0 +1,169 @@
+use crate::{FetchError, FetchOptions, FetchResult, build_client, error_message};
+use folio_core::{Config, LinkCheck, LinkHealth, link_key};
+use reqwest::StatusCode;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time::{MissedTickBehavior, interval};
+
+pub struct LinkChecker {
+    client: reqwest::Client,
+    wayback_api: String,
+}
+
+fn is_dns_error(error: &reqwest::Error) -> bool {
+    let mut source: Option<&dyn std::error::Error> = Some(error);
+    while let Some(cause) = source {
+        let message = cause.to_string().to_lowercase();
+        if message.contains("dns error") || message.contains("failed to lookup address") {
+            return true;
+        }
+        source = cause.source();
+    }
+    false
+}
+
+fn health_for(status: StatusCode) -> LinkHealth {
+    match status.as_u16() {
+        _ if status.is_success() => LinkHealth::Ok,
+        404 | 410 => LinkHealth::NotFound,
+        _ => LinkHealth::HttpError,
+    }
+}
+
+impl LinkChecker {
+    pub fn new(options: FetchOptions, wayback_api: &str) -> FetchResult<Self> {
+        if options.offline {
+            return Err(FetchError::Offline);
+        }
+        Ok(Self {
+            client: build_client(&options)?,
+            wayback_api: wayback_api.to_string(),
+        })
+    }
+
+    pub fn from_config(config: &Config) -> FetchResult<Self> {
+        Self::new(FetchOptions::from_config(config), &config.wayback_api)
+    }
+
+    // HEAD first, since it skips the body. Plenty of servers refuse or mishandle HEAD, so
+    // anything but a clean answer is asked again with a GET.
+    pub async fn check(&self, url: &str) -> LinkCheck {
+        let response = match self.client.head(url).send().await {
+            Ok(response) if response.status().is_success() => Ok(response),
+            Err(e) if is_dns_error(&e) => Err(e),
+            _ => self.client.get(url).send().await,
+        };
+
+        match response {
+            Ok(response) => {
+                let status = response.status();
+                let mut check = LinkCheck::new(health_for(status));
+                check.http_status = Some(status.as_u16());
+                let final_url = response.url().as_str();
+                if link_key(final_url) != link_key(url) {
+                    check.final_url = Some(final_url.to_string());
+                }
+                check
+            }
+            Err(e) => {
+                let health = if e.is_timeout() {
+                    LinkHealth::Timeout
+                } else if is_dns_error(&e) {
+                    LinkHealth::DnsFailure
+                } else {
+                    LinkHealth::Unreachable
+                };
+                let mut check = LinkCheck::new(health);
+                check.message = Some(error_message(&e));
+                check
+            }
+        }
+    }
+
+    // The Wayback Machine's closest capture of the link, if it has one.
+    pub async fn archived_copy(&self, url: &str) -> Option<String> {
+        let body = self
+            .client
+            .get(&self.wayback_api)
+            .query(&[("url", url)])
+            .send()
+            .await
+            .ok()?
+            .error_for_status()
+            .ok()?
+            .text()
+            .await
+            .ok()?;
+
+        let json: serde_json::Value = serde_json::from_str(&body).ok()?;
+        let closest = &json["archived_snapshots"]["closest"];
+        if closest["available"].as_bool() != Some(true) {
+            return None;
+        }
+        let archived = closest["url"].as_str()?;
+        Some(match archived.strip_prefix("http://web.archive.org/") {
+            Some(path) => format!("https://web.archive.org/{}", path),
+            None => archived.to_string(),
+        })
+    }
+
+    // Checks every link with at most `concurrency` requests in flight, starting no more than
+    // `per_second` of them each second. Results come back in the order of `urls`, and
+    // `on_result` sees each one as it finishes.
+    pub async fn check_all(
+        self: Arc<Self>,
+        urls: Vec<String>,
+        concurrency: usize,
+        per_second: u32,
+        wayback: bool,
+        mut on_result: impl FnMut(usize, &LinkCheck),
+    ) -> Vec<LinkCheck> {
+        let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+        let mut pace = interval(Duration::from_secs_f64(1.0 / per_second.max(1) as f64));
+        pace.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let mut results: Vec<Option<LinkCheck>> = vec![None; urls.len()];
+        let mut tasks = JoinSet::new();
+        let mut record = |results: &mut Vec<Option<LinkCheck>>,
+                          (index, check): (usize, LinkCheck)| {
+            on_result(index, &check);
+            results[index] = Some(check);
+        };
+
+        for (index, url) in urls.into_iter().enumerate() {
+            pace.tick().await;
+            let Ok(permit) = semaphore.clone().acquire_owned().await else {
+                break;
+            };
+            while let Some(Ok(result)) = tasks.try_join_next() {
+                record(&mut results, result);
+            }
+
+            let checker = self.clone();
+            tasks.spawn(async move {
+                let _permit = permit;
+                let mut check = checker.check(&url).await;
+                if wayback && check.is_dead() {
+                    check.archived_url = checker.archived_copy(&url).await;
+                }
+                (index, check)
+            });
+        }
+        while let Some(Ok(result)) = tasks.join_next().await {
+            record(&mut results, result);
+        }
+
+        results
+            .into_iter()
+            .map(|check| {
+                check.unwrap_or_else(|| {
+                    let mut check = LinkCheck::new(LinkHealth::Unreachable);
+                    check.message = Some("check did not finish".to_string());
+                    check
+                })
+            })
+            .collect()
+    }
+}
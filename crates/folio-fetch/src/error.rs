@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum FetchError {
+    #[error("Offline mode is on (`folio config set offline false` to go online)")]
+    Offline,
+
+    #[error("Only http and https links can be fetched: {url}")]
+    UnsupportedLink { url: String },
+
+    #[error("Timed out after {seconds}s fetching {url}")]
+    Timeout { url: String, seconds: u32 },
+
+    #[error("{url} returned HTTP {status}")]
+    Status { url: String, status: u16 },
+
+    #[error("{url} is not a web page ({content_type})")]
+    NotHtml { url: String, content_type: String },
+
+    #[error("Failed to fetch {url}: {message}")]
+    Request { url: String, message: String },
+}
+
+pub type FetchResult<T> = Result<T, FetchError>;
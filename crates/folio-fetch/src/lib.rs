@@ -0,0 +1,174 @@
+use folio_core::{Config, PageMetadata, parse_page_metadata};
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use std::time::Duration;
+
+pub mod check;
+pub mod error;
+
+pub use check::LinkChecker;
+pub use error::{FetchError, FetchResult};
+
+const USER_AGENT: &str = concat!(
+    "folio/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/smitchaudhary/folio)"
+);
+
+// Pages are read up to this size; titles and authors live in the <head> anyway.
+const MAX_PAGE_BYTES: usize = 5 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub struct FetchOptions {
+    pub timeout_secs: u32,
+    pub offline: bool,
+}
+
+impl FetchOptions {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            timeout_secs: config.fetch_timeout,
+            offline: config.offline,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Page {
+    pub url: String,
+    pub html: String,
+}
+
+pub struct Fetcher {
+    client: reqwest::Client,
+    options: FetchOptions,
+}
+
+fn decode(bytes: &[u8], content_type: &str) -> String {
+    let charset = content_type
+        .to_ascii_lowercase()
+        .split(';')
+        .find_map(|part| part.trim().strip_prefix("charset=").map(str::to_string))
+        .map(|charset| charset.trim_matches('"').to_string());
+
+    match charset.as_deref() {
+        Some("iso-8859-1" | "latin1" | "windows-1252") => {
+            bytes.iter().map(|&byte| char::from(byte)).collect()
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+fn build_client(options: &FetchOptions) -> FetchResult<reqwest::Client> {
+    let timeout = Duration::from_secs(options.timeout_secs.max(1) as u64);
+    reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(timeout)
+        .connect_timeout(timeout)
+        .redirect(reqwest::redirect::Policy::limited(10))
+        .build()
+        .map_err(|e| FetchError::Request {
+            url: String::new(),
+            message: e.to_string(),
+        })
+}
+
+// The innermost cause ("Connection refused", "dns error") says the most.
+fn error_message(error: &reqwest::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message = cause.to_string();
+        source = cause.source();
+    }
+    message
+}
+
+impl Fetcher {
+    pub fn new(options: FetchOptions) -> FetchResult<Self> {
+        let client = build_client(&options)?;
+        Ok(Self { client, options })
+    }
+
+    pub fn from_config(config: &Config) -> FetchResult<Self> {
+        Self::new(FetchOptions::from_config(config))
+    }
+
+    fn request_error(&self, url: &str, error: reqwest::Error) -> FetchError {
+        if error.is_timeout() {
+            FetchError::Timeout {
+                url: url.to_string(),
+                seconds: self.options.timeout_secs,
+            }
+        } else {
+            FetchError::Request {
+                url: url.to_string(),
+                message: error_message(&error),
+            }
+        }
+    }
+
+    // Follows redirects; `Page::url` is where the page was finally found.
+    pub async fn fetch_page(&self, url: &str) -> FetchResult<Page> {
+        if self.options.offline {
+            return Err(FetchError::Offline);
+        }
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(FetchError::UnsupportedLink {
+                url: url.to_string(),
+            });
+        }
+
+        let mut response = self
+            .client
+            .get(url)
+            .header(ACCEPT, "text/html,application/xhtml+xml;q=0.9,*/*;q=0.5")
+            .send()
+            .await
+            .map_err(|e| self.request_error(url, e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(FetchError::Status {
+                url: url.to_string(),
+                status: status.as_u16(),
+            });
+        }
+
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("text/html")
+            .to_string();
+        if !content_type.contains("html") {
+            return Err(FetchError::NotHtml {
+                url: url.to_string(),
+                content_type,
+            });
+        }
+
+        let final_url = response.url().to_string();
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| self.request_error(url, e))?
+        {
+            body.extend_from_slice(&chunk);
+            if body.len() >= MAX_PAGE_BYTES {
+                body.truncate(MAX_PAGE_BYTES);
+                break;
+            }
+        }
+
+        Ok(Page {
+            url: final_url,
+            html: decode(&body, &content_type),
+        })
+    }
+
+    pub async fn fetch_metadata(&self, url: &str) -> FetchResult<PageMetadata> {
+        let page = self.fetch_page(url).await?;
+        Ok(parse_page_metadata(&page.html))
+    }
+}
@@ -0,0 +1,236 @@
+mod common;
+
+use common::{Reply, serve};
+use folio_core::LinkHealth;
+use folio_fetch::{FetchError, FetchOptions, LinkChecker};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const UNUSED_WAYBACK: &str = "http://127.0.0.1:9/wayback/available";
+
+fn options(timeout_secs: u32) -> FetchOptions {
+    FetchOptions {
+        timeout_secs,
+        offline: false,
+    }
+}
+
+fn checker(wayback_api: &str) -> LinkChecker {
+    LinkChecker::new(options(5), wayback_api).unwrap()
+}
+
+#[tokio::test]
+async fn falls_back_to_get_when_head_is_refused() {
+    let methods = Arc::new(Mutex::new(Vec::new()));
+    let seen = methods.clone();
+    let server = serve(move |request| {
+        seen.lock().unwrap().push(request.method.clone());
+        match request.method.as_str() {
+            "HEAD" => Reply::new(405),
+            _ => Reply::html("<p>fine</p>"),
+        }
+    })
+    .await;
+
+    let check = checker(UNUSED_WAYBACK).check(&server.url("/nohead")).await;
+    assert_eq!(check.health, LinkHealth::Ok);
+    assert_eq!(check.http_status, Some(200));
+    assert_eq!(*methods.lock().unwrap(), ["HEAD", "GET"]);
+}
+
+#[tokio::test]
+async fn a_good_head_answer_skips_the_get() {
+    let methods = Arc::new(Mutex::new(Vec::new()));
+    let seen = methods.clone();
+    let server = serve(move |request| {
+        seen.lock().unwrap().push(request.method.clone());
+        Reply::html("<p>fine</p>")
+    })
+    .await;
+
+    let check = checker(UNUSED_WAYBACK).check(&server.url("/ok")).await;
+    assert_eq!(check.health, LinkHealth::Ok);
+    assert_eq!(*methods.lock().unwrap(), ["HEAD"]);
+}
+
+#[tokio::test]
+async fn classifies_http_statuses() {
+    let server = serve(|request| match request.path.as_str() {
+        "/gone" => Reply::new(404),
+        "/removed" => Reply::new(410),
+        "/error" => Reply::new(503),
+        _ => Reply::new(200),
+    })
+    .await;
+
+    let checker = checker(UNUSED_WAYBACK);
+    for (path, health, status) in [
+        ("/gone", LinkHealth::NotFound, 404),
+        ("/removed", LinkHealth::NotFound, 410),
+        ("/error", LinkHealth::HttpError, 503),
+    ] {
+        let check = checker.check(&server.url(path)).await;
+        assert_eq!(check.health, health, "{}", path);
+        assert_eq!(check.http_status, Some(status), "{}", path);
+    }
+}
+
+#[tokio::test]
+async fn records_where_a_redirect_ends() {
+    let server = serve(|request| match request.path.as_str() {
+        "/moved" => Reply::redirect("/ok"),
+        _ => Reply::new(200),
+    })
+    .await;
+
+    let check = checker(UNUSED_WAYBACK).check(&server.url("/moved")).await;
+    assert_eq!(check.health, LinkHealth::Ok);
+    assert_eq!(check.final_url, Some(server.url("/ok")));
+}
+
+#[tokio::test]
+async fn unresolvable_hosts_are_dns_failures() {
+    let check = checker(UNUSED_WAYBACK)
+        .check("http://folio-link-check.invalid/")
+        .await;
+    assert_eq!(check.health, LinkHealth::DnsFailure, "{:?}", check.message);
+    assert!(check.is_dead());
+}
+
+#[tokio::test]
+async fn refused_connections_are_unreachable() {
+    // Bind a port and let it go, so nothing is listening there.
+    let address = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let check = checker(UNUSED_WAYBACK)
+        .check(&format!("http://{}/", address))
+        .await;
+    assert_eq!(check.health, LinkHealth::Unreachable);
+    assert!(!check.is_dead());
+    assert!(check.message.is_some());
+}
+
+#[tokio::test]
+async fn slow_links_time_out() {
+    let server = serve(|_| Reply::new(200).after(Duration::from_secs(5))).await;
+
+    let check = LinkChecker::new(options(1), UNUSED_WAYBACK)
+        .unwrap()
+        .check(&server.url("/slow"))
+        .await;
+    assert_eq!(check.health, LinkHealth::Timeout);
+}
+
+#[tokio::test]
+async fn offline_mode_refuses_to_check() {
+    let offline = FetchOptions {
+        timeout_secs: 5,
+        offline: true,
+    };
+    assert!(matches!(
+        LinkChecker::new(offline, UNUSED_WAYBACK),
+        Err(FetchError::Offline)
+    ));
+}
+
+#[tokio::test]
+async fn keeps_no_more_than_the_limit_in_flight() {
+    let server = serve(|_| Reply::new(200).after(Duration::from_millis(150))).await;
+    let urls: Vec<String> = (0..12).map(|i| server.url(&format!("/{}", i))).collect();
+
+    let checks = Arc::new(checker(UNUSED_WAYBACK))
+        .check_all(urls, 3, 1000, false, |_, _| {})
+        .await;
+
+    assert_eq!(checks.len(), 12);
+    assert!(checks.iter().all(|check| check.health == LinkHealth::Ok));
+    assert_eq!(server.peak_in_flight(), 3);
+}
+
+#[tokio::test]
+async fn starts_no_more_than_the_rate_allows() {
+    let server = serve(|_| Reply::new(200)).await;
+    let urls: Vec<String> = (0..6).map(|i| server.url(&format!("/{}", i))).collect();
+
+    let started = Instant::now();
+    let checks = Arc::new(checker(UNUSED_WAYBACK))
+        .check_all(urls, 10, 10, false, |_, _| {})
+        .await;
+
+    // The first check starts at once, the other five 100ms apart.
+    assert_eq!(checks.len(), 6);
+    assert!(started.elapsed() >= Duration::from_millis(500));
+}
+
+#[tokio::test]
+async fn results_come_back_in_link_order() {
+    let server = serve(|request| match request.path.as_str() {
+        "/first" => Reply::new(404).after(Duration::from_millis(200)),
+        _ => Reply::new(200),
+    })
+    .await;
+    let urls = vec![server.url("/first"), server.url("/second")];
+
+    let finished = Arc::new(Mutex::new(Vec::new()));
+    let order = finished.clone();
+    let checks = Arc::new(checker(UNUSED_WAYBACK))
+        .check_all(urls, 2, 1000, false, move |index, _| {
+            order.lock().unwrap().push(index)
+        })
+        .await;
+
+    assert_eq!(*finished.lock().unwrap(), [1, 0]);
+    assert_eq!(checks[0].health, LinkHealth::NotFound);
+    assert_eq!(checks[1].health, LinkHealth::Ok);
+}
+
+#[tokio::test]
+async fn dead_links_get_an_archived_copy() {
+    let server = serve(|request| {
+        if request.path.starts_with("/wayback/available?url=") {
+            if request.path.contains("gone") {
+                Reply::new(200).body(
+                    r#"{"archived_snapshots": {"closest": {"available": true, "status": "200",
+                    "url": "http://web.archive.org/web/20200101000000/http://example.com/gone"}}}"#,
+                )
+            } else {
+                Reply::new(200).body(r#"{"archived_snapshots": {}}"#)
+            }
+        } else if request.path == "/error" {
+            Reply::new(503)
+        } else {
+            Reply::new(404)
+        }
+    })
+    .await;
+
+    let urls = vec![
+        server.url("/gone"),
+        server.url("/never-archived"),
+        server.url("/error"),
+    ];
+    let checks = Arc::new(checker(&server.url("/wayback/available")))
+        .check_all(urls, 2, 1000, true, |_, _| {})
+        .await;
+
+    assert_eq!(
+        checks[0].archived_url.as_deref(),
+        Some("https://web.archive.org/web/20200101000000/http://example.com/gone")
+    );
+    assert_eq!(checks[1].health, LinkHealth::NotFound);
+    assert_eq!(checks[1].archived_url, None);
+    // Only dead links are looked up; a server error may well be temporary.
+    assert_eq!(checks[2].health, LinkHealth::HttpError);
+    assert_eq!(checks[2].archived_url, None);
+}
+
+#[tokio::test]
+async fn archive_lookups_fail_quietly() {
+    let server = serve(|_| Reply::new(500)).await;
+
+    let checker = checker(&server.url("/wayback/available"));
+    assert_eq!(checker.archived_copy("http://example.com/gone").await, None);
+}
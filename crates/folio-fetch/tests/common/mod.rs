@@ -0,0 +1,135 @@
+// A throwaway HTTP/1.1 server on a random local port, answering each request from a route table.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+#[derive(Debug, Clone)]
+pub struct Reply {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    pub delay: Duration,
+}
+
+impl Reply {
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn html(body: &str) -> Self {
+        Self::new(200)
+            .header("Content-Type", "text/html; charset=utf-8")
+            .body(body)
+    }
+
+    pub fn redirect(location: &str) -> Self {
+        Self::new(301).header("Location", location)
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn body(mut self, body: &str) -> Self {
+        self.body = body.to_string();
+        self
+    }
+
+    pub fn after(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+}
+
+pub struct Server {
+    pub base: String,
+    peak_in_flight: Arc<AtomicUsize>,
+}
+
+impl Server {
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base, path)
+    }
+
+    // The most requests that were being answered at the same time.
+    #[allow(dead_code)]
+    pub fn peak_in_flight(&self) -> usize {
+        self.peak_in_flight.load(Ordering::SeqCst)
+    }
+}
+
+pub async fn serve<F>(route: F) -> Server
+where
+    F: Fn(&Request) -> Reply + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let route = Arc::new(route);
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak_in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = peak_in_flight.clone();
+
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let route = route.clone();
+            let in_flight = in_flight.clone();
+            let peak = peak.clone();
+            tokio::spawn(async move {
+                let mut head = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(read) => head.extend_from_slice(&buffer[..read]),
+                    }
+                }
+
+                let head = String::from_utf8_lossy(&head);
+                let mut request_line = head.lines().next().unwrap_or("").split(' ');
+                let request = Request {
+                    method: request_line.next().unwrap_or("").to_string(),
+                    path: request_line.next().unwrap_or("/").to_string(),
+                };
+
+                let reply = route(&request);
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(reply.delay).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                let mut response = format!("HTTP/1.1 {} Stub\r\n", reply.status);
+                for (name, value) in &reply.headers {
+                    response.push_str(&format!("{}: {}\r\n", name, value));
+                }
+                response.push_str(&format!(
+                    "Content-Length: {}\r\nConnection: close\r\n\r\n",
+                    reply.body.len()
+                ));
+                if request.method != "HEAD" {
+                    response.push_str(&reply.body);
+                }
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+
+    Server {
+        base,
+        peak_in_flight,
+    }
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use common::{Reply, serve};
+use folio_core::ItemType;
+use folio_fetch::{FetchError, FetchOptions, Fetcher};
+use std::time::Duration;
+
+const ARTICLE: &str = r#"<!doctype html><html><head>
+<title>Slow Reading, Fast Lists | Field Notes</title>
+<meta property="og:site_name" content="Field Notes">
+<meta property="og:type" content="article">
+<meta name="author" content="Grace Hopper">
+</head><body><p>Body</p></body></html>"#;
+
+fn fetcher(timeout_secs: u32) -> Fetcher {
+    Fetcher::new(FetchOptions {
+        timeout_secs,
+        offline: false,
+    })
+    .unwrap()
+}
+
+#[tokio::test]
+async fn prefills_title_author_and_type() {
+    let server = serve(|request| match request.path.as_str() {
+        "/article" => Reply::html(ARTICLE),
+        _ => Reply::new(404),
+    })
+    .await;
+
+    let metadata = fetcher(5)
+        .fetch_metadata(&server.url("/article"))
+        .await
+        .unwrap();
+    assert_eq!(metadata.title.as_deref(), Some("Slow Reading, Fast Lists"));
+    assert_eq!(metadata.author.as_deref(), Some("Grace Hopper"));
+    assert_eq!(metadata.item_type, Some(ItemType::BlogPost));
+}
+
+#[tokio::test]
+async fn follows_redirects_to_the_final_url() {
+    let server = serve(|request| match request.path.as_str() {
+        "/old" => Reply::redirect("/moved"),
+        "/moved" => Reply::redirect("/article"),
+        "/article" => Reply::html(ARTICLE),
+        _ => Reply::new(404),
+    })
+    .await;
+
+    let page = fetcher(5).fetch_page(&server.url("/old")).await.unwrap();
+    assert_eq!(page.url, server.url("/article"));
+    assert!(page.html.contains("Slow Reading"));
+}
+
+#[tokio::test]
+async fn times_out_on_a_slow_server() {
+    let server = serve(|_| Reply::html(ARTICLE).after(Duration::from_secs(5))).await;
+
+    let error = fetcher(1)
+        .fetch_page(&server.url("/slow"))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, FetchError::Timeout { seconds: 1, .. }),
+        "{:?}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn offline_mode_never_touches_the_network() {
+    let fetcher = Fetcher::new(FetchOptions {
+        timeout_secs: 5,
+        offline: true,
+    })
+    .unwrap();
+
+    // Nothing listens on port 9 (discard); offline must answer before trying it.
+    let error = fetcher.fetch_page("http://127.0.0.1:9/").await.unwrap_err();
+    assert!(matches!(error, FetchError::Offline), "{:?}", error);
+}
+
+#[tokio::test]
+async fn rejects_responses_that_are_not_html() {
+    let server = serve(|request| match request.path.as_str() {
+        "/paper.pdf" => Reply::new(200)
+            .header("Content-Type", "application/pdf")
+            .body("%PDF-1.7"),
+        "/data.json" => Reply::new(200)
+            .header("Content-Type", "application/json")
+            .body("{}"),
+        _ => Reply::new(404),
+    })
+    .await;
+
+    for (path, expected) in [
+        ("/paper.pdf", "application/pdf"),
+        ("/data.json", "application/json"),
+    ] {
+        match fetcher(5).fetch_page(&server.url(path)).await {
+            Err(FetchError::NotHtml { content_type, .. }) => assert_eq!(content_type, expected),
+            other => panic!("{}: {:?}", path, other.map(|page| page.url)),
+        }
+    }
+}
+
+#[tokio::test]
+async fn reports_http_errors_and_unsupported_links() {
+    let server = serve(|_| Reply::new(404)).await;
+
+    let error = fetcher(5)
+        .fetch_page(&server.url("/missing"))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, FetchError::Status { status: 404, .. }),
+        "{:?}",
+        error
+    );
+
+    let error = fetcher(5)
+        .fetch_page("ftp://example.com/file")
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, FetchError::UnsupportedLink { .. }),
+        "{:?}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn decodes_latin1_pages() {
+    let server = serve(|_| {
+        let mut reply = Reply::new(200).header("Content-Type", "text/html; charset=ISO-8859-1");
+        reply.body = "<title>Caf\u{e9}</title>".to_string();
+        reply
+    })
+    .await;
+
+    // The stub writes UTF-8, so a latin1 decode shows each byte of "é" on its own.
+    let page = fetcher(5).fetch_page(&server.url("/")).await.unwrap();
+    assert_eq!(page.html, "<title>Caf\u{c3}\u{a9}</title>");
+}
@@ -0,0 +1,90 @@
+use folio_core::{Item, Kind, Status};
+use folio_storage::{
+    append_item_to_file, load_items_from_file, read_item_at, save_indexed_items_to_file,
+    update_item_at,
+};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+const ITERATIONS: u32 = 200;
+const REWRITE_ITERATIONS: u32 = 5;
+
+fn sample_item(index: usize) -> Item {
+    let mut item = folio_core::create_item(
+        format!("Archived article number {}", index),
+        Some("blog_post".to_string()),
+        Some("Some Author".to_string()),
+        Some(format!("https://example.com/posts/{}", index)),
+        Some("A short note about why this was worth reading".to_string()),
+        None,
+    )
+    .expect("valid item");
+    item.status = Status::Done;
+    folio_core::update_timestamps(&mut item);
+    item
+}
+
+fn time_per_op(iterations: u32, mut op: impl FnMut(u32)) -> Duration {
+    let start = Instant::now();
+    for i in 0..iterations {
+        op(i);
+    }
+    start.elapsed() / iterations
+}
+
+fn bench_size(dir: &Path, size: usize) {
+    let path = dir.join(format!("archive-{}.jsonl", size));
+    let items: Vec<Item> = (0..size).map(sample_item).collect();
+    save_indexed_items_to_file(&items, &path).expect("write archive");
+
+    let position = size / 2;
+
+    let toggle_reference = time_per_op(ITERATIONS, |i| {
+        let mut item = read_item_at(&path, position).unwrap().unwrap();
+        item.kind = if i % 2 == 0 {
+            Kind::Reference
+        } else {
+            Kind::Normal
+        };
+        update_item_at(&path, position, &item).unwrap();
+    });
+
+    let edit_note = time_per_op(ITERATIONS, |i| {
+        let mut item = read_item_at(&path, position).unwrap().unwrap();
+        item.note = format!("Edited note, revision {}", i);
+        update_item_at(&path, position, &item).unwrap();
+    });
+
+    let full_rewrite = time_per_op(REWRITE_ITERATIONS, |i| {
+        let mut all = load_items_from_file(&path).unwrap();
+        all[position].note = format!("Rewritten note, revision {}", i);
+        save_indexed_items_to_file(&all, &path).unwrap();
+    });
+
+    println!(
+        "{:>8} items | toggle ref {:>10.1?} | edit note {:>10.1?} | full rewrite {:>10.1?}",
+        size, toggle_reference, edit_note, full_rewrite
+    );
+}
+
+fn main() {
+    let dir = std::env::temp_dir().join(format!("folio-bench-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("create bench dir");
+
+    println!(
+        "Average latency per single-item update ({} iterations)",
+        ITERATIONS
+    );
+    for size in SIZES {
+        bench_size(&dir, size);
+    }
+
+    let append_path = dir.join("archive-append.jsonl");
+    let append = time_per_op(ITERATIONS, |i| {
+        append_item_to_file(&sample_item(i as usize), &append_path).unwrap()
+    });
+    println!("append to archive          {:>10.1?}", append);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
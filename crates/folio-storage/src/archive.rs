@@ -0,0 +1,441 @@
+use crate::{
+    StorageError, StorageResult, append_item_to_file, ensure_folio_dir, get_archive_path,
+    get_folio_dir, index, load_items_from_file, save_indexed_items_to_file,
+    serialize_items_to_jsonl,
+};
+use chrono::{Datelike, Utc};
+use folio_core::Item;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveScope {
+    Recent,
+    All,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArchiveSegment {
+    pub year: i32,
+    pub path: PathBuf,
+    pub compressed: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct CompactReport {
+    pub moved: Vec<(i32, usize)>,
+    pub compressed: Vec<i32>,
+    pub remaining: usize,
+}
+
+pub fn archive_year(item: &Item) -> i32 {
+    item.finished_at.unwrap_or(item.added_at).year()
+}
+
+const ARCHIVE_FILE: &str = "archive.jsonl";
+const SEGMENTS_DIR: &str = "archive";
+
+pub fn get_archive_segments_dir() -> StorageResult<PathBuf> {
+    Ok(get_folio_dir()?.join(SEGMENTS_DIR))
+}
+
+fn segment_path(dir: &Path, year: i32, compressed: bool) -> PathBuf {
+    if compressed {
+        dir.join(format!("{}.jsonl.gz", year))
+    } else {
+        dir.join(format!("{}.jsonl", year))
+    }
+}
+
+pub fn list_archive_segments() -> StorageResult<Vec<ArchiveSegment>> {
+    list_segments_in(&get_archive_segments_dir()?)
+}
+
+fn list_segments_in(dir: &Path) -> StorageResult<Vec<ArchiveSegment>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(_) => {
+            return Err(StorageError::FileRead {
+                path: dir.to_path_buf(),
+            });
+        }
+    };
+
+    let mut segments = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        let (stem, compressed) = if let Some(stem) = file_name.strip_suffix(".jsonl.gz") {
+            (stem, true)
+        } else if let Some(stem) = file_name.strip_suffix(".jsonl") {
+            (stem, false)
+        } else {
+            continue;
+        };
+
+        if let Ok(year) = stem.parse::<i32>() {
+            segments.push(ArchiveSegment {
+                year,
+                path,
+                compressed,
+            });
+        }
+    }
+
+    segments.sort_by_key(|segment| segment.year);
+    Ok(segments)
+}
+
+pub fn load_segment(segment: &ArchiveSegment) -> StorageResult<Vec<Item>> {
+    load_items_from_file(&segment.path)
+}
+
+fn remove_segment_file(path: &Path) -> StorageResult<()> {
+    fs::remove_file(path).map_err(|_| StorageError::FileWrite {
+        path: path.to_path_buf(),
+    })?;
+    let _ = fs::remove_file(index::index_path(path));
+    let _ = fs::remove_file(index::journal_path(path));
+    Ok(())
+}
+
+fn write_segment(dir: &Path, year: i32, items: &[Item], compressed: bool) -> StorageResult<()> {
+    fs::create_dir_all(dir).map_err(|_| StorageError::DirectoryCreation {
+        path: dir.to_path_buf(),
+    })?;
+
+    save_indexed_items_to_file(items, segment_path(dir, year, compressed))?;
+
+    // A year lives in exactly one segment, so drop the other encoding if present.
+    let stale = segment_path(dir, year, !compressed);
+    if stale.exists() {
+        remove_segment_file(&stale)?;
+    }
+
+    Ok(())
+}
+
+// The recent archive comes first and older years follow, newest first, so a position in the
+// recent archive means the same item whichever scope was loaded.
+pub fn load_archive(scope: ArchiveScope) -> StorageResult<Vec<Item>> {
+    let mut items = Vec::new();
+    for path in archive_files(scope)? {
+        items.extend(load_items_from_file(path)?);
+    }
+    Ok(items)
+}
+
+pub fn save_archive_scoped(items: &[Item], scope: ArchiveScope) -> StorageResult<()> {
+    ensure_folio_dir()?;
+    let archive_path = get_archive_path()?;
+
+    if scope == ArchiveScope::Recent {
+        return save_indexed_items_to_file(items, archive_path);
+    }
+
+    let dir = get_archive_segments_dir()?;
+    let segments = list_archive_segments()?;
+
+    let mut by_year: BTreeMap<i32, Vec<Item>> = segments
+        .iter()
+        .map(|segment| (segment.year, Vec::new()))
+        .collect();
+    let mut recent = Vec::new();
+
+    for item in items {
+        match by_year.get_mut(&archive_year(item)) {
+            Some(bucket) => bucket.push(item.clone()),
+            None => recent.push(item.clone()),
+        }
+    }
+
+    for segment in &segments {
+        let bucket = &by_year[&segment.year];
+        let existing = load_segment(segment)?;
+
+        if serialize_items_to_jsonl(&existing)? == serialize_items_to_jsonl(bucket)? {
+            continue;
+        }
+
+        if bucket.is_empty() {
+            remove_segment_file(&segment.path)?;
+        } else {
+            write_segment(&dir, segment.year, bucket, segment.compressed)?;
+        }
+    }
+
+    save_indexed_items_to_file(&recent, archive_path)
+}
+
+pub fn compact_archive(compress: bool) -> StorageResult<CompactReport> {
+    ensure_folio_dir()?;
+    let archive_path = get_archive_path()?;
+    let dir = get_archive_segments_dir()?;
+    let current_year = Utc::now().year();
+
+    let mut closed: BTreeMap<i32, Vec<Item>> = BTreeMap::new();
+    let mut recent = Vec::new();
+
+    for item in load_items_from_file(&archive_path)? {
+        let year = archive_year(&item);
+        if year < current_year {
+            closed.entry(year).or_default().push(item);
+        } else {
+            recent.push(item);
+        }
+    }
+
+    let segments = list_archive_segments()?;
+    let mut report = CompactReport {
+        remaining: recent.len(),
+        ..CompactReport::default()
+    };
+
+    for (year, moved) in &closed {
+        let existing = segments.iter().find(|segment| segment.year == *year);
+
+        let mut combined = match existing {
+            Some(segment) => load_segment(segment)?,
+            None => Vec::new(),
+        };
+        combined.extend(moved.iter().cloned());
+
+        let compressed = compress || existing.is_some_and(|segment| segment.compressed);
+        write_segment(&dir, *year, &combined, compressed)?;
+
+        report.moved.push((*year, moved.len()));
+        if compressed {
+            report.compressed.push(*year);
+        }
+    }
+
+    if compress {
+        for segment in &segments {
+            if segment.compressed
+                || segment.year >= current_year
+                || closed.contains_key(&segment.year)
+            {
+                continue;
+            }
+
+            let items = load_segment(segment)?;
+            write_segment(&dir, segment.year, &items, true)?;
+            report.compressed.push(segment.year);
+        }
+        report.compressed.sort();
+    }
+
+    // Segments are written before the recent file is trimmed, so an interruption can only
+    // leave duplicates behind, never lose items.
+    if !closed.is_empty() {
+        save_indexed_items_to_file(&recent, archive_path)?;
+    }
+
+    Ok(report)
+}
+
+fn archive_files(scope: ArchiveScope) -> StorageResult<Vec<PathBuf>> {
+    archive_files_in(&get_folio_dir()?, scope)
+}
+
+fn archive_files_in(folio_dir: &Path, scope: ArchiveScope) -> StorageResult<Vec<PathBuf>> {
+    let mut files = vec![folio_dir.join(ARCHIVE_FILE)];
+
+    if scope == ArchiveScope::All {
+        files.extend(
+            list_segments_in(&folio_dir.join(SEGMENTS_DIR))?
+                .into_iter()
+                .rev()
+                .map(|segment| segment.path),
+        );
+    }
+
+    Ok(files)
+}
+
+fn locate_archive_position(
+    scope: ArchiveScope,
+    position: usize,
+) -> StorageResult<Option<(PathBuf, usize)>> {
+    locate_archive_position_in(&get_folio_dir()?, scope, position)
+}
+
+fn locate_archive_position_in(
+    folio_dir: &Path,
+    scope: ArchiveScope,
+    mut position: usize,
+) -> StorageResult<Option<(PathBuf, usize)>> {
+    for path in archive_files_in(folio_dir, scope)? {
+        let count = index::record_count(&path)?;
+        if position < count {
+            return Ok(Some((path, position)));
+        }
+        position -= count;
+    }
+
+    Ok(None)
+}
+
+pub fn archive_len(scope: ArchiveScope) -> StorageResult<usize> {
+    let mut len = 0;
+    for path in archive_files(scope)? {
+        len += index::record_count(&path)?;
+    }
+    Ok(len)
+}
+
+pub fn read_archive_item(scope: ArchiveScope, position: usize) -> StorageResult<Option<Item>> {
+    match locate_archive_position(scope, position)? {
+        Some((path, local)) => index::read_item_at(&path, local),
+        None => Ok(None),
+    }
+}
+
+pub fn update_archive_item(scope: ArchiveScope, position: usize, item: &Item) -> StorageResult<()> {
+    match locate_archive_position(scope, position)? {
+        Some((path, local)) => index::update_item_at(&path, local, item),
+        None => Err(StorageError::RecordNotFound {
+            path: get_archive_path()?,
+            position,
+        }),
+    }
+}
+
+pub fn remove_archive_item(scope: ArchiveScope, position: usize) -> StorageResult<Option<Item>> {
+    remove_archive_item_in(&get_folio_dir()?, scope, position)
+}
+
+fn remove_archive_item_in(
+    folio_dir: &Path,
+    scope: ArchiveScope,
+    position: usize,
+) -> StorageResult<Option<Item>> {
+    let Some((path, local)) = locate_archive_position_in(folio_dir, scope, position)? else {
+        return Ok(None);
+    };
+
+    let removed = index::remove_item_at(&path, local)?;
+    if path != folio_dir.join(ARCHIVE_FILE) && index::record_count(&path)? == 0 {
+        remove_segment_file(&path)?;
+    }
+    Ok(removed)
+}
+
+// Takes an archived item out for its trip back to the inbox, then archives the items that
+// overflowed the inbox to make room. Appending first would shift the positions of every item
+// in an older segment, since the recent archive comes first.
+pub fn reopen_archive_item(
+    scope: ArchiveScope,
+    position: usize,
+    overflow: &[Item],
+) -> StorageResult<Option<Item>> {
+    ensure_folio_dir()?;
+    reopen_archive_item_in(&get_folio_dir()?, scope, position, overflow)
+}
+
+fn reopen_archive_item_in(
+    folio_dir: &Path,
+    scope: ArchiveScope,
+    position: usize,
+    overflow: &[Item],
+) -> StorageResult<Option<Item>> {
+    let removed = remove_archive_item_in(folio_dir, scope, position)?;
+    for item in overflow {
+        append_item_to_file(item, folio_dir.join(ARCHIVE_FILE))?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use folio_core::create_item;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("folio-archive-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join(SEGMENTS_DIR)).unwrap();
+        dir
+    }
+
+    fn items(prefix: &str, count: usize) -> Vec<Item> {
+        (0..count)
+            .map(|i| {
+                create_item(
+                    format!("{} {}", prefix, i),
+                    None,
+                    None,
+                    Some(format!("https://example.com/{}/{}", prefix, i)),
+                    None,
+                    None,
+                )
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn names(items: &[Item]) -> Vec<&str> {
+        items.iter().map(|item| item.name.as_str()).collect()
+    }
+
+    #[test]
+    fn reopening_a_segment_item_removes_it_before_archiving_the_overflow() {
+        let dir = scratch_dir("reopen");
+        let recent = items("recent", 2);
+        let older = items("older", 2);
+        save_indexed_items_to_file(&recent, dir.join(ARCHIVE_FILE)).unwrap();
+        let segment = segment_path(&dir.join(SEGMENTS_DIR), 2023, false);
+        save_indexed_items_to_file(&older, &segment).unwrap();
+
+        // "older 1" is the fourth item of the whole archive.
+        let overflow = items("overflow", 2);
+        let removed = reopen_archive_item_in(&dir, ArchiveScope::All, 3, &overflow).unwrap();
+
+        assert_eq!(removed.unwrap().name, "older 1");
+        assert_eq!(names(&load_items_from_file(&segment).unwrap()), ["older 0"]);
+        assert_eq!(
+            names(&load_items_from_file(dir.join(ARCHIVE_FILE)).unwrap()),
+            ["recent 0", "recent 1", "overflow 0", "overflow 1"]
+        );
+    }
+
+    #[test]
+    fn reopening_the_last_item_of_a_segment_removes_the_segment() {
+        let dir = scratch_dir("reopen-last");
+        save_indexed_items_to_file(&items("recent", 1), dir.join(ARCHIVE_FILE)).unwrap();
+        let segment = segment_path(&dir.join(SEGMENTS_DIR), 2022, false);
+        save_indexed_items_to_file(&items("older", 1), &segment).unwrap();
+
+        let removed =
+            reopen_archive_item_in(&dir, ArchiveScope::All, 1, &items("overflow", 1)).unwrap();
+
+        assert_eq!(removed.unwrap().name, "older 0");
+        assert!(!segment.exists());
+        assert!(
+            list_segments_in(&dir.join(SEGMENTS_DIR))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn recent_scope_positions_ignore_segments() {
+        let dir = scratch_dir("recent-scope");
+        save_indexed_items_to_file(&items("recent", 1), dir.join(ARCHIVE_FILE)).unwrap();
+        let segment = segment_path(&dir.join(SEGMENTS_DIR), 2021, false);
+        save_indexed_items_to_file(&items("older", 1), &segment).unwrap();
+
+        assert!(
+            reopen_archive_item_in(&dir, ArchiveScope::Recent, 1, &[])
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(names(&load_items_from_file(&segment).unwrap()), ["older 0"]);
+    }
+}
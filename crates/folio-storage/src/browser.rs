@@ -0,0 +1,147 @@
+use crate::error::{StorageError, StorageResult};
+use crate::{ensure_folio_dir, fs_atomic, get_folio_dir};
+use chrono::DateTime;
+use folio_core::import::{Bookmark, BrowserBookmark, BrowserSyncState};
+use rusqlite::{Connection, OpenFlags};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const FIREFOX_TAGS_ROOT: &str = "tags________";
+
+struct PlacesRow {
+    id: i64,
+    parent: i64,
+    kind: i64,
+    title: String,
+    guid: String,
+    date_added: Option<i64>,
+    url: Option<String>,
+}
+
+fn database_error(path: &Path, error: rusqlite::Error) -> StorageError {
+    StorageError::Database {
+        path: path.to_path_buf(),
+        message: error.to_string(),
+    }
+}
+
+// Firefox keeps places.sqlite locked while it runs, so the database (and its write-ahead
+// log, which holds recent changes) is copied aside and the copy is opened read-only.
+fn snapshot_places(path: &Path) -> StorageResult<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("folio-places-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+
+    let copy = dir.join("places.sqlite");
+    fs::copy(path, &copy).map_err(|_| StorageError::FileRead {
+        path: path.to_path_buf(),
+    })?;
+
+    let mut wal = path.as_os_str().to_os_string();
+    wal.push("-wal");
+    if Path::new(&wal).exists() {
+        fs::copy(&wal, dir.join("places.sqlite-wal"))?;
+    }
+
+    Ok(copy)
+}
+
+fn read_places_rows(path: &Path) -> StorageResult<Vec<PlacesRow>> {
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| database_error(path, e))?;
+
+    let mut statement = connection
+        .prepare(
+            "SELECT b.id, b.parent, b.type, COALESCE(b.title, ''), b.guid, b.dateAdded, p.url
+             FROM moz_bookmarks b LEFT JOIN moz_places p ON p.id = b.fk
+             ORDER BY b.parent, b.position",
+        )
+        .map_err(|e| database_error(path, e))?;
+
+    statement
+        .query_map([], |row| {
+            Ok(PlacesRow {
+                id: row.get(0)?,
+                parent: row.get(1)?,
+                kind: row.get(2)?,
+                title: row.get(3)?,
+                guid: row.get(4)?,
+                date_added: row.get(5)?,
+                url: row.get(6)?,
+            })
+        })
+        .and_then(|rows| rows.collect())
+        .map_err(|e| database_error(path, e))
+}
+
+// Folder paths start below the built-in roots (menu, toolbar, other, mobile), and tag
+// folders, which Firefox also stores as bookmarks, are skipped.
+pub fn read_firefox_bookmarks(path: &Path) -> StorageResult<Vec<BrowserBookmark>> {
+    let copy = snapshot_places(path)?;
+    let rows = read_places_rows(&copy);
+    if let Some(dir) = copy.parent() {
+        let _ = fs::remove_dir_all(dir);
+    }
+    let rows = rows?;
+
+    let rows_by_id: HashMap<i64, &PlacesRow> = rows.iter().map(|row| (row.id, row)).collect();
+    let root_id = rows.iter().find(|row| row.parent == 0).map(|row| row.id);
+
+    let folder_path = |mut parent: i64| -> Option<Vec<String>> {
+        let mut folders = Vec::new();
+        while let Some(folder) = rows_by_id.get(&parent) {
+            if folder.guid == FIREFOX_TAGS_ROOT {
+                return None;
+            }
+            if Some(folder.parent) == root_id || folder.parent == 0 {
+                break;
+            }
+            folders.push(folder.title.clone());
+            parent = folder.parent;
+        }
+        folders.reverse();
+        Some(folders)
+    };
+
+    let bookmarks = rows
+        .iter()
+        .filter(|row| row.kind == 1)
+        .filter_map(|row| {
+            let url = row
+                .url
+                .as_ref()
+                .filter(|url| folio_core::is_supported_link(url))?;
+            Some(BrowserBookmark {
+                guid: row.guid.clone(),
+                bookmark: Bookmark {
+                    title: row.title.trim().to_string(),
+                    url: url.clone(),
+                    add_date: row.date_added.and_then(DateTime::from_timestamp_micros),
+                    folders: folder_path(row.parent)?,
+                },
+            })
+        })
+        .collect();
+
+    Ok(bookmarks)
+}
+
+pub fn get_browser_sync_path() -> StorageResult<PathBuf> {
+    Ok(get_folio_dir()?.join("browser_sync.json"))
+}
+
+pub fn load_browser_sync_state() -> StorageResult<BrowserSyncState> {
+    let path = get_browser_sync_path()?;
+    match fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).map_err(|_| StorageError::JsonDeserialization),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BrowserSyncState::default()),
+        Err(_) => Err(StorageError::FileRead { path }),
+    }
+}
+
+pub fn save_browser_sync_state(state: &BrowserSyncState) -> StorageResult<()> {
+    ensure_folio_dir()?;
+    let path = get_browser_sync_path()?;
+    let json = serde_json::to_string_pretty(state).map_err(|_| StorageError::JsonSerialization)?;
+    fs_atomic::atomic_write(&path, json.as_bytes()).map_err(|_| StorageError::FileWrite { path })
+}
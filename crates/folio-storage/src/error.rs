@@ -15,6 +15,12 @@ pub enum StorageError {
     #[error("Failed to write file: {path}")]
     FileWrite { path: PathBuf },
 
+    #[error("No record at position {position} in {path}")]
+    RecordNotFound { path: PathBuf, position: usize },
+
+    #[error("Failed to read database {path}: {message}")]
+    Database { path: PathBuf, message: String },
+
     #[error("Failed to parse JSONL data")]
     JsonlParse,
 
@@ -27,6 +33,9 @@ pub enum StorageError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(#[from] folio_core::ConfigError),
+
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 }
@@ -0,0 +1,502 @@
+use crate::{StorageError, StorageResult, fs_atomic, load_items_from_file};
+use flate2::Crc;
+use flate2::read::GzDecoder;
+use folio_core::Item;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+const MAGIC: &[u8; 8] = b"FOLIOIX1";
+const HEADER_LEN: u64 = 16;
+const ENTRY_LEN: u64 = 12;
+
+const JOURNAL_MAGIC: &[u8; 8] = b"FOLIOJ01";
+const JOURNAL_HEADER_LEN: usize = 32;
+
+// Every record of an indexed (archive) file is padded so that small edits (status, kind, a few characters of a note)
+// can be written back into the same slot without moving the rest of the file.
+const RECORD_SLACK: usize = 48;
+const RECORD_ALIGN: usize = 64;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RecordSlot {
+    pub offset: u64,
+    pub len: u32,
+}
+
+fn pad_record(mut json: String, slot_len: usize) -> String {
+    let padding = slot_len.saturating_sub(json.len());
+    json.extend(std::iter::repeat_n(' ', padding));
+    json
+}
+
+pub fn encode_record(item: &Item) -> StorageResult<String> {
+    let json = serde_json::to_string(item).map_err(|_| StorageError::JsonSerialization)?;
+    let slot_len = (json.len() + RECORD_SLACK).next_multiple_of(RECORD_ALIGN);
+    Ok(pad_record(json, slot_len))
+}
+
+pub fn encode_records(items: &[Item]) -> StorageResult<(String, Vec<RecordSlot>)> {
+    let mut jsonl = String::new();
+    let mut slots = Vec::with_capacity(items.len());
+
+    for item in items {
+        let record = encode_record(item)?;
+        slots.push(RecordSlot {
+            offset: jsonl.len() as u64,
+            len: record.len() as u32,
+        });
+        jsonl.push_str(&record);
+        jsonl.push('\n');
+    }
+
+    Ok((jsonl, slots))
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut sibling = path.as_os_str().to_os_string();
+    sibling.push(suffix);
+    PathBuf::from(sibling)
+}
+
+pub fn index_path(path: &Path) -> PathBuf {
+    sibling_path(path, ".idx")
+}
+
+pub fn journal_path(path: &Path) -> PathBuf {
+    sibling_path(path, ".journal")
+}
+
+fn checksum(bytes: &[u8]) -> u32 {
+    let mut crc = Crc::new();
+    crc.update(bytes);
+    crc.sum()
+}
+
+// An in-place write is first saved whole to a journal next to the data file, and the journal
+// is only removed once the write has reached the disk. A crash in between leaves the journal
+// behind, and the next access replays it over the half-written record.
+fn write_journal(path: &Path, data_len: u64, slot: RecordSlot, record: &[u8]) -> StorageResult<()> {
+    let mut journal = Vec::with_capacity(JOURNAL_HEADER_LEN + record.len());
+    journal.extend_from_slice(JOURNAL_MAGIC);
+    journal.extend_from_slice(&data_len.to_le_bytes());
+    journal.extend_from_slice(&slot.offset.to_le_bytes());
+    journal.extend_from_slice(&slot.len.to_le_bytes());
+    journal.extend_from_slice(&checksum(record).to_le_bytes());
+    journal.extend_from_slice(record);
+
+    let journal_path = journal_path(path);
+    fs_atomic::atomic_write(&journal_path, &journal)
+        .map_err(|_| StorageError::FileWrite { path: journal_path })
+}
+
+fn write_slot(path: &Path, offset: u64, record: &[u8]) -> StorageResult<()> {
+    OpenOptions::new()
+        .write(true)
+        .open(path)
+        .and_then(|mut data| {
+            data.seek(SeekFrom::Start(offset))?;
+            data.write_all(record)?;
+            data.sync_data()
+        })
+        .map_err(|_| StorageError::FileWrite {
+            path: path.to_path_buf(),
+        })
+}
+
+// Finishes an in-place write that was cut short. A journal that doesn't check out, or that
+// was written for a file of another length, no longer describes this file and is dropped.
+pub(crate) fn recover(path: &Path) -> StorageResult<()> {
+    let journal_path = journal_path(path);
+    let journal = match std::fs::read(&journal_path) {
+        Ok(journal) => journal,
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(_) => return Err(StorageError::FileRead { path: journal_path }),
+    };
+
+    if journal.len() >= JOURNAL_HEADER_LEN && &journal[..8] == JOURNAL_MAGIC {
+        let data_len = u64::from_le_bytes(journal[8..16].try_into().unwrap());
+        let offset = u64::from_le_bytes(journal[16..24].try_into().unwrap());
+        let len = u32::from_le_bytes(journal[24..28].try_into().unwrap()) as usize;
+        let sum = u32::from_le_bytes(journal[28..32].try_into().unwrap());
+        let record = &journal[JOURNAL_HEADER_LEN..];
+
+        let current_len = path.metadata().map(|metadata| metadata.len()).ok();
+        if record.len() == len && checksum(record) == sum && current_len == Some(data_len) {
+            write_slot(path, offset, record)?;
+        }
+    }
+
+    std::fs::remove_file(&journal_path).map_err(|_| StorageError::FileWrite { path: journal_path })
+}
+
+pub(crate) fn is_compressed(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "gz")
+}
+
+pub fn write_index(path: &Path, data_len: u64, slots: &[RecordSlot]) -> StorageResult<()> {
+    let mut buffer = Vec::with_capacity(HEADER_LEN as usize + slots.len() * ENTRY_LEN as usize);
+    buffer.extend_from_slice(MAGIC);
+    buffer.extend_from_slice(&data_len.to_le_bytes());
+
+    for slot in slots {
+        buffer.extend_from_slice(&slot.offset.to_le_bytes());
+        buffer.extend_from_slice(&slot.len.to_le_bytes());
+    }
+
+    let index_path = index_path(path);
+    fs_atomic::atomic_write(&index_path, &buffer)
+        .map_err(|_| StorageError::FileWrite { path: index_path })
+}
+
+pub fn rebuild_index(path: &Path) -> StorageResult<()> {
+    let file = File::open(path).map_err(|_| StorageError::FileRead {
+        path: path.to_path_buf(),
+    })?;
+    let data_len = file.metadata()?.len();
+
+    let mut reader: Box<dyn BufRead> = if is_compressed(path) {
+        Box::new(BufReader::new(GzDecoder::new(file)))
+    } else {
+        Box::new(BufReader::new(file))
+    };
+
+    let mut slots = Vec::new();
+    let mut offset = 0u64;
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line)?;
+        if read == 0 {
+            break;
+        }
+
+        let content_len = if line.last() == Some(&b'\n') {
+            read - 1
+        } else {
+            read
+        };
+
+        if !line[..content_len].iter().all(u8::is_ascii_whitespace) {
+            slots.push(RecordSlot {
+                offset,
+                len: content_len as u32,
+            });
+        }
+
+        offset += read as u64;
+    }
+
+    write_index(path, data_len, &slots)
+}
+
+fn open_index(path: &Path) -> StorageResult<Option<File>> {
+    let data_len = match path.metadata() {
+        Ok(metadata) => metadata.len(),
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(_) => {
+            return Err(StorageError::FileRead {
+                path: path.to_path_buf(),
+            });
+        }
+    };
+
+    if let Ok(mut index) = File::open(index_path(path)) {
+        let mut header = [0u8; HEADER_LEN as usize];
+        if index.read_exact(&mut header).is_ok()
+            && &header[..8] == MAGIC
+            && u64::from_le_bytes(header[8..].try_into().unwrap()) == data_len
+        {
+            return Ok(Some(index));
+        }
+    }
+
+    rebuild_index(path)?;
+    Ok(Some(File::open(index_path(path))?))
+}
+
+fn slot_at(index: &mut File, position: usize) -> StorageResult<Option<RecordSlot>> {
+    let count = (index.metadata()?.len().saturating_sub(HEADER_LEN)) / ENTRY_LEN;
+    if position as u64 >= count {
+        return Ok(None);
+    }
+
+    let mut entry = [0u8; ENTRY_LEN as usize];
+    index.seek(SeekFrom::Start(HEADER_LEN + position as u64 * ENTRY_LEN))?;
+    index.read_exact(&mut entry)?;
+
+    Ok(Some(RecordSlot {
+        offset: u64::from_le_bytes(entry[..8].try_into().unwrap()),
+        len: u32::from_le_bytes(entry[8..].try_into().unwrap()),
+    }))
+}
+
+pub fn record_count(path: &Path) -> StorageResult<usize> {
+    match open_index(path)? {
+        Some(index) => {
+            Ok(((index.metadata()?.len().saturating_sub(HEADER_LEN)) / ENTRY_LEN) as usize)
+        }
+        None => Ok(0),
+    }
+}
+
+fn read_slot(data: &mut File, slot: RecordSlot) -> StorageResult<Option<Item>> {
+    let mut buffer = vec![0u8; slot.len as usize + 1];
+    data.seek(SeekFrom::Start(slot.offset))?;
+    if data.read_exact(&mut buffer).is_err() || buffer.last() != Some(&b'\n') {
+        return Ok(None);
+    }
+
+    Ok(serde_json::from_slice(&buffer[..slot.len as usize]).ok())
+}
+
+fn locate(path: &Path, position: usize) -> StorageResult<Option<(u64, RecordSlot, Item)>> {
+    recover(path)?;
+
+    for attempt in 0..2 {
+        let Some(mut index) = open_index(path)? else {
+            return Ok(None);
+        };
+        let Some(slot) = slot_at(&mut index, position)? else {
+            return Ok(None);
+        };
+
+        let mut data = File::open(path).map_err(|_| StorageError::FileRead {
+            path: path.to_path_buf(),
+        })?;
+
+        if let Some(item) = read_slot(&mut data, slot)? {
+            return Ok(Some((data.metadata()?.len(), slot, item)));
+        }
+
+        // The file was changed behind the index's back without changing its length.
+        if attempt == 0 {
+            rebuild_index(path)?;
+        }
+    }
+
+    Err(StorageError::JsonlParse)
+}
+
+pub fn read_item_at(path: &Path, position: usize) -> StorageResult<Option<Item>> {
+    if is_compressed(path) {
+        return Ok(load_items_from_file(path)?.into_iter().nth(position));
+    }
+
+    Ok(locate(path, position)?.map(|(_, _, item)| item))
+}
+
+pub fn update_item_in_place(path: &Path, position: usize, item: &Item) -> StorageResult<bool> {
+    if is_compressed(path) {
+        return Ok(false);
+    }
+
+    let Some((data_len, slot, _)) = locate(path, position)? else {
+        return Err(StorageError::RecordNotFound {
+            path: path.to_path_buf(),
+            position,
+        });
+    };
+
+    let json = serde_json::to_string(item).map_err(|_| StorageError::JsonSerialization)?;
+    if json.len() > slot.len as usize {
+        return Ok(false);
+    }
+
+    let record = pad_record(json, slot.len as usize);
+    write_journal(path, data_len, slot, record.as_bytes())?;
+    write_slot(path, slot.offset, record.as_bytes())?;
+
+    let journal_path = journal_path(path);
+    std::fs::remove_file(&journal_path)
+        .map_err(|_| StorageError::FileWrite { path: journal_path })?;
+    Ok(true)
+}
+
+pub fn update_item_at(path: &Path, position: usize, item: &Item) -> StorageResult<()> {
+    if update_item_in_place(path, position, item)? {
+        return Ok(());
+    }
+
+    let mut items = load_items_from_file(path)?;
+
+    match items.get_mut(position) {
+        Some(slot) => *slot = item.clone(),
+        None => {
+            return Err(StorageError::RecordNotFound {
+                path: path.to_path_buf(),
+                position,
+            });
+        }
+    }
+
+    crate::save_indexed_items_to_file(&items, path)
+}
+
+pub fn remove_item_at(path: &Path, position: usize) -> StorageResult<Option<Item>> {
+    let mut items = load_items_from_file(path)?;
+
+    if position >= items.len() {
+        return Ok(None);
+    }
+
+    let removed = items.remove(position);
+    crate::save_indexed_items_to_file(&items, path)?;
+    Ok(Some(removed))
+}
+
+pub(crate) fn record_appended(
+    path: &Path,
+    slot: RecordSlot,
+    previous_len: u64,
+) -> StorageResult<()> {
+    let index_path = index_path(path);
+
+    let Ok(mut index) = OpenOptions::new().read(true).write(true).open(&index_path) else {
+        return Ok(());
+    };
+
+    let mut header = [0u8; HEADER_LEN as usize];
+    if index.read_exact(&mut header).is_err()
+        || &header[..8] != MAGIC
+        || u64::from_le_bytes(header[8..].try_into().unwrap()) != previous_len
+    {
+        // Stale already; it will be rebuilt on the next indexed access.
+        return Ok(());
+    }
+
+    let data_len = slot.offset + slot.len as u64 + 1;
+    index.seek(SeekFrom::End(0))?;
+    index.write_all(&slot.offset.to_le_bytes())?;
+    index.write_all(&slot.len.to_le_bytes())?;
+    index.seek(SeekFrom::Start(8))?;
+    index.write_all(&data_len.to_le_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{save_indexed_items_to_file, save_items_to_file};
+    use folio_core::{Kind, create_item};
+
+    fn scratch_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("folio-index-{}-{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("archive.jsonl")
+    }
+
+    fn items(count: usize) -> Vec<Item> {
+        (0..count)
+            .map(|i| {
+                create_item(
+                    format!("Item {}", i),
+                    None,
+                    None,
+                    Some(format!("https://example.com/{}", i)),
+                    None,
+                    None,
+                )
+                .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn updates_a_record_in_place() {
+        let path = scratch_file("in-place");
+        let mut all = items(3);
+        save_indexed_items_to_file(&all, &path).unwrap();
+        let len = path.metadata().unwrap().len();
+
+        all[1].kind = Kind::Reference;
+        assert!(update_item_in_place(&path, 1, &all[1]).unwrap());
+
+        assert_eq!(path.metadata().unwrap().len(), len);
+        assert!(!journal_path(&path).exists());
+        assert_eq!(load_items_from_file(&path).unwrap(), all);
+        assert_eq!(read_item_at(&path, 1).unwrap(), Some(all[1].clone()));
+    }
+
+    #[test]
+    fn records_that_outgrow_their_slot_rewrite_the_file() {
+        let path = scratch_file("outgrow");
+        let mut all = items(3);
+        save_indexed_items_to_file(&all, &path).unwrap();
+
+        all[0].note = "a much longer note ".repeat(20);
+        assert!(!update_item_in_place(&path, 0, &all[0]).unwrap());
+        update_item_at(&path, 0, &all[0]).unwrap();
+
+        assert_eq!(load_items_from_file(&path).unwrap(), all);
+        assert_eq!(record_count(&path).unwrap(), 3);
+    }
+
+    #[test]
+    fn replays_a_journal_left_by_an_interrupted_write() {
+        let path = scratch_file("replay");
+        let mut all = items(3);
+        save_indexed_items_to_file(&all, &path).unwrap();
+        let (data_len, slot, _) = locate(&path, 2).unwrap().unwrap();
+
+        // The journal made it to disk, the record itself only halfway.
+        all[2].kind = Kind::Reference;
+        let record = pad_record(serde_json::to_string(&all[2]).unwrap(), slot.len as usize);
+        write_journal(&path, data_len, slot, record.as_bytes()).unwrap();
+        write_slot(&path, slot.offset, &record.as_bytes()[..record.len() / 2]).unwrap();
+
+        assert_eq!(load_items_from_file(&path).unwrap(), all);
+        assert!(!journal_path(&path).exists());
+    }
+
+    #[test]
+    fn drops_a_journal_that_does_not_check_out() {
+        let path = scratch_file("torn");
+        let all = items(2);
+        save_indexed_items_to_file(&all, &path).unwrap();
+        let (data_len, slot, _) = locate(&path, 0).unwrap().unwrap();
+
+        let record = pad_record(serde_json::to_string(&all[1]).unwrap(), slot.len as usize);
+        write_journal(&path, data_len, slot, record.as_bytes()).unwrap();
+        let mut journal = std::fs::read(journal_path(&path)).unwrap();
+        journal.truncate(journal.len() - 5);
+        std::fs::write(journal_path(&path), journal).unwrap();
+
+        assert_eq!(load_items_from_file(&path).unwrap(), all);
+        assert!(!journal_path(&path).exists());
+    }
+
+    #[test]
+    fn rebuilds_a_stale_index() {
+        let path = scratch_file("stale");
+        let all = items(4);
+        save_indexed_items_to_file(&all[..2], &path).unwrap();
+        std::fs::write(index_path(&path), b"garbage").unwrap();
+
+        assert_eq!(record_count(&path).unwrap(), 2);
+        crate::append_item_to_file(&all[2], &path).unwrap();
+        crate::append_item_to_file(&all[3], &path).unwrap();
+        assert_eq!(record_count(&path).unwrap(), 4);
+        assert_eq!(read_item_at(&path, 3).unwrap(), Some(all[3].clone()));
+    }
+
+    #[test]
+    fn only_indexed_files_are_padded() {
+        let plain = scratch_file("plain");
+        let indexed = plain.with_file_name("indexed.jsonl");
+        let all = items(2);
+
+        save_items_to_file(&all, &plain).unwrap();
+        save_indexed_items_to_file(&all, &indexed).unwrap();
+
+        let contents = std::fs::read_to_string(&plain).unwrap();
+        assert!(contents.lines().all(|line| !line.ends_with(' ')));
+        assert_eq!(contents, crate::serialize_items_to_jsonl(&all).unwrap());
+        assert!(!index_path(&plain).exists());
+
+        let padded = std::fs::read_to_string(&indexed).unwrap();
+        assert!(padded.lines().all(|line| line.ends_with(' ')));
+        assert_eq!(load_items_from_file(&indexed).unwrap(), all);
+    }
+}
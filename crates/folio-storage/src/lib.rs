@@ -1,12 +1,32 @@
-use folio_core::{Config, Item};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use folio_core::{CONFIG_SCHEMA, Config, ConfigError, Item};
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Read, Write};
 use std::path::{Path, PathBuf};
 
+pub mod archive;
+pub mod browser;
 pub mod error;
 pub mod fs_atomic;
-
+pub mod index;
+pub mod snapshot;
+
+pub use archive::{
+    ArchiveScope, ArchiveSegment, CompactReport, archive_len, compact_archive,
+    list_archive_segments, load_archive, read_archive_item, remove_archive_item,
+    reopen_archive_item, save_archive_scoped, update_archive_item,
+};
+pub use browser::{
+    get_browser_sync_path, load_browser_sync_state, read_firefox_bookmarks, save_browser_sync_state,
+};
 pub use error::{StorageError, StorageResult};
+pub use index::{read_item_at, remove_item_at, update_item_at};
+pub use snapshot::{
+    get_snapshot_path, get_snapshots_dir, has_snapshot, load_snapshot, load_snapshot_from,
+    remove_snapshot, save_snapshot, save_snapshot_in,
+};
 
 pub fn deserialize_jsonl_from_string(jsonl_str: &str) -> StorageResult<Vec<Item>> {
     let mut items = Vec::new();
@@ -43,7 +63,14 @@ pub fn deserialize_jsonl_from_reader<R: Read>(reader: R) -> StorageResult<Vec<It
 
 pub fn load_items_from_file<P: AsRef<Path>>(path: P) -> StorageResult<Vec<Item>> {
     let path_ref = path.as_ref();
+    if !index::is_compressed(path_ref) {
+        index::recover(path_ref)?;
+    }
+
     match File::open(path_ref) {
+        Ok(file) if index::is_compressed(path_ref) => {
+            deserialize_jsonl_from_reader(GzDecoder::new(file))
+        }
         Ok(file) => {
             let items = deserialize_jsonl_from_reader(file)?;
             Ok(items)
@@ -100,57 +127,155 @@ pub fn get_archive_path() -> StorageResult<PathBuf> {
     Ok(archive_path)
 }
 
+pub fn get_someday_path() -> StorageResult<PathBuf> {
+    let someday_path = get_folio_dir()?.join("someday.jsonl");
+    Ok(someday_path)
+}
+
+pub fn get_staging_path() -> StorageResult<PathBuf> {
+    let staging_path = get_folio_dir()?.join("staging.jsonl");
+    Ok(staging_path)
+}
+
 pub fn get_config_path() -> StorageResult<PathBuf> {
     let config_path = get_folio_dir()?.join("config.json");
     Ok(config_path)
 }
 
-fn save_items(items: &[Item], path: PathBuf) -> StorageResult<()> {
-    let jsonl = serialize_items_to_jsonl(items)?;
-    fs_atomic::atomic_write(&path, jsonl.as_bytes())
-        .map_err(|_| StorageError::FileWrite { path })?;
+fn write_jsonl(path: &Path, jsonl: String) -> StorageResult<u64> {
+    let data = if index::is_compressed(path) {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(jsonl.as_bytes())?;
+        encoder.finish()?
+    } else {
+        jsonl.into_bytes()
+    };
+
+    fs_atomic::atomic_write(path, &data).map_err(|_| StorageError::FileWrite {
+        path: path.to_path_buf(),
+    })?;
+    Ok(data.len() as u64)
+}
+
+pub fn save_items_to_file<P: AsRef<Path>>(items: &[Item], path: P) -> StorageResult<()> {
+    let path = path.as_ref();
+    write_jsonl(path, serialize_items_to_jsonl(items)?)?;
     Ok(())
 }
 
+// Archive files are written padded and with a record index, so single items can be read and
+// changed without loading or rewriting the whole file.
+pub fn save_indexed_items_to_file<P: AsRef<Path>>(items: &[Item], path: P) -> StorageResult<()> {
+    let path = path.as_ref();
+    if !index::is_compressed(path) {
+        index::recover(path)?;
+    }
+
+    let (jsonl, slots) = index::encode_records(items)?;
+    let data_len = write_jsonl(path, jsonl)?;
+    index::write_index(path, data_len, &slots)
+}
+
+fn replace_item_in_file(path: &Path, position: usize, item: &Item) -> StorageResult<()> {
+    let mut items = load_items_from_file(path)?;
+
+    match items.get_mut(position) {
+        Some(slot) => *slot = item.clone(),
+        None => {
+            return Err(StorageError::RecordNotFound {
+                path: path.to_path_buf(),
+                position,
+            });
+        }
+    }
+
+    save_items_to_file(&items, path)
+}
+
 pub fn save_inbox(items: &[Item]) -> StorageResult<()> {
     ensure_folio_dir()?;
-    save_items(items, get_inbox_path()?)
+    save_items_to_file(items, get_inbox_path()?)
 }
 
-pub fn save_archive(items: &[Item]) -> StorageResult<()> {
+pub fn update_inbox_item(position: usize, item: &Item) -> StorageResult<()> {
+    replace_item_in_file(&get_inbox_path()?, position, item)
+}
+
+pub fn load_someday() -> StorageResult<Vec<Item>> {
+    load_items_from_file(get_someday_path()?)
+}
+
+pub fn save_someday(items: &[Item]) -> StorageResult<()> {
     ensure_folio_dir()?;
-    save_items(items, get_archive_path()?)
+    save_items_to_file(items, get_someday_path()?)
+}
+
+pub fn update_someday_item(position: usize, item: &Item) -> StorageResult<()> {
+    replace_item_in_file(&get_someday_path()?, position, item)
+}
+
+pub fn load_staging() -> StorageResult<Vec<Item>> {
+    load_items_from_file(get_staging_path()?)
+}
+
+pub fn save_staging(items: &[Item]) -> StorageResult<()> {
+    ensure_folio_dir()?;
+    save_items_to_file(items, get_staging_path()?)
+}
+
+pub fn save_archive(items: &[Item]) -> StorageResult<()> {
+    save_archive_scoped(items, ArchiveScope::All)
 }
 
 pub fn append_to_archive(item: &Item) -> StorageResult<()> {
     ensure_folio_dir()?;
-    let archive_path = get_archive_path()?;
-    let json_line = serde_json::to_string(item).map_err(|_| StorageError::JsonSerialization)?;
+    append_item_to_file(item, get_archive_path()?)
+}
+
+pub fn append_item_to_file<P: AsRef<Path>>(item: &Item, path: P) -> StorageResult<()> {
+    let path = path.as_ref();
+    index::recover(path)?;
+    let record = index::encode_record(item)?;
 
     let mut file = OpenOptions::new()
         .create(true)
         .append(true)
-        .open(&archive_path)
+        .open(path)
         .map_err(|_| StorageError::FileWrite {
-            path: archive_path.clone(),
+            path: path.to_path_buf(),
         })?;
 
-    writeln!(file, "{}", json_line).map_err(|_| StorageError::FileWrite { path: archive_path })?;
-    Ok(())
+    let previous_len = file.metadata()?.len();
+    writeln!(file, "{}", record).map_err(|_| StorageError::FileWrite {
+        path: path.to_path_buf(),
+    })?;
+
+    let slot = index::RecordSlot {
+        offset: previous_len,
+        len: record.len() as u32,
+    };
+    index::record_appended(path, slot, previous_len)
 }
 
 pub fn load_config() -> StorageResult<Config> {
+    match read_config_file()? {
+        Some(value) => serde_json::from_value(value).map_err(|_| StorageError::JsonDeserialization),
+        None => Ok(Config::default()),
+    }
+}
+
+fn read_config_file() -> StorageResult<Option<serde_json::Value>> {
     let config_path = get_config_path()?;
 
     if config_path.exists() {
         let file = File::open(&config_path).map_err(|_| StorageError::FileRead {
             path: config_path.clone(),
         })?;
-        let config: Config =
+        let value: serde_json::Value =
             serde_json::from_reader(file).map_err(|_| StorageError::JsonDeserialization)?;
-        Ok(config)
+        Ok(Some(value))
     } else {
-        Ok(Config::default())
+        Ok(None)
     }
 }
 
@@ -163,29 +288,141 @@ pub fn save_config(config: &Config) -> StorageResult<()> {
     Ok(())
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    Default,
+    File,
+    Env,
+    Flag,
+}
+
+impl ConfigSource {
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            ConfigSource::Default => "default",
+            ConfigSource::File => "file",
+            ConfigSource::Env => "env",
+            ConfigSource::Flag => "flag",
+        }
+    }
+}
+
 pub struct ConfigManager {
+    file_config: Config,
+    file_keys: Vec<String>,
+    overrides: Vec<(String, String)>,
     config: Config,
 }
 
 impl ConfigManager {
     pub fn new() -> StorageResult<Self> {
-        let config = load_config()?;
-        Ok(Self { config })
+        Self::with_overrides(Vec::new())
+    }
+
+    pub fn with_overrides(overrides: Vec<(String, String)>) -> StorageResult<Self> {
+        let (file_config, file_keys) = match read_config_file()? {
+            Some(value) => {
+                let keys = value
+                    .as_object()
+                    .map(|object| object.keys().cloned().collect())
+                    .unwrap_or_default();
+                let config: Config =
+                    serde_json::from_value(value).map_err(|_| StorageError::JsonDeserialization)?;
+                (config, keys)
+            }
+            None => (Config::default(), Vec::new()),
+        };
+
+        let mut manager = Self {
+            config: file_config.clone(),
+            file_config,
+            file_keys,
+            overrides,
+        };
+        manager.resolve()?;
+        Ok(manager)
+    }
+
+    fn resolve(&mut self) -> StorageResult<()> {
+        let mut config = self.file_config.clone();
+
+        for field in CONFIG_SCHEMA {
+            if let Ok(value) = std::env::var(field.env_var()) {
+                config
+                    .set_value(field.key, &value)
+                    .map_err(|_| ConfigError::InvalidValue {
+                        key: field.env_var(),
+                        value: value.clone(),
+                        expected: field.kind.expected(),
+                    })?;
+            }
+        }
+
+        for (key, value) in &self.overrides {
+            config.set_value(key, value)?;
+        }
+
+        config.validate()?;
+        self.config = config;
+        Ok(())
     }
 
     pub fn get(&self) -> &Config {
         &self.config
     }
 
+    pub fn file_config(&self) -> &Config {
+        &self.file_config
+    }
+
+    pub fn overrides(&self) -> &[(String, String)] {
+        &self.overrides
+    }
+
+    pub fn source(&self, key: &str) -> ConfigSource {
+        let env_set = CONFIG_SCHEMA
+            .iter()
+            .find(|field| field.key == key)
+            .is_some_and(|field| std::env::var(field.env_var()).is_ok());
+
+        if self.overrides.iter().any(|(k, _)| k == key) {
+            ConfigSource::Flag
+        } else if env_set {
+            ConfigSource::Env
+        } else if self.file_keys.iter().any(|k| k == key) {
+            ConfigSource::File
+        } else {
+            ConfigSource::Default
+        }
+    }
+
     pub fn save(&self) -> StorageResult<()> {
-        save_config(&self.config)
+        save_config(&self.file_config)
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) -> StorageResult<()> {
+        let mut candidate = self.file_config.clone();
+        candidate.set_value(key, value)?;
+        self.commit(candidate)
     }
 
     pub fn update<F>(&mut self, updater: F) -> StorageResult<()>
     where
         F: FnOnce(&mut Config),
     {
-        updater(&mut self.config);
-        self.save()
+        let mut candidate = self.file_config.clone();
+        updater(&mut candidate);
+        self.commit(candidate)
+    }
+
+    fn commit(&mut self, candidate: Config) -> StorageResult<()> {
+        candidate.validate()?;
+        self.file_config = candidate;
+        self.file_keys = CONFIG_SCHEMA
+            .iter()
+            .map(|field| field.key.to_string())
+            .collect();
+        self.save()?;
+        self.resolve()
     }
 }
@@ -0,0 +1,153 @@
+use crate::error::{StorageError, StorageResult};
+use crate::{fs_atomic, get_folio_dir};
+use folio_core::{Item, Snapshot};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn get_snapshots_dir() -> StorageResult<PathBuf> {
+    Ok(get_folio_dir()?.join("snapshots"))
+}
+
+// Keyed by the item's id, which stays the same as it moves between inbox, someday and archive.
+fn snapshot_path_in(dir: &Path, item: &Item) -> PathBuf {
+    dir.join(format!("{}.json", item.id()))
+}
+
+pub fn get_snapshot_path(item: &Item) -> StorageResult<PathBuf> {
+    Ok(snapshot_path_in(&get_snapshots_dir()?, item))
+}
+
+pub fn has_snapshot(item: &Item) -> bool {
+    get_snapshot_path(item).is_ok_and(|path| path.exists())
+}
+
+pub fn load_snapshot(item: &Item) -> StorageResult<Option<Snapshot>> {
+    load_snapshot_from(&get_snapshots_dir()?, item)
+}
+
+pub fn load_snapshot_from(dir: &Path, item: &Item) -> StorageResult<Option<Snapshot>> {
+    let path = snapshot_path_in(dir, item);
+    match fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|_| StorageError::JsonDeserialization),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(_) => Err(StorageError::FileRead { path }),
+    }
+}
+
+pub fn save_snapshot(item: &Item, snapshot: &Snapshot) -> StorageResult<PathBuf> {
+    save_snapshot_in(&get_snapshots_dir()?, item, snapshot)
+}
+
+pub fn save_snapshot_in(dir: &Path, item: &Item, snapshot: &Snapshot) -> StorageResult<PathBuf> {
+    fs::create_dir_all(dir).map_err(|_| StorageError::DirectoryCreation {
+        path: dir.to_path_buf(),
+    })?;
+
+    let path = snapshot_path_in(dir, item);
+    let json =
+        serde_json::to_string_pretty(snapshot).map_err(|_| StorageError::JsonSerialization)?;
+    fs_atomic::atomic_write(&path, json.as_bytes())
+        .map_err(|_| StorageError::FileWrite { path: path.clone() })?;
+    Ok(path)
+}
+
+pub fn remove_snapshot(item: &Item) -> StorageResult<()> {
+    let path = get_snapshot_path(item)?;
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(_) => Err(StorageError::FileWrite { path }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use folio_core::{Article, Status, change_item_status, create_item};
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("folio-snapshots-{}-{}", std::process::id(), name))
+    }
+
+    fn item(name: &str) -> Item {
+        create_item(
+            name.to_string(),
+            None,
+            None,
+            Some("https://example.com/tides".to_string()),
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    fn snapshot(text: &str) -> Snapshot {
+        Snapshot::new(
+            "https://example.com/tides".to_string(),
+            Article {
+                title: Some("How Tides Work".to_string()),
+                byline: Some("Marina Reyes".to_string()),
+                text: text.to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn saves_and_loads_a_snapshot() {
+        let dir = scratch_dir("round-trip");
+        let item = item("How Tides Work");
+        let saved = snapshot("The tides are caused by the moon.\n\n## Spring tides\n\n- larger");
+
+        let path = save_snapshot_in(&dir, &item, &saved).unwrap();
+        assert_eq!(path, dir.join(format!("{}.json", item.id())));
+
+        let loaded = load_snapshot_from(&dir, &item).unwrap().unwrap();
+        assert_eq!(loaded.url, saved.url);
+        assert_eq!(loaded.title, saved.title);
+        assert_eq!(loaded.byline, saved.byline);
+        assert_eq!(loaded.fetched_at, saved.fetched_at);
+        assert_eq!(loaded.text, saved.text);
+        assert_eq!(loaded.word_count(), 12);
+    }
+
+    #[test]
+    fn follows_the_item_through_status_changes_and_edits() {
+        let dir = scratch_dir("same-item");
+        let mut item = item("Before");
+        save_snapshot_in(&dir, &item, &snapshot("kept")).unwrap();
+
+        item.name = "After".to_string();
+        change_item_status(&mut item, Status::Done);
+
+        let loaded = load_snapshot_from(&dir, &item).unwrap().unwrap();
+        assert_eq!(loaded.text, "kept");
+    }
+
+    #[test]
+    fn a_new_snapshot_replaces_the_old_one() {
+        let dir = scratch_dir("replace");
+        let item = item("Tides");
+        save_snapshot_in(&dir, &item, &snapshot("first")).unwrap();
+        save_snapshot_in(&dir, &item, &snapshot("second, with ünïcödé")).unwrap();
+
+        let loaded = load_snapshot_from(&dir, &item).unwrap().unwrap();
+        assert_eq!(loaded.text, "second, with ünïcödé");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn missing_and_damaged_snapshots() {
+        let dir = scratch_dir("missing");
+        let item = item("Tides");
+        assert!(load_snapshot_from(&dir, &item).unwrap().is_none());
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(snapshot_path_in(&dir, &item), "{not json").unwrap();
+        assert!(matches!(
+            load_snapshot_from(&dir, &item),
+            Err(StorageError::JsonDeserialization)
+        ));
+    }
+}
@@ -1,26 +1,47 @@
 use crate::error::TuiResult;
 use crate::event::{AppEvent, EventHandler};
-use crate::forms::{FormType, ItemForm};
+use crate::forms::{AUTO_TYPE, FormType, ItemForm};
 use crate::state::{AppState, View};
 use crate::terminal::{restore_terminal, setup_terminal};
-use crate::widgets::ItemsTable;
+use crate::widgets::{ItemDetails, ItemsTable, SnapshotReader};
 use crossterm::event::{KeyCode, KeyEvent};
-use folio_core::{Item, OverflowStrategy};
+use folio_core::{
+    Config, CoreError, Duplicate, DuplicateLocation, Item, ItemType, OverflowStrategy, Snapshot,
+    Status, TimeTotal, TypeRules, ValidationErrors, estimate_minutes, extract_article,
+    parse_item_type_field, parse_minutes_field, parse_page_metadata,
+};
+use folio_fetch::{FetchError, Fetcher, Page};
 use folio_storage::{
-    ConfigManager, get_archive_path, get_inbox_path, load_items_from_file, save_archive, save_inbox,
+    ArchiveScope, ConfigManager, append_to_archive, get_inbox_path, list_archive_segments,
+    load_archive, load_items_from_file, load_snapshot, load_someday, remove_snapshot,
+    reopen_archive_item, save_archive_scoped, save_inbox, save_snapshot, save_someday,
+    update_archive_item, update_inbox_item, update_someday_item,
 };
 use ratatui::widgets::TableState;
-use std::str::FromStr;
 use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+pub struct ReaderView {
+    pub snapshot: Snapshot,
+    pub scroll: usize,
+}
+
+pub struct FetchedSnapshot {
+    pub snapshot: Snapshot,
+    pub duration_seconds: Option<u64>,
+}
 
 pub struct App {
     pub should_quit: bool,
     pub state: AppState,
+    pub config: Config,
+    pub config_overrides: Vec<(String, String)>,
     pub table_state: TableState,
     pub add_form: ItemForm,
     pub edit_form: ItemForm,
     pub show_delete_confirmation: bool,
     pub show_help: bool,
+    pub show_details: bool,
     pub status_message: Option<(String, Instant)>,
     pub filter_input_mode: bool,
     pub filter_input: String,
@@ -28,6 +49,10 @@ pub struct App {
     pub show_cap_warning: bool,
     pub cap_warning_message: String,
     pub pending_add_item: Option<Item>,
+    pub duplicate_prompt: Option<Duplicate>,
+    pub reader: Option<ReaderView>,
+    pub add_fetch: Option<JoinHandle<Result<Page, FetchError>>>,
+    pub snapshot_fetch: Option<(Item, JoinHandle<Result<FetchedSnapshot, FetchError>>)>,
     pub show_done_confirmation: bool,
     pub show_config_dialog: bool,
     pub config_max_items_input: String,
@@ -42,14 +67,23 @@ impl Default for App {
 
 impl App {
     pub fn new() -> Self {
+        Self::with_overrides(Vec::new())
+    }
+
+    pub fn with_overrides(config_overrides: Vec<(String, String)>) -> Self {
         Self {
             should_quit: false,
             state: AppState::new(),
+            config: ConfigManager::with_overrides(config_overrides.clone())
+                .map(|config_manager| config_manager.get().clone())
+                .unwrap_or_default(),
+            config_overrides,
             table_state: TableState::default(),
             add_form: ItemForm::new(FormType::Add),
             edit_form: ItemForm::new(FormType::Edit),
             show_delete_confirmation: false,
             show_help: false,
+            show_details: false,
             status_message: None,
             filter_input_mode: false,
             filter_input: String::new(),
@@ -57,6 +91,10 @@ impl App {
             show_cap_warning: false,
             cap_warning_message: String::new(),
             pending_add_item: None,
+            duplicate_prompt: None,
+            reader: None,
+            add_fetch: None,
+            snapshot_fetch: None,
             show_done_confirmation: false,
             show_config_dialog: false,
             config_max_items_input: String::new(),
@@ -72,21 +110,91 @@ impl App {
 
     pub async fn load_data(&mut self) -> TuiResult<()> {
         let inbox_items = load_items_from_file(get_inbox_path()?)?;
-        let archive_items = load_items_from_file(get_archive_path()?)?;
+        let archive_items = load_archive(self.state.archive_scope)?;
+        let someday_items = load_someday()?;
+
+        self.state.older_archive_available = self.state.archive_scope == ArchiveScope::Recent
+            && !list_archive_segments()?.is_empty();
 
         self.state.load_inbox_items(inbox_items);
         self.state.load_archive_items(archive_items);
+        self.state.load_someday_items(someday_items);
 
         Ok(())
     }
 
+    async fn load_older_archive(&mut self) {
+        if !self.state.older_archive_available {
+            self.show_status_message("Full archive already loaded".to_string());
+            return;
+        }
+
+        match load_archive(ArchiveScope::All) {
+            Ok(archive_items) => {
+                self.state.archive_scope = ArchiveScope::All;
+                self.state.older_archive_available = false;
+                self.state.load_archive_items(archive_items);
+                if self.state.current_view == View::Archive {
+                    self.state
+                        .reselect_visible_row(self.state.selected_table_row());
+                    self.table_state.select(self.state.selected_table_row());
+                }
+                self.show_status_message("Loaded older archive".to_string());
+            }
+            Err(_) => {
+                self.show_status_message("Failed to load older archive".to_string());
+            }
+        }
+    }
+
     pub async fn save_data(&mut self) -> TuiResult<()> {
         save_inbox(self.state.get_inbox_items())?;
-        save_archive(self.state.get_archive_items())?;
+        save_someday(self.state.get_someday_items())?;
+        save_archive_scoped(self.state.get_archive_items(), self.state.archive_scope)?;
         self.show_status_message("Saved".to_string());
         Ok(())
     }
 
+    async fn persist_selected_item(&mut self) -> TuiResult<()> {
+        let Some(selected_id) = self.state.selected_item_id else {
+            return Ok(());
+        };
+
+        let position = self
+            .state
+            .current_items()
+            .iter()
+            .position(|item| item.id() == selected_id);
+
+        match (position, &self.state.current_view) {
+            (Some(position), View::Inbox) => {
+                update_inbox_item(position, &self.state.inbox_items[position])?;
+            }
+            (Some(position), View::Someday) => {
+                update_someday_item(position, &self.state.someday_items[position])?;
+            }
+            (Some(position), View::Archive) => {
+                update_archive_item(
+                    self.state.archive_scope,
+                    position,
+                    &self.state.archive_items[position],
+                )?;
+            }
+            (None, _) => return self.save_data().await,
+        }
+
+        self.show_status_message("Saved".to_string());
+        Ok(())
+    }
+
+    async fn save_status_change(&mut self, result: &folio_core::StatusUpdateResult) {
+        if result.moved_to_inbox || !result.moved_to_archive.is_empty() {
+            let _ = self.save_data().await;
+        } else {
+            let _ = self.persist_selected_item().await;
+        }
+    }
+
     fn show_status_message(&mut self, message: String) {
         self.status_message = Some((message, Instant::now()));
     }
@@ -99,6 +207,21 @@ impl App {
             return;
         }
 
+        if let Some(reader) = &mut self.reader {
+            match key_event.code {
+                KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('R') => self.reader = None,
+                KeyCode::Char('j') | KeyCode::Down => reader.scroll += 1,
+                KeyCode::Char('k') | KeyCode::Up => reader.scroll = reader.scroll.saturating_sub(1),
+                KeyCode::PageDown | KeyCode::Char(' ') => reader.scroll += 20,
+                KeyCode::PageUp => reader.scroll = reader.scroll.saturating_sub(20),
+                KeyCode::Char('g') | KeyCode::Home => reader.scroll = 0,
+                // Clamped to the last screenful when drawn.
+                KeyCode::Char('G') | KeyCode::End => reader.scroll = usize::MAX,
+                _ => {}
+            }
+            return;
+        }
+
         if self.show_cap_warning {
             if let KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter = key_event.code {
                 self.show_cap_warning = false;
@@ -106,6 +229,33 @@ impl App {
             return;
         }
 
+        if let Some(duplicate) = self.duplicate_prompt.take() {
+            match key_event.code {
+                KeyCode::Char('o') => {
+                    let _ = opener::open(&duplicate.item.link);
+                    self.show_status_message("Opening the saved item...".to_string());
+                }
+                KeyCode::Char('r') => {
+                    let name = duplicate.item.name.clone();
+                    match self.requeue_duplicate(duplicate).await {
+                        Ok(()) => self.show_status_message(format!("Re-queued '{}'", name)),
+                        Err(_) => self.show_status_message("Failed to re-queue item".to_string()),
+                    }
+                }
+                KeyCode::Char('s') | KeyCode::Esc => {
+                    self.show_status_message("Already saved, nothing added".to_string());
+                }
+                _ => {
+                    self.duplicate_prompt = Some(duplicate);
+                    return;
+                }
+            }
+            if self.add_form.is_visible {
+                self.add_form.toggle_visibility();
+            }
+            return;
+        }
+
         if self.show_delete_confirmation {
             match key_event.code {
                 KeyCode::Char('y') | KeyCode::Char('Y') => {
@@ -123,7 +273,7 @@ impl App {
         if self.show_done_confirmation {
             match key_event.code {
                 KeyCode::Char('y') | KeyCode::Char('Y') => {
-                    match self.state.move_selected_to_done() {
+                    match self.state.move_selected_to_done(&self.config) {
                         Ok(maybe_item) => {
                             if maybe_item.is_some() {
                                 self.show_status_message("Item archived".to_string());
@@ -159,10 +309,12 @@ impl App {
                 KeyCode::Char('k') | KeyCode::Up => {
                     self.config_overflow_strategy = (self.config_overflow_strategy + 2) % 3;
                 }
-                KeyCode::Char(c) if c.is_ascii_digit() && key_event.code != KeyCode::Enter => {
-                    if self.config_max_items_input.len() < 4 {
-                        self.config_max_items_input.push(c);
-                    }
+                KeyCode::Char(c)
+                    if c.is_ascii_digit()
+                        && key_event.code != KeyCode::Enter
+                        && self.config_max_items_input.len() < 4 =>
+                {
+                    self.config_max_items_input.push(c);
                 }
                 KeyCode::Backspace => {
                     self.config_max_items_input.pop();
@@ -172,6 +324,17 @@ impl App {
             return;
         }
 
+        if self.add_form.is_visible && self.add_form.fetching {
+            if key_event.code == KeyCode::Esc {
+                if let Some(fetch) = self.add_fetch.take() {
+                    fetch.abort();
+                }
+                self.add_form.fetching = false;
+                self.show_status_message("Stopped fetching the page".to_string());
+            }
+            return;
+        }
+
         if self.add_form.is_visible {
             match key_event.code {
                 KeyCode::Esc => {
@@ -179,9 +342,7 @@ impl App {
                 }
                 KeyCode::Enter => {
                     if let Some(item_id) = self.submit_add_form().await {
-                        self.state.selected_item_id = Some(item_id);
-                        self.table_state.select(self.state.selected_table_row());
-                        self.add_form.toggle_visibility();
+                        self.show_added_item(item_id);
                     }
                 }
                 _ => {
@@ -302,9 +463,17 @@ impl App {
                 self.state.jump_to_last();
                 self.table_state.select(self.state.selected_table_row());
             }
-            KeyCode::Char('t') => match self.state.move_selected_to_todo() {
+            KeyCode::Char('t') | KeyCode::Char('i') | KeyCode::Char('d')
+                if self.state.current_view == View::Someday =>
+            {
+                self.show_status_message("Promote the item (p) to change its status".to_string());
+            }
+            KeyCode::Char('p') if self.state.current_view == View::Someday => {
+                self.promote_selected_item().await;
+            }
+            KeyCode::Char('t') => match self.state.move_selected_to_todo(&self.config) {
                 Ok(result) => {
-                    let _ = self.save_data().await;
+                    self.save_status_change(&result).await;
                     self.table_state.select(self.state.selected_table_row());
                     if result.moved_to_inbox && !result.overflow_items.is_empty() {
                         self.show_status_message(format!(
@@ -324,9 +493,9 @@ impl App {
                     self.show_status_message("Failed to update status".to_string());
                 }
             },
-            KeyCode::Char('i') => match self.state.move_selected_to_doing() {
+            KeyCode::Char('i') => match self.state.move_selected_to_doing(&self.config) {
                 Ok(result) => {
-                    let _ = self.save_data().await;
+                    self.save_status_change(&result).await;
                     self.table_state.select(self.state.selected_table_row());
                     if result.moved_to_inbox && !result.overflow_items.is_empty() {
                         self.show_status_message(format!(
@@ -346,11 +515,9 @@ impl App {
                     self.show_status_message("Failed to update status".to_string());
                 }
             },
-            KeyCode::Char('d') => {
-                if self.state.selected_item().is_some() {
-                    self.show_done_confirmation = true;
-                    self.show_status_message("Status set to Done".to_string());
-                }
+            KeyCode::Char('d') if self.state.selected_item().is_some() => {
+                self.show_done_confirmation = true;
+                self.show_status_message("Status set to Done".to_string());
             }
             KeyCode::Char('a') => {
                 self.check_cap_before_add();
@@ -365,34 +532,33 @@ impl App {
             KeyCode::Char('e') => {
                 self.start_edit_item();
             }
+            KeyCode::Char('v') => {
+                self.show_details = !self.show_details;
+            }
             KeyCode::Char('?') => {
                 self.show_help = true;
             }
             KeyCode::Char('C') => {
                 self.show_config_dialog = true;
-                if let Ok(config_manager) = ConfigManager::new() {
-                    let config = config_manager.get();
-                    self.config_max_items_input = config.max_items.to_string();
-                    self.config_overflow_strategy = match config.archive_on_overflow {
-                        OverflowStrategy::Abort => 0,
-                        OverflowStrategy::Todo => 1,
-                        OverflowStrategy::Any => 2,
-                    };
-                } else {
-                    self.config_max_items_input = "30".to_string();
-                    self.config_overflow_strategy = 0;
-                }
+                self.config_max_items_input = self.config.max_items.to_string();
+                self.config_overflow_strategy = match self.config.archive_on_overflow {
+                    OverflowStrategy::Abort => 0,
+                    OverflowStrategy::Todo => 1,
+                    OverflowStrategy::Any => 2,
+                };
             }
             KeyCode::Tab => {
-                match self.state.current_view {
-                    View::Inbox => self.state.current_view = View::Archive,
-                    View::Archive => self.state.current_view = View::Inbox,
-                }
+                self.state.current_view = match self.state.current_view {
+                    View::Inbox => View::Someday,
+                    View::Someday => View::Archive,
+                    View::Archive => View::Inbox,
+                };
                 self.state.selected_item_id = self.state.visible_items().first().map(|(id, _)| *id);
                 self.table_state.select(self.state.selected_table_row());
                 self.show_status_message(
                     match self.state.current_view {
                         View::Inbox => "Switched to Inbox",
+                        View::Someday => "Switched to Someday",
                         View::Archive => "Switched to Archive",
                     }
                     .to_string(),
@@ -401,6 +567,18 @@ impl App {
             KeyCode::Char('y') => {
                 self.copy_selected_link_to_clipboard();
             }
+            KeyCode::Char('L') => {
+                self.load_older_archive().await;
+            }
+            KeyCode::Char('R') => {
+                self.open_reader();
+            }
+            KeyCode::Char('W') => {
+                self.open_archived_copy();
+            }
+            KeyCode::Char('S') => {
+                self.snapshot_selected_item();
+            }
             KeyCode::Enter => {
                 if let Some(item) = self.state.selected_item()
                     && let Some(link_to_use) = Self::normalized_link(&item.link)
@@ -433,6 +611,7 @@ impl App {
             && !self.show_done_confirmation
             && !self.show_cap_warning
             && !self.show_config_dialog
+            && self.reader.is_none()
             && !self.show_help
         {
             match mouse_event.kind {
@@ -480,33 +659,52 @@ impl App {
     }
 
     fn check_cap_before_add(&mut self) {
-        match ConfigManager::new() {
-            Ok(config_manager) => {
-                let config = config_manager.get();
-                if self.state.inbox_items.len() >= config.max_items as usize {
-                    match config.archive_on_overflow {
-                        OverflowStrategy::Abort => {
-                            self.cap_warning_message = format!(
-                                "Inbox limit ({}) reached.\n\n\
-                                Choose an action:\n\
-                                • Delete an existing item (use 'x' key to delete)\n\
-                                • Archive an item (change status to 'done' or use 'A' key)\n\
-                                • Adjust inbox size: `folio config set max_items N`\n\
-                                • Change overflow strategy: `folio config set archive_on_overflow [todo|any]`",
-                                config.max_items
-                            );
-                            self.show_cap_warning = true;
-                        }
-                        OverflowStrategy::Todo | OverflowStrategy::Any => {
-                            self.add_form.toggle_visibility();
-                        }
-                    }
-                } else {
+        let config = &self.config;
+        if self.state.inbox_items.len() >= config.max_items as usize {
+            match config.archive_on_overflow {
+                OverflowStrategy::Abort => {
+                    self.cap_warning_message = format!(
+                        "Inbox limit ({}) reached.\n\n\
+                        Choose an action:\n\
+                        • Delete an existing item (use 'x' key to delete)\n\
+                        • Archive an item (change status to 'done' or use 'A' key)\n\
+                        • Adjust inbox size: `folio config set max_items N`\n\
+                        • Change overflow strategy: `folio config set archive_on_overflow [todo|any]`",
+                        config.max_items
+                    );
+                    self.show_cap_warning = true;
+                }
+                OverflowStrategy::Todo | OverflowStrategy::Any => {
                     self.add_form.toggle_visibility();
                 }
             }
+        } else {
+            self.add_form.toggle_visibility();
+        }
+    }
+
+    async fn promote_selected_item(&mut self) {
+        match self.state.promote_selected(&self.config) {
+            Ok(result) => {
+                let _ = self.save_data().await;
+                self.table_state.select(self.state.selected_table_row());
+                if result.overflow_items.is_empty() {
+                    self.show_status_message(format!(
+                        "Promoted '{}' to inbox",
+                        result.promoted.name
+                    ));
+                } else {
+                    self.show_status_message(format!(
+                        "Promoted to inbox. {} item(s) archived due to overflow",
+                        result.overflow_items.len()
+                    ));
+                }
+            }
+            Err(folio_core::CoreError::InboxFull) => {
+                self.show_status_message("Cannot promote: inbox limit reached".to_string());
+            }
             Err(_) => {
-                self.add_form.toggle_visibility();
+                self.show_status_message("Failed to promote item".to_string());
             }
         }
     }
@@ -521,48 +719,44 @@ impl App {
                 folio_core::Kind::Normal => item.kind = folio_core::Kind::Reference,
                 folio_core::Kind::Reference => item.kind = folio_core::Kind::Normal,
             }
-            let _ = self.save_data().await;
+            let _ = self.persist_selected_item().await;
         }
     }
 
     fn save_config_changes(&mut self) {
-        match self.config_max_items_input.parse::<u32>() {
-            Ok(max_items) => {
-                if max_items > 0 && max_items <= 1000 {
-                    let strategy = match self.config_overflow_strategy {
-                        0 => OverflowStrategy::Abort,
-                        1 => OverflowStrategy::Todo,
-                        2 => OverflowStrategy::Any,
-                        _ => OverflowStrategy::Abort,
-                    };
-
-                    match ConfigManager::new() {
-                        Ok(mut config_manager) => {
-                            match config_manager.update(|config| {
-                                config.max_items = max_items;
-                                config.archive_on_overflow = strategy;
-                            }) {
-                                Ok(_) => {
-                                    self.show_status_message(
-                                        "Config saved successfully".to_string(),
-                                    );
-                                    self.show_config_dialog = false;
-                                }
-                                Err(_) => {
-                                    self.show_status_message("Failed to save config".to_string());
-                                }
-                            }
-                        }
-                        Err(_) => {
-                            self.show_status_message("Failed to load config".to_string());
-                        }
-                    }
-                } else {
-                    self.show_status_message("Max items must be 1-1000".to_string());
-                }
+        let strategy = match self.config_overflow_strategy {
+            1 => OverflowStrategy::Todo,
+            2 => OverflowStrategy::Any,
+            _ => OverflowStrategy::Abort,
+        };
+
+        let mut config_manager = match ConfigManager::with_overrides(self.config_overrides.clone())
+        {
+            Ok(config_manager) => config_manager,
+            Err(_) => {
+                self.show_status_message("Failed to load config".to_string());
+                return;
+            }
+        };
+
+        let mut candidate = config_manager.file_config().clone();
+        let validated = candidate
+            .set_value("max_items", &self.config_max_items_input)
+            .and_then(|_| candidate.set_value("archive_on_overflow", strategy.as_string()));
+
+        if let Err(e) = validated {
+            self.show_status_message(e.to_string());
+            return;
+        }
+
+        match config_manager.update(|config| *config = candidate) {
+            Ok(_) => {
+                self.config = config_manager.get().clone();
+                self.show_status_message("Config saved successfully".to_string());
+                self.show_config_dialog = false;
             }
             Err(_) => {
-                self.show_status_message("Invalid number format".to_string());
+                self.show_status_message("Failed to save config".to_string());
             }
         }
     }
@@ -591,7 +785,25 @@ impl App {
                     .iter()
                     .position(|item| item.id() == selected_id)
                 {
-                    self.state.inbox_items.remove(pos);
+                    let removed = self.state.inbox_items.remove(pos);
+                    let _ = remove_snapshot(&removed);
+
+                    self.state.reselect_visible_row(preferred_row);
+                    self.table_state.select(self.state.selected_table_row());
+
+                    let _ = self.save_data().await;
+                    self.show_status_message("Item deleted".to_string());
+                }
+            }
+            View::Someday => {
+                if let Some(pos) = self
+                    .state
+                    .someday_items
+                    .iter()
+                    .position(|item| item.id() == selected_id)
+                {
+                    let removed = self.state.someday_items.remove(pos);
+                    let _ = remove_snapshot(&removed);
 
                     self.state.reselect_visible_row(preferred_row);
                     self.table_state.select(self.state.selected_table_row());
@@ -607,7 +819,8 @@ impl App {
                     .iter()
                     .position(|item| item.id() == selected_id)
                 {
-                    self.state.archive_items.remove(pos);
+                    let removed = self.state.archive_items.remove(pos);
+                    let _ = remove_snapshot(&removed);
 
                     self.state.reselect_visible_row(preferred_row);
                     self.table_state.select(self.state.selected_table_row());
@@ -619,16 +832,62 @@ impl App {
         }
     }
 
+    fn show_added_item(&mut self, item_id: usize) {
+        self.state.selected_item_id = Some(item_id);
+        self.table_state.select(self.state.selected_table_row());
+        self.add_form.toggle_visibility();
+    }
+
+    // An empty name is filled in from the page the link points to, and with auto_snapshot on
+    // the same download is kept as the item's snapshot. The page is fetched in the background
+    // and the form waits for it, see `poll_add_fetch`.
     async fn submit_add_form(&mut self) -> Option<usize> {
-        let name = self
+        let config = self.config.clone();
+        let name = self.add_form.get_field_value("name").cloned();
+        let link = self
             .add_form
-            .get_field_value("name")
+            .get_field_value("link")
             .cloned()
             .unwrap_or_default();
-        if name.is_empty() {
+
+        let needs_name = name.is_none_or(|name| name.trim().is_empty());
+        if (needs_name || config.auto_snapshot) && folio_core::is_supported_link(&link) {
+            self.add_fetch = Some(tokio::spawn(async move {
+                Self::fetch_page(&config, &link).await
+            }));
+            self.add_form.fetching = true;
             return None;
         }
 
+        self.finish_add_form(&config, None).await
+    }
+
+    async fn poll_add_fetch(&mut self) {
+        let Some(fetch) = self.add_fetch.take_if(|fetch| fetch.is_finished()) else {
+            return;
+        };
+        self.add_form.fetching = false;
+
+        let Ok(fetched) = fetch.await else {
+            return;
+        };
+        let config = self.config.clone();
+        if let Some(item_id) = self.finish_add_form(&config, Some(fetched)).await {
+            self.show_added_item(item_id);
+        }
+    }
+
+    async fn finish_add_form(
+        &mut self,
+        config: &Config,
+        fetched: Option<Result<Page, FetchError>>,
+    ) -> Option<usize> {
+        let name = self
+            .add_form
+            .get_field_value("name")
+            .cloned()
+            .unwrap_or_default();
+
         let item_type = self
             .add_form
             .get_field_value("type")
@@ -649,8 +908,62 @@ impl App {
             .get_field_value("note")
             .cloned()
             .unwrap_or_default();
+        let minutes = self
+            .add_form
+            .get_field_value("minutes")
+            .cloned()
+            .unwrap_or_default();
+
+        let mut name = name;
+        let mut author = author;
+        let mut page_type = None;
+        let mut duration_seconds = None;
+        let mut fetch_error = None;
+        let mut snapshot = None;
+        let needs_name = name.trim().is_empty();
+        if let Some(fetched) = fetched {
+            match fetched {
+                Ok(page) => {
+                    let metadata = parse_page_metadata(&page.html);
+                    duration_seconds = metadata.duration_seconds;
+                    if needs_name {
+                        name = metadata.title.unwrap_or_else(|| link.clone());
+                        if author.trim().is_empty() {
+                            author = metadata.author.unwrap_or_default();
+                        }
+                        page_type = metadata.item_type;
+                    }
+                    if config.auto_snapshot {
+                        snapshot = Some(Snapshot::new(page.url, extract_article(&page.html)))
+                            .filter(|snapshot| !snapshot.text.is_empty());
+                    }
+                }
+                Err(FetchError::Offline) => {}
+                Err(e) => fetch_error = Some(e.to_string()),
+            }
+        }
+        if needs_name && name.trim().is_empty() && folio_core::is_supported_link(&link) {
+            name = link.clone();
+        }
 
-        let new_item = match folio_core::create_item(
+        let item_type = if item_type == AUTO_TYPE {
+            TypeRules::from_config(config)
+                .infer(&link)
+                .or(page_type)
+                .unwrap_or(ItemType::BlogPost)
+                .as_string()
+                .to_string()
+        } else {
+            item_type
+        };
+
+        let mut errors = ValidationErrors::default();
+        let estimated_minutes = parse_minutes_field(&minutes).unwrap_or_else(|error| {
+            errors.errors.push(error);
+            None
+        });
+
+        let mut new_item = match folio_core::create_item(
             name,
             Some(item_type),
             Some(author),
@@ -658,73 +971,274 @@ impl App {
             Some(note),
             None,
         ) {
-            Ok(item) => item,
+            Ok(item) if errors.is_empty() => item,
+            Ok(_) => {
+                self.add_form.set_errors(&errors);
+                return None;
+            }
+            Err(CoreError::InvalidFields(found)) => {
+                errors.errors.extend(found.errors);
+                self.add_form.set_errors(&errors);
+                return None;
+            }
             Err(_) => return None,
         };
+        new_item.estimated_minutes = estimated_minutes.or_else(|| {
+            estimate_minutes(
+                &new_item.item_type,
+                duration_seconds,
+                snapshot.as_ref().map(Snapshot::word_count),
+            )
+        });
 
         let item_id = new_item.id();
+        let saved_item = new_item.clone();
 
-        match ConfigManager::new() {
-            Ok(config_manager) => {
-                let config = config_manager.get();
-                match folio_core::add_item_to_inbox(
-                    self.state.inbox_items.clone(),
-                    new_item,
-                    config,
-                ) {
-                    Ok((new_inbox, to_archive)) => {
-                        self.state.inbox_items = new_inbox;
-
-                        for item in &to_archive {
-                            self.state.add_item_to_archive(item.clone());
-                        }
+        // Older archive segments may not be loaded in the TUI, so the check reads all of them.
+        let archive = load_archive(ArchiveScope::All).unwrap_or_default();
 
-                        let _ = self.save_data().await;
+        match folio_core::add_item_to_inbox(
+            self.state.inbox_items.clone(),
+            new_item,
+            &self.state.someday_items,
+            &archive,
+            config,
+        ) {
+            Ok((new_inbox, to_archive)) => {
+                self.state.inbox_items = new_inbox;
 
-                        if !to_archive.is_empty() {
-                            self.show_status_message(format!(
-                                "Item added. {} item(s) archived due to overflow",
-                                to_archive.len()
-                            ));
-                        } else {
-                            self.show_status_message("Item added".to_string());
-                        }
+                for item in &to_archive {
+                    self.state.add_item_to_archive(item.clone());
+                }
 
-                        Some(item_id)
-                    }
-                    Err(_) => {
-                        self.cap_warning_message = format!(
-                            "Inbox limit ({}) reached.\n\n\
-                             Choose an action:\n\
-                             • Delete an existing item (use 'x' key to delete)\n\
-                             • Archive an item (change status to 'done' or use 'A' key)\n\
-                             • Adjust inbox size: `folio config set max_items N`\n\
-                             • Change overflow strategy: `folio config set archive_on_overflow [todo|any]`",
-                            config.max_items
-                        );
-                        self.show_cap_warning = true;
-                        None
-                    }
+                let _ = self.save_data().await;
+                if let Some(snapshot) = &snapshot {
+                    let _ = save_snapshot(&saved_item, snapshot);
                 }
+
+                if !to_archive.is_empty() {
+                    self.show_status_message(format!(
+                        "Item added. {} item(s) archived due to overflow",
+                        to_archive.len()
+                    ));
+                } else {
+                    self.show_status_message("Item added".to_string());
+                }
+                if let Some(error) = fetch_error {
+                    self.show_status_message(format!(
+                        "Item added without fetching the page: {}",
+                        error
+                    ));
+                }
+
+                Some(item_id)
+            }
+            Err(CoreError::Duplicate(duplicate)) => {
+                self.duplicate_prompt = Some(*duplicate);
+                None
             }
             Err(_) => {
-                self.state.inbox_items.push(new_item);
-                let _ = self.save_data().await;
-                self.show_status_message("Item added".to_string());
-                Some(item_id)
+                self.cap_warning_message = format!(
+                    "Inbox limit ({}) reached.\n\n\
+                     Choose an action:\n\
+                     • Delete an existing item (use 'x' key to delete)\n\
+                     • Archive an item (change status to 'done' or use 'A' key)\n\
+                     • Adjust inbox size: `folio config set max_items N`\n\
+                     • Change overflow strategy: `folio config set archive_on_overflow [todo|any]`",
+                    config.max_items
+                );
+                self.show_cap_warning = true;
+                None
             }
         }
     }
 
+    async fn fetch_page(config: &Config, link: &str) -> Result<Page, FetchError> {
+        Fetcher::from_config(config)?.fetch_page(link).await
+    }
+
+    fn open_archived_copy(&mut self) {
+        let Some(item) = self.state.selected_item() else {
+            return;
+        };
+        match item
+            .link_check
+            .as_ref()
+            .and_then(|check| check.archived_url.clone())
+        {
+            Some(archived_url) => {
+                let _ = opener::open(&archived_url);
+                self.show_status_message("Opening archived copy...".to_string());
+            }
+            None => self.show_status_message(
+                "No archived copy known, run `folio check-links --wayback`".to_string(),
+            ),
+        }
+    }
+
+    fn open_reader(&mut self) {
+        let Some(item) = self.state.selected_item() else {
+            return;
+        };
+        match load_snapshot(item) {
+            Ok(Some(snapshot)) => {
+                self.reader = Some(ReaderView {
+                    snapshot,
+                    scroll: 0,
+                })
+            }
+            Ok(None) => {
+                self.show_status_message("No snapshot yet, press S to save one".to_string())
+            }
+            Err(_) => self.show_status_message("Failed to read the snapshot".to_string()),
+        }
+    }
+
+    // The page is fetched and reduced to its text in the background; `poll_snapshot_fetch`
+    // saves the result once it's in.
+    fn snapshot_selected_item(&mut self) {
+        let Some(item) = self.state.selected_item().cloned() else {
+            return;
+        };
+        if !folio_core::is_supported_link(&item.link) {
+            self.show_status_message("This item has no link to take a snapshot of".to_string());
+            return;
+        }
+        if self.snapshot_fetch.is_some() {
+            self.show_status_message("Already saving a snapshot, one at a time".to_string());
+            return;
+        }
+
+        let config = self.config.clone();
+        let link = item.link.clone();
+        let fetch = tokio::spawn(async move {
+            let page = Self::fetch_page(&config, &link).await?;
+            Ok(FetchedSnapshot {
+                duration_seconds: parse_page_metadata(&page.html).duration_seconds,
+                snapshot: Snapshot::new(page.url, extract_article(&page.html)),
+            })
+        });
+
+        self.show_status_message(format!("Saving a snapshot of '{}'...", item.name));
+        self.snapshot_fetch = Some((item, fetch));
+    }
+
+    async fn poll_snapshot_fetch(&mut self) {
+        let Some((item, fetch)) = self
+            .snapshot_fetch
+            .take_if(|(_, fetch)| fetch.is_finished())
+        else {
+            return;
+        };
+
+        let fetched = match fetch.await {
+            Ok(Ok(fetched)) => fetched,
+            Ok(Err(e)) => {
+                self.show_status_message(format!("Snapshot failed: {}", e));
+                return;
+            }
+            Err(_) => return,
+        };
+        let snapshot = fetched.snapshot;
+        if snapshot.text.is_empty() {
+            self.show_status_message("No readable text found on the page".to_string());
+            return;
+        }
+        if save_snapshot(&item, &snapshot).is_err() {
+            self.show_status_message("Failed to save the snapshot".to_string());
+            return;
+        }
+
+        // An estimate set by hand, or from a published duration, is left alone. The selection
+        // may have moved on while the page was fetched, so the item is looked up again.
+        let estimate = estimate_minutes(
+            &item.item_type,
+            fetched.duration_seconds,
+            Some(snapshot.word_count()),
+        );
+        if let Some(minutes) = estimate
+            && item.estimated_minutes.is_none()
+        {
+            let _ = self.set_missing_estimate(item.id(), minutes);
+        }
+        self.show_status_message(format!(
+            "Snapshot of '{}' saved ({} words), press R to read",
+            item.name,
+            snapshot.word_count()
+        ));
+    }
+
+    fn set_missing_estimate(&mut self, item_id: usize, minutes: u32) -> TuiResult<()> {
+        let find = |items: &[Item]| items.iter().position(|item| item.id() == item_id);
+
+        if let Some(position) = find(&self.state.inbox_items) {
+            let item = &mut self.state.inbox_items[position];
+            item.estimated_minutes.get_or_insert(minutes);
+            update_inbox_item(position, item)?;
+        } else if let Some(position) = find(&self.state.someday_items) {
+            let item = &mut self.state.someday_items[position];
+            item.estimated_minutes.get_or_insert(minutes);
+            update_someday_item(position, item)?;
+        } else if let Some(position) = find(&self.state.archive_items) {
+            let item = &mut self.state.archive_items[position];
+            item.estimated_minutes.get_or_insert(minutes);
+            update_archive_item(self.state.archive_scope, position, item)?;
+        }
+        Ok(())
+    }
+
+    // An inbox item moves to the back of the queue; a someday item is promoted, and an archived
+    // one returns to the inbox as a todo, the same as `folio status <id> todo`.
+    async fn requeue_duplicate(&mut self, duplicate: Duplicate) -> TuiResult<()> {
+        let item_id = duplicate.item.id();
+
+        match duplicate.location {
+            DuplicateLocation::Inbox(index) => {
+                let inbox = std::mem::take(&mut self.state.inbox_items);
+                self.state.inbox_items = folio_core::requeue_inbox_item(inbox, index);
+                save_inbox(&self.state.inbox_items)?;
+            }
+            DuplicateLocation::Someday(index) => {
+                let result = folio_core::promote_from_someday(
+                    self.state.inbox_items.clone(),
+                    self.state.someday_items.clone(),
+                    index,
+                    &self.config,
+                )?;
+                save_inbox(&result.inbox_items)?;
+                for item in &result.overflow_items {
+                    append_to_archive(item)?;
+                }
+                save_someday(&result.someday_items)?;
+            }
+            DuplicateLocation::Archive(index) => {
+                let mut item = duplicate.item;
+                folio_core::change_item_status(&mut item, Status::Todo);
+
+                let (inbox, overflow_items) = folio_core::move_item_to_inbox(
+                    self.state.inbox_items.clone(),
+                    item,
+                    &self.config,
+                )?;
+                save_inbox(&inbox)?;
+                reopen_archive_item(ArchiveScope::All, index, &overflow_items)?;
+            }
+        }
+
+        self.load_data().await?;
+        if self.state.current_view == View::Inbox {
+            self.state.selected_item_id = Some(item_id);
+            self.table_state.select(self.state.selected_table_row());
+        }
+        Ok(())
+    }
+
     async fn submit_edit_form(&mut self) -> bool {
         let name = self
             .edit_form
             .get_field_value("name")
             .cloned()
             .unwrap_or_default();
-        if name.is_empty() {
-            return false;
-        }
 
         let item_type = self
             .edit_form
@@ -746,25 +1260,47 @@ impl App {
             .get_field_value("note")
             .cloned()
             .unwrap_or_default();
+        let minutes = self
+            .edit_form
+            .get_field_value("minutes")
+            .cloned()
+            .unwrap_or_default();
 
-        if let Some(item) = self.state.selected_item_mut() {
-            item.name = name;
-            item.item_type =
-                folio_core::ItemType::from_str(&item_type).unwrap_or(folio_core::ItemType::Other);
-            item.author = author;
-            item.link = link;
-            item.note = note;
+        let Some(selected) = self.state.selected_item_mut() else {
+            return false;
+        };
 
-            if item.validate().is_err() {
-                return false;
-            }
+        // Checked on a copy so a rejected edit leaves the item untouched.
+        let mut errors = ValidationErrors::default();
+        let mut item = selected.clone();
+        item.name = name;
+        match parse_item_type_field(&item_type) {
+            Ok(item_type) => item.item_type = item_type,
+            Err(error) => errors.errors.push(error),
+        }
+        item.author = author;
+        if item.link != link {
+            item.link = link;
+            item.link_check = None;
+        }
+        item.note = note;
+        match parse_minutes_field(&minutes) {
+            Ok(minutes) => item.estimated_minutes = minutes,
+            Err(error) => errors.errors.push(error),
+        }
+        errors
+            .errors
+            .extend(item.changed_field_errors(selected).errors);
 
-            let _ = self.save_data().await;
-            self.show_status_message("Item updated".to_string());
-            true
-        } else {
-            false
+        if !errors.is_empty() {
+            self.edit_form.set_errors(&errors);
+            return false;
         }
+
+        *selected = item;
+        let _ = self.persist_selected_item().await;
+        self.show_status_message("Item updated".to_string());
+        true
     }
 
     pub async fn run(&mut self) -> TuiResult<()> {
@@ -790,7 +1326,20 @@ impl App {
                     ])
                     .split(f.area());
 
-                ItemsTable::render(f, &self.state, chunks[0], &self.table_state);
+                if self.show_details {
+                    let panes = ratatui::layout::Layout::default()
+                        .direction(ratatui::layout::Direction::Horizontal)
+                        .constraints([
+                            ratatui::layout::Constraint::Percentage(60),
+                            ratatui::layout::Constraint::Percentage(40),
+                        ])
+                        .split(chunks[0]);
+
+                    ItemsTable::render(f, &self.state, panes[0], &self.table_state);
+                    ItemDetails::render(f, self.state.selected_item(), panes[1]);
+                } else {
+                    ItemsTable::render(f, &self.state, chunks[0], &self.table_state);
+                }
                 self.add_form.render(f);
                 self.edit_form.render(f);
 
@@ -814,6 +1363,15 @@ impl App {
                     Self::render_done_confirmation_dialog(f);
                 }
 
+                if let Some(duplicate) = &self.duplicate_prompt {
+                    Self::render_duplicate_dialog(f, duplicate, self.state.inbox_items.len());
+                }
+
+                if let Some(reader) = &mut self.reader {
+                    reader.scroll =
+                        SnapshotReader::render(f, &reader.snapshot, reader.scroll, chunks[0]);
+                }
+
                 if self.show_config_dialog {
                     Self::render_config_dialog(
                         f,
@@ -827,6 +1385,7 @@ impl App {
                     chunks[1],
                     &self.status_message,
                     &self.state.current_view,
+                    TimeTotal::of(&self.state.inbox_items),
                 );
             })?;
 
@@ -839,8 +1398,12 @@ impl App {
                         self.handle_mouse_event(mouse_event);
                     }
                     AppEvent::Tick => {
+                        self.poll_add_fetch().await;
+                        self.poll_snapshot_fetch().await;
+                        // "Saving a snapshot..." stays up until the snapshot is in.
                         if let Some((_, time)) = self.status_message
                             && time.elapsed() > Duration::from_secs(2)
+                            && self.snapshot_fetch.is_none()
                         {
                             self.status_message = None;
                         }
@@ -885,13 +1448,57 @@ impl App {
         frame.render_widget(paragraph, popup_area);
     }
 
+    fn render_duplicate_dialog(
+        frame: &mut ratatui::Frame,
+        duplicate: &Duplicate,
+        inbox_len: usize,
+    ) {
+        let area = frame.area();
+        let popup_area = ratatui::layout::Rect {
+            x: area.width.saturating_sub(60) / 2,
+            y: area.height.saturating_sub(7) / 2,
+            width: 60.min(area.width),
+            height: 7.min(area.height),
+        };
+
+        frame.render_widget(ratatui::widgets::Clear, popup_area);
+
+        let block = ratatui::widgets::Block::default()
+            .title("Already Saved")
+            .borders(ratatui::widgets::Borders::ALL);
+
+        let place = match duplicate.location {
+            DuplicateLocation::Inbox(_) => "inbox",
+            DuplicateLocation::Someday(_) => "someday list",
+            DuplicateLocation::Archive(_) => "archive",
+        };
+        let text = vec![
+            ratatui::text::Line::from(duplicate.item.name.clone()),
+            ratatui::text::Line::from(format!(
+                "#{} in the {}, {}",
+                duplicate.display_id(inbox_len),
+                place,
+                duplicate.item.status.as_string()
+            )),
+            ratatui::text::Line::from(""),
+            ratatui::text::Line::from("(O)pen / (R)e-queue / (S)kip"),
+        ];
+
+        let paragraph = ratatui::widgets::Paragraph::new(text)
+            .block(block)
+            .alignment(ratatui::layout::Alignment::Center)
+            .wrap(ratatui::widgets::Wrap { trim: true });
+
+        frame.render_widget(paragraph, popup_area);
+    }
+
     fn render_help_dialog(frame: &mut ratatui::Frame) {
         let area = frame.area();
         let popup_area = ratatui::layout::Rect {
             x: area.width / 2 - 30,
-            y: area.height / 2 - 14,
+            y: (area.height / 2).saturating_sub(17),
             width: 60.min(area.width),
-            height: 27.min(area.height),
+            height: 34.min(area.height),
         };
 
         frame.render_widget(ratatui::widgets::Clear, popup_area);
@@ -906,11 +1513,16 @@ impl App {
             ratatui::text::Line::from("  J/K               Move item down/up"),
             ratatui::text::Line::from("  PgUp/PgDn         Jump pages"),
             ratatui::text::Line::from("  Home/End          Jump to top/bottom"),
-            ratatui::text::Line::from("  Tab               Switch between Inbox/Archive"),
+            ratatui::text::Line::from("  Tab               Cycle Inbox/Someday/Archive"),
+            ratatui::text::Line::from("  L                 Load older archive years"),
             ratatui::text::Line::from(""),
             ratatui::text::Line::from("Item Actions:"),
             ratatui::text::Line::from("  Enter             Open link"),
             ratatui::text::Line::from("  y                 Copy link to clipboard"),
+            ratatui::text::Line::from("  S                 Save a snapshot for offline reading"),
+            ratatui::text::Line::from("  R                 Read the saved snapshot"),
+            ratatui::text::Line::from("  W                 Open archived copy of a dead link"),
+            ratatui::text::Line::from("  v                 Toggle detail pane"),
             ratatui::text::Line::from("  t                 Set status to todo"),
             ratatui::text::Line::from("  i                 Set status to in progress"),
             ratatui::text::Line::from("  d                 Set status to done"),
@@ -918,6 +1530,7 @@ impl App {
             ratatui::text::Line::from("  e                 Edit item"),
             ratatui::text::Line::from("  x                 Delete item"),
             ratatui::text::Line::from("  r                 Toggle reference (Archive only)"),
+            ratatui::text::Line::from("  p                 Promote to inbox (Someday only)"),
             ratatui::text::Line::from(""),
             ratatui::text::Line::from("System:"),
             ratatui::text::Line::from("  C                 Configure settings"),
@@ -1050,9 +1663,11 @@ impl App {
         area: ratatui::layout::Rect,
         status_message: &Option<(String, Instant)>,
         current_view: &View,
+        inbox_time: TimeTotal,
     ) {
         let view_text = match current_view {
             View::Inbox => " Inbox ",
+            View::Someday => " Someday ",
             View::Archive => " Archive ",
         };
 
@@ -1063,9 +1678,18 @@ impl App {
         };
 
         let status_text = if message_text.is_empty() {
-            format!("View: {} | Press ? for help", view_text)
+            format!(
+                "View: {} | Inbox time: {} | Press ? for help",
+                view_text,
+                inbox_time.describe()
+            )
         } else {
-            format!("{} | View: {} | Press ? for help", message_text, view_text)
+            format!(
+                "{} | View: {} | Inbox time: {} | Press ? for help",
+                message_text,
+                view_text,
+                inbox_time.describe()
+            )
         };
 
         let paragraph = ratatui::widgets::Paragraph::new(status_text)
@@ -25,6 +25,8 @@ impl EventHandler {
             async move {
                 let mut reader = EventStream::new();
                 while let Some(event) = reader.next().await {
+                    // Sending is a side effect, so it stays out of the match guards.
+                    #[allow(clippy::collapsible_match)]
                     match event {
                         Ok(Event::Key(key_event)) => {
                             if tx.send(AppEvent::Key(key_event)).is_err() {
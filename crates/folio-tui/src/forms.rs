@@ -6,12 +6,15 @@ use ratatui::{
 };
 use std::collections::HashMap;
 
+pub const AUTO_TYPE: &str = "auto";
+
 pub struct FormField {
     pub label: String,
     pub value: String,
     pub cursor_position: usize,
     pub is_focused: bool,
     pub field_type: FieldType,
+    pub error: Option<String>,
 }
 
 pub enum FieldType {
@@ -28,11 +31,15 @@ pub enum FormType {
     Edit,
 }
 
+const FIELD_ORDER: [&str; 6] = ["name", "type", "author", "link", "note", "minutes"];
+
 pub struct ItemForm {
     pub fields: HashMap<String, FormField>,
     pub focused_field: String,
     pub is_visible: bool,
     pub form_type: FormType,
+    // Set while the page behind the link is being fetched; the form waits for it.
+    pub fetching: bool,
 }
 
 impl ItemForm {
@@ -47,28 +54,36 @@ impl ItemForm {
                 cursor_position: 0,
                 is_focused: form_type == FormType::Add,
                 field_type: FieldType::Text,
+                error: None,
             },
         );
 
+        // New items default to guessing the type from the link when the form is submitted.
+        let mut type_options = vec![
+            "blog_post".to_string(),
+            "video".to_string(),
+            "podcast".to_string(),
+            "news".to_string(),
+            "thread".to_string(),
+            "academic_paper".to_string(),
+            "other".to_string(),
+        ];
+        if form_type == FormType::Add {
+            type_options.insert(0, AUTO_TYPE.to_string());
+        }
+
         fields.insert(
             "type".to_string(),
             FormField {
                 label: "Type".to_string(),
-                value: "blog_post".to_string(),
+                value: type_options[0].clone(),
                 cursor_position: 0,
                 is_focused: false,
                 field_type: FieldType::Dropdown {
-                    options: vec![
-                        "blog_post".to_string(),
-                        "video".to_string(),
-                        "podcast".to_string(),
-                        "news".to_string(),
-                        "thread".to_string(),
-                        "academic_paper".to_string(),
-                        "other".to_string(),
-                    ],
+                    options: type_options,
                     selected: 0,
                 },
+                error: None,
             },
         );
 
@@ -80,6 +95,7 @@ impl ItemForm {
                 cursor_position: 0,
                 is_focused: false,
                 field_type: FieldType::Text,
+                error: None,
             },
         );
 
@@ -91,6 +107,7 @@ impl ItemForm {
                 cursor_position: 0,
                 is_focused: false,
                 field_type: FieldType::Text,
+                error: None,
             },
         );
 
@@ -102,6 +119,19 @@ impl ItemForm {
                 cursor_position: 0,
                 is_focused: false,
                 field_type: FieldType::Text,
+                error: None,
+            },
+        );
+
+        fields.insert(
+            "minutes".to_string(),
+            FormField {
+                label: "Minutes".to_string(),
+                value: String::new(),
+                cursor_position: 0,
+                is_focused: false,
+                field_type: FieldType::Text,
+                error: None,
             },
         );
 
@@ -110,6 +140,7 @@ impl ItemForm {
             focused_field: "name".to_string(),
             is_visible: false,
             form_type,
+            fetching: false,
         }
     }
 
@@ -142,20 +173,62 @@ impl ItemForm {
             note_field.value = item.note.clone();
             note_field.cursor_position = note_field.value.len();
         }
+
+        if let Some(minutes_field) = self.fields.get_mut("minutes") {
+            minutes_field.value = item
+                .estimated_minutes
+                .map(|minutes| minutes.to_string())
+                .unwrap_or_default();
+            minutes_field.cursor_position = minutes_field.value.len();
+        }
+    }
+
+    // Marks the offending fields and moves focus to the first of them.
+    pub fn set_errors(&mut self, errors: &folio_core::ValidationErrors) {
+        self.clear_errors();
+        for error in &errors.errors {
+            if let Some(field) = self.fields.get_mut(error.field.as_string())
+                && field.error.is_none()
+            {
+                field.error = Some(error.message.clone());
+            }
+        }
+
+        let first = FIELD_ORDER
+            .iter()
+            .find(|name| self.fields.get(**name).is_some_and(|f| f.error.is_some()));
+        if let Some(first) = first {
+            for field in self.fields.values_mut() {
+                field.is_focused = false;
+            }
+            if let Some(field) = self.fields.get_mut(*first) {
+                field.is_focused = true;
+            }
+            self.focused_field = first.to_string();
+        }
+    }
+
+    pub fn clear_errors(&mut self) {
+        for field in self.fields.values_mut() {
+            field.error = None;
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.fields.values().any(|field| field.error.is_some())
     }
 
     pub fn toggle_visibility(&mut self) {
         self.is_visible = !self.is_visible;
+        self.fetching = false;
+        self.clear_errors();
         if self.is_visible {
             match self.form_type {
                 FormType::Add => {
                     for field in self.fields.values_mut() {
-                        if let FieldType::Dropdown { selected, .. } = &mut field.field_type {
+                        if let FieldType::Dropdown { options, selected } = &mut field.field_type {
                             *selected = 0;
-                            field.value = match field.label.as_str() {
-                                "Type" => "blog_post".to_string(),
-                                _ => String::new(),
-                            };
+                            field.value = options[0].clone();
                             field.cursor_position = 0;
                         } else {
                             field.value = String::new();
@@ -182,7 +255,7 @@ impl ItemForm {
     }
 
     pub fn focus_next(&mut self) {
-        let field_order = ["name", "type", "author", "link", "note"];
+        let field_order = FIELD_ORDER;
         let current_index = field_order
             .iter()
             .position(|&f| f == self.focused_field)
@@ -201,7 +274,7 @@ impl ItemForm {
     }
 
     pub fn focus_prev(&mut self) {
-        let field_order = ["name", "type", "author", "link", "note"];
+        let field_order = FIELD_ORDER;
         let current_index = field_order
             .iter()
             .position(|&f| f == self.focused_field)
@@ -224,6 +297,21 @@ impl ItemForm {
             return;
         }
 
+        // Changing a field's value clears its error until the form is submitted again.
+        let edits_value = match key.code {
+            crossterm::event::KeyCode::Char(_)
+            | crossterm::event::KeyCode::Backspace
+            | crossterm::event::KeyCode::Delete => true,
+            crossterm::event::KeyCode::Left | crossterm::event::KeyCode::Right => self
+                .fields
+                .get(&self.focused_field)
+                .is_some_and(|field| matches!(field.field_type, FieldType::Dropdown { .. })),
+            _ => false,
+        };
+        if edits_value && let Some(field) = self.fields.get_mut(&self.focused_field) {
+            field.error = None;
+        }
+
         match key.code {
             crossterm::event::KeyCode::Tab => {
                 self.focus_next();
@@ -354,6 +442,7 @@ impl ItemForm {
         frame.render_widget(Clear, popup_area);
 
         let title = match self.form_type {
+            FormType::Add if self.fetching => "Add Item (fetching…)",
             FormType::Add => "Add Item",
             FormType::Edit => "Edit Item",
         };
@@ -375,18 +464,22 @@ impl ItemForm {
                 Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Length(3),
+                Constraint::Length(3),
                 Constraint::Min(1),
             ])
             .split(popup_area);
 
-        let field_order = ["name", "type", "author", "link", "note"];
-
-        for (i, field_name) in field_order.iter().enumerate() {
+        for (i, field_name) in FIELD_ORDER.iter().enumerate() {
             if let Some(field) = self.fields.get(*field_name) {
                 let field_area = inner_area[i];
 
+                let label_color = if field.error.is_some() {
+                    Color::Red
+                } else {
+                    Color::Cyan
+                };
                 let label = Paragraph::new(format!("{}:", field.label))
-                    .style(Style::default().fg(Color::Cyan).bold())
+                    .style(Style::default().fg(label_color).bold())
                     .wrap(ratatui::widgets::Wrap { trim: true });
 
                 let value_style = if field.is_focused {
@@ -449,17 +542,41 @@ impl ItemForm {
                     .split(field_area);
 
                 frame.render_widget(label, field_layout[0]);
-                frame.render_widget(value_widget, field_layout[1]);
+
+                match &field.error {
+                    Some(error) => {
+                        let value_layout = Layout::default()
+                            .direction(Direction::Vertical)
+                            .constraints([Constraint::Min(1), Constraint::Length(1)])
+                            .split(field_layout[1]);
+                        let error_widget = Paragraph::new(format!("✗ {}", error))
+                            .style(Style::default().fg(Color::Red).bg(Color::Black));
+                        frame.render_widget(value_widget, value_layout[0]);
+                        frame.render_widget(error_widget, value_layout[1]);
+                    }
+                    None => frame.render_widget(value_widget, field_layout[1]),
+                }
             }
         }
 
-        let instructions_text = "Tab/Shift+Tab or ↑/↓: Navigate fields, ←/→: Navigate dropdown, Enter: Save, Esc: Cancel";
+        let instructions_text = match self.form_type {
+            FormType::Add => {
+                "Tab/Shift+Tab or ↑/↓: Navigate fields, ←/→: Navigate dropdown, Enter: Save (an empty name is fetched from the link), Esc: Cancel"
+            }
+            FormType::Edit => {
+                "Tab/Shift+Tab or ↑/↓: Navigate fields, ←/→: Navigate dropdown, Enter: Save, Esc: Cancel"
+            }
+        };
 
-        let instructions = Paragraph::new(instructions_text)
-            .style(Style::default().fg(Color::Gray).italic())
-            .wrap(Wrap { trim: true });
+        let instructions = if self.fetching {
+            Paragraph::new("Fetching the page… Esc: Stop fetching")
+                .style(Style::default().fg(Color::Yellow).italic())
+        } else {
+            Paragraph::new(instructions_text).style(Style::default().fg(Color::Gray).italic())
+        }
+        .wrap(Wrap { trim: true });
 
-        frame.render_widget(instructions, inner_area[6]);
+        frame.render_widget(instructions, inner_area[7]);
     }
 }
 
@@ -2,27 +2,28 @@ pub mod app;
 pub mod error;
 pub mod event;
 pub mod forms;
+pub mod picker;
 pub mod state;
 pub mod terminal;
 pub mod widgets;
 
 use crate::app::App;
 pub use error::{TuiError, TuiResult};
+pub use picker::{PickerSummary, run_staging_picker};
 
-pub async fn run_tui(add_form: bool) -> TuiResult<()> {
-    let mut app = if add_form {
-        App::new_with_add_form()
-    } else {
-        App::new()
-    };
+// `overrides` are the `--set key=value` pairs from the command line, applied on top of the
+// config file and environment the same way the CLI applies them.
+pub async fn run_tui(add_form: bool, overrides: Vec<(String, String)>) -> TuiResult<()> {
+    let mut app = App::with_overrides(overrides);
+    app.start_with_add_form = add_form;
 
     app.run().await
 }
 
-pub async fn run_tui_default() -> TuiResult<()> {
-    run_tui(false).await
+pub async fn run_tui_default(overrides: Vec<(String, String)>) -> TuiResult<()> {
+    run_tui(false, overrides).await
 }
 
-pub async fn run_tui_add_form() -> TuiResult<()> {
-    run_tui(true).await
+pub async fn run_tui_add_form(overrides: Vec<(String, String)>) -> TuiResult<()> {
+    run_tui(true, overrides).await
 }
@@ -5,8 +5,8 @@ async fn main() -> TuiResult<()> {
     let args: Vec<String> = std::env::args().collect();
 
     if args.len() > 1 && args[1] == "--add" {
-        folio_tui::run_tui_add_form().await
+        folio_tui::run_tui_add_form(Vec::new()).await
     } else {
-        folio_tui::run_tui_default().await
+        folio_tui::run_tui_default(Vec::new()).await
     }
 }
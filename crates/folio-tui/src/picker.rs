@@ -0,0 +1,239 @@
+use crate::error::TuiResult;
+use crate::event::{AppEvent, EventHandler};
+use crate::terminal::{restore_terminal, setup_terminal};
+use crossterm::event::{KeyCode, KeyEvent};
+use folio_core::{Config, Item, add_items_to_inbox, find_duplicate};
+use folio_storage::{
+    ArchiveScope, append_to_archive, get_inbox_path, load_archive, load_items_from_file,
+    load_someday, load_staging, save_inbox, save_staging,
+};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+};
+use std::time::Duration;
+
+#[derive(Debug, Default)]
+pub struct PickerSummary {
+    pub added: Vec<Item>,
+    pub archived: Vec<Item>,
+    pub duplicates: Vec<Item>,
+    pub rejected: usize,
+    pub discarded: usize,
+    pub remaining: usize,
+}
+
+pub struct StagingPicker {
+    items: Vec<Item>,
+    checked: Vec<bool>,
+    inbox_len: usize,
+    table_state: TableState,
+    discarded: usize,
+    accept: bool,
+    should_quit: bool,
+}
+
+impl StagingPicker {
+    pub fn new(items: Vec<Item>, inbox_len: usize) -> Self {
+        let mut table_state = TableState::default();
+        if !items.is_empty() {
+            table_state.select(Some(0));
+        }
+
+        Self {
+            checked: vec![true; items.len()],
+            items,
+            inbox_len,
+            table_state,
+            discarded: 0,
+            accept: false,
+            should_quit: false,
+        }
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        let Some(selected) = self.table_state.selected() else {
+            return;
+        };
+        let next = if down {
+            (selected + 1).min(self.items.len() - 1)
+        } else {
+            selected.saturating_sub(1)
+        };
+        self.table_state.select(Some(next));
+    }
+
+    fn discard_selected(&mut self) {
+        let Some(selected) = self.table_state.selected() else {
+            return;
+        };
+        self.items.remove(selected);
+        self.checked.remove(selected);
+        self.discarded += 1;
+
+        if self.items.is_empty() {
+            self.table_state.select(None);
+        } else {
+            self.table_state
+                .select(Some(selected.min(self.items.len() - 1)));
+        }
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => self.move_selection(true),
+            KeyCode::Char('k') | KeyCode::Up => self.move_selection(false),
+            KeyCode::Char(' ') => {
+                if let Some(selected) = self.table_state.selected() {
+                    self.checked[selected] = !self.checked[selected];
+                }
+            }
+            KeyCode::Char('a') => {
+                let all = self.checked.iter().all(|checked| *checked);
+                self.checked.iter_mut().for_each(|checked| *checked = !all);
+            }
+            KeyCode::Char('d') | KeyCode::Delete => self.discard_selected(),
+            KeyCode::Enter => {
+                self.accept = true;
+                self.should_quit = true;
+            }
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, config: &Config) {
+        let chunks = Layout::default()
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(frame.area());
+
+        let checked = self.checked.iter().filter(|checked| **checked).count();
+        let title = format!(
+            " Staged links: {} of {} selected, inbox {}/{} ",
+            checked,
+            self.items.len(),
+            self.inbox_len,
+            config.max_items
+        );
+
+        let rows = self.items.iter().zip(&self.checked).map(|(item, checked)| {
+            let (mark, style) = if *checked {
+                ("[x]", Style::default().fg(Color::White))
+            } else {
+                ("[ ]", Style::default().fg(Color::DarkGray))
+            };
+            Row::new(vec![
+                mark.to_string(),
+                item.name.clone(),
+                item.item_type.abbreviation().to_string(),
+                item.link.clone(),
+            ])
+            .style(style)
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(3),
+                Constraint::Percentage(45),
+                Constraint::Length(4),
+                Constraint::Percentage(55),
+            ],
+        )
+        .header(
+            Row::new(vec!["", "Name", "Type", "Link"])
+                .style(Style::default().fg(Color::White).bold()),
+        )
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow).bold()),
+        )
+        .row_highlight_style(Style::default().bg(Color::DarkGray));
+
+        frame.render_stateful_widget(table, chunks[0], &mut self.table_state);
+
+        let help = Paragraph::new(Line::from(
+            "Space: toggle | a: all/none | d: discard | Enter: add selected | q: keep staged",
+        ))
+        .style(Style::default().fg(Color::White).bg(Color::Black));
+        frame.render_widget(help, chunks[1]);
+    }
+
+    // Selected links go through the inbox cap as one batch; anything the cap rejects and
+    // anything left unselected stays staged for the next review. Selected links that are
+    // already saved leave staging and are returned for the caller to resolve.
+    fn finish(self, config: &Config) -> TuiResult<PickerSummary> {
+        let mut summary = PickerSummary {
+            discarded: self.discarded,
+            ..PickerSummary::default()
+        };
+
+        let accept = self.accept;
+        let (selected, staged): (Vec<_>, Vec<_>) = self
+            .items
+            .into_iter()
+            .zip(self.checked)
+            .partition(|(_, checked)| accept && *checked);
+        let selected: Vec<Item> = selected.into_iter().map(|(item, _)| item).collect();
+        let mut staged: Vec<Item> = staged.into_iter().map(|(item, _)| item).collect();
+
+        if !selected.is_empty() {
+            let inbox = load_items_from_file(get_inbox_path()?)?;
+            let someday = load_someday()?;
+            let archive = load_archive(ArchiveScope::All)?;
+            let (duplicates, selected): (Vec<_>, Vec<_>) = selected
+                .into_iter()
+                .partition(|item| find_duplicate(&inbox, &someday, &archive, item).is_some());
+            summary.duplicates = duplicates;
+
+            let outcome = add_items_to_inbox(inbox, selected, config)?;
+
+            if !outcome.added.is_empty() {
+                save_inbox(&outcome.inbox)?;
+                for item in &outcome.archived {
+                    append_to_archive(item)?;
+                }
+            }
+
+            summary.rejected = outcome.rejected.len();
+            staged.extend(outcome.rejected);
+            summary.added = outcome.added;
+            summary.archived = outcome.archived;
+        }
+
+        save_staging(&staged)?;
+        summary.remaining = staged.len();
+        Ok(summary)
+    }
+
+    pub async fn run(mut self, config: &Config) -> TuiResult<PickerSummary> {
+        let mut terminal = setup_terminal()?;
+        let mut events = EventHandler::new(Duration::from_millis(250));
+
+        while !self.should_quit && !self.items.is_empty() {
+            terminal.draw(|f| self.render(f, config))?;
+
+            if let Some(AppEvent::Key(key_event)) = events.next().await {
+                self.handle_key_event(key_event);
+            }
+        }
+
+        terminal.clear()?;
+        restore_terminal(&mut terminal)?;
+        self.finish(config)
+    }
+}
+
+pub async fn run_staging_picker(config: &Config) -> TuiResult<PickerSummary> {
+    let items = load_staging()?;
+    if items.is_empty() {
+        return Ok(PickerSummary::default());
+    }
+    let inbox_len = load_items_from_file(get_inbox_path()?)?.len();
+    StagingPicker::new(items, inbox_len).run(config).await
+}
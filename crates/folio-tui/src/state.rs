@@ -1,17 +1,22 @@
-use folio_core::{CoreError, Item, Status, StatusUpdateResult};
+use folio_core::{Config, CoreError, Item, PromoteResult, Status, StatusUpdateResult};
+use folio_storage::ArchiveScope;
 
 #[derive(PartialEq)]
 pub enum View {
     Inbox,
+    Someday,
     Archive,
 }
 
 pub struct AppState {
     pub inbox_items: Vec<Item>,
     pub archive_items: Vec<Item>,
+    pub someday_items: Vec<Item>,
     pub selected_item_id: Option<usize>,
     pub current_view: View,
     pub filter: Option<String>,
+    pub archive_scope: ArchiveScope,
+    pub older_archive_available: bool,
 }
 
 impl Default for AppState {
@@ -25,9 +30,12 @@ impl AppState {
         Self {
             inbox_items: vec![],
             archive_items: vec![],
+            someday_items: vec![],
             selected_item_id: None,
             current_view: View::Inbox,
             filter: None,
+            archive_scope: ArchiveScope::Recent,
+            older_archive_available: false,
         }
     }
 
@@ -40,9 +48,14 @@ impl AppState {
         self.archive_items = items;
     }
 
+    pub fn load_someday_items(&mut self, items: Vec<Item>) {
+        self.someday_items = items;
+    }
+
     pub fn current_items(&self) -> &Vec<Item> {
         match self.current_view {
             View::Inbox => &self.inbox_items,
+            View::Someday => &self.someday_items,
             View::Archive => &self.archive_items,
         }
     }
@@ -50,6 +63,7 @@ impl AppState {
     pub fn current_items_mut(&mut self) -> &mut Vec<Item> {
         match self.current_view {
             View::Inbox => &mut self.inbox_items,
+            View::Someday => &mut self.someday_items,
             View::Archive => &mut self.archive_items,
         }
     }
@@ -68,8 +82,8 @@ impl AppState {
             .find(|item| item.id() == selected_id)
     }
 
-    pub fn move_selected_to_done(&mut self) -> Result<Option<Item>, CoreError> {
-        let result = self.change_selected_status(Status::Done)?;
+    pub fn move_selected_to_done(&mut self, config: &Config) -> Result<Option<Item>, CoreError> {
+        let result = self.change_selected_status(Status::Done, config)?;
 
         if self.current_view == View::Inbox && !result.moved_to_archive.is_empty() {
             Ok(result.moved_to_archive.into_iter().next())
@@ -78,12 +92,18 @@ impl AppState {
         }
     }
 
-    pub fn move_selected_to_doing(&mut self) -> Result<StatusUpdateResult, CoreError> {
-        self.change_selected_status(Status::Doing)
+    pub fn move_selected_to_doing(
+        &mut self,
+        config: &Config,
+    ) -> Result<StatusUpdateResult, CoreError> {
+        self.change_selected_status(Status::Doing, config)
     }
 
-    pub fn move_selected_to_todo(&mut self) -> Result<StatusUpdateResult, CoreError> {
-        self.change_selected_status(Status::Todo)
+    pub fn move_selected_to_todo(
+        &mut self,
+        config: &Config,
+    ) -> Result<StatusUpdateResult, CoreError> {
+        self.change_selected_status(Status::Todo, config)
     }
 
     pub fn next_item(&mut self) {
@@ -236,6 +256,36 @@ impl AppState {
         &self.archive_items
     }
 
+    pub fn get_someday_items(&self) -> &[Item] {
+        &self.someday_items
+    }
+
+    pub fn promote_selected(&mut self, config: &Config) -> Result<PromoteResult, CoreError> {
+        let selected_id = self.selected_item_id.ok_or(CoreError::ItemNotFound)?;
+        let previous_row = self.selected_table_row();
+
+        let position = self
+            .someday_items
+            .iter()
+            .position(|item| item.id() == selected_id)
+            .ok_or(CoreError::ItemNotFound)?;
+
+        let result = folio_core::promote_from_someday(
+            self.inbox_items.clone(),
+            self.someday_items.clone(),
+            position,
+            config,
+        )?;
+
+        self.inbox_items = result.inbox_items.clone();
+        self.someday_items = result.someday_items.clone();
+        self.archive_items
+            .extend(result.overflow_items.iter().cloned());
+        self.reselect_visible_row(previous_row);
+
+        Ok(result)
+    }
+
     pub fn set_filter(&mut self, filter: Option<String>) {
         self.filter = filter;
 
@@ -297,6 +347,7 @@ impl AppState {
     fn change_selected_status(
         &mut self,
         new_status: Status,
+        config: &Config,
     ) -> Result<StatusUpdateResult, CoreError> {
         let selected_id = self.selected_item_id.ok_or(CoreError::ItemNotFound)?;
         let previous_row = self.selected_table_row();
@@ -317,10 +368,6 @@ impl AppState {
             return Err(CoreError::ItemNotFound);
         };
 
-        let config_manager = folio_storage::ConfigManager::new()
-            .map_err(|_| CoreError::ValidationError("Failed to load config".to_string()))?;
-        let config = config_manager.get();
-
         let result = folio_core::update_item_status(
             item_id,
             new_status,
@@ -1,8 +1,11 @@
+use folio_core::{Item, LinkHealth, Snapshot, format_minutes};
+use folio_storage::has_snapshot;
 use ratatui::{
     Frame,
     layout::{Constraint, Rect},
     style::{Color, Style, Stylize},
-    widgets::{Block, Borders, Row, Table, TableState},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
 };
 
 use crate::state::{AppState, View};
@@ -10,6 +13,28 @@ use crate::state::{AppState, View};
 pub struct ItemsTable;
 
 impl ItemsTable {
+    // Links that failed their last check are flagged in front of the link text: red for dead
+    // ones (404/410, DNS failures), yellow for errors that may be temporary.
+    fn link_cell(item: &Item, max_len: usize) -> Cell<'static> {
+        let link = if item.link.chars().count() > max_len {
+            let cut: String = item.link.chars().take(max_len - 3).collect();
+            format!("{}...", cut)
+        } else {
+            item.link.clone()
+        };
+        match &item.link_check {
+            Some(check) if check.is_dead() => {
+                Cell::from(format!("✗ {} {}", check.short_label(), link))
+                    .style(Style::default().fg(Color::Red).bold())
+            }
+            Some(check) if check.health != LinkHealth::Ok => {
+                Cell::from(format!("! {} {}", check.short_label(), link))
+                    .style(Style::default().fg(Color::Yellow))
+            }
+            _ => Cell::from(link),
+        }
+    }
+
     pub fn render(frame: &mut Frame, app_state: &AppState, area: Rect, table_state: &TableState) {
         let (header, title, border_style) = match app_state.current_view {
             View::Inbox => {
@@ -25,16 +50,36 @@ impl ItemsTable {
                     Style::default().fg(Color::Green).bold(),
                 )
             }
-            View::Archive => {
+            View::Someday => {
                 let title = if let Some(filter) = &app_state.filter {
-                    format!(" Archive (filtered: {}) ", filter)
+                    format!(" Someday (filtered: {}) ", filter)
                 } else {
-                    " Archive ".to_string()
+                    format!(" Someday ({}) ", app_state.someday_items.len())
                 };
                 (
-                    Row::new(vec!["ID", "R", "Name", "Author", "Done On", "Type", "Note", "Link"])
+                    Row::new(vec!["ID", "Name", "Type", "Added", "Author", "Link"])
                         .style(Style::default().fg(Color::White).bold()),
                     title,
+                    Style::default().fg(Color::Magenta).bold(),
+                )
+            }
+            View::Archive => {
+                let scope_hint = if app_state.older_archive_available {
+                    " [recent, L: load older]"
+                } else {
+                    ""
+                };
+                let title = if let Some(filter) = &app_state.filter {
+                    format!(" Archive{} (filtered: {}) ", scope_hint, filter)
+                } else {
+                    format!(" Archive{} ", scope_hint)
+                };
+                (
+                    Row::new(vec![
+                        "ID", "R", "Name", "Author", "Done On", "Type", "Note", "Link",
+                    ])
+                    .style(Style::default().fg(Color::White).bold()),
+                    title,
                     Style::default().fg(Color::Blue).bold(),
                 )
             }
@@ -60,23 +105,32 @@ impl ItemsTable {
 
                         let added_date = item.added_at.format("%Y-%m-%d").to_string();
 
-                        let link_display = if item.link.len() > 25 {
-                            format!("{}...", &item.link[..22])
-                        } else {
-                            item.link.clone()
-                        };
-
                         Row::new(vec![
-                            (display_index + 1).to_string(),
-                            status_char.to_string(),
-                            item.name.clone(),
-                            item_type.to_string(),
-                            added_date,
-                            item.author.clone(),
-                            link_display,
+                            Cell::from((display_index + 1).to_string()),
+                            Cell::from(status_char),
+                            Cell::from(item.name.clone()),
+                            Cell::from(item_type),
+                            Cell::from(added_date),
+                            Cell::from(item.author.clone()),
+                            Self::link_cell(item, 25),
                         ])
                         .style(status_style)
                     }
+                    View::Someday => {
+                        let item_type = item.item_type.abbreviation();
+
+                        let added_date = item.added_at.format("%Y-%m-%d").to_string();
+
+                        Row::new(vec![
+                            Cell::from((display_index + 1).to_string()),
+                            Cell::from(item.name.clone()),
+                            Cell::from(item_type),
+                            Cell::from(added_date),
+                            Cell::from(item.author.clone()),
+                            Self::link_cell(item, 25),
+                        ])
+                        .style(Style::default().fg(Color::Gray))
+                    }
                     View::Archive => {
                         let reference_char = item.kind.display_emoji();
 
@@ -99,21 +153,15 @@ impl ItemsTable {
                             item.note.clone()
                         };
 
-                        let link_display = if item.link.len() > 20 {
-                            format!("{}...", &item.link[..17])
-                        } else {
-                            item.link.clone()
-                        };
-
                         Row::new(vec![
-                            (display_index + 1).to_string(),
-                            reference_char.to_string(),
-                            item.name.clone(),
-                            item.author.clone(),
-                            done_date,
-                            item_type.to_string(),
-                            note,
-                            link_display,
+                            Cell::from((display_index + 1).to_string()),
+                            Cell::from(reference_char),
+                            Cell::from(item.name.clone()),
+                            Cell::from(item.author.clone()),
+                            Cell::from(done_date),
+                            Cell::from(item_type),
+                            Cell::from(note),
+                            Self::link_cell(item, 20),
                         ])
                         .style(reference_style)
                     }
@@ -130,6 +178,14 @@ impl ItemsTable {
                 Constraint::Percentage(20),
                 Constraint::Percentage(25),
             ],
+            View::Someday => vec![
+                Constraint::Length(3),
+                Constraint::Percentage(35),
+                Constraint::Length(5),
+                Constraint::Length(10),
+                Constraint::Percentage(20),
+                Constraint::Percentage(25),
+            ],
             View::Archive => vec![
                 Constraint::Length(3),
                 Constraint::Length(2),
@@ -156,3 +212,243 @@ impl ItemsTable {
         frame.render_stateful_widget(table, area, &mut table_state.clone());
     }
 }
+
+pub struct ItemDetails;
+
+impl ItemDetails {
+    fn field<'a>(label: &'a str, value: String) -> Line<'a> {
+        Line::from(vec![
+            Span::styled(
+                format!("{:<10}", label),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::raw(value),
+        ])
+    }
+
+    pub fn render(frame: &mut Frame, item: Option<&Item>, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Details ")
+            .border_style(Style::default().fg(Color::Cyan));
+
+        let Some(item) = item else {
+            frame.render_widget(Paragraph::new("No item selected").block(block), area);
+            return;
+        };
+
+        let date = |time: Option<chrono::DateTime<chrono::Utc>>| {
+            time.map(|time| time.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| "–".to_string())
+        };
+
+        let mut lines = vec![
+            Line::from(Span::styled(item.name.clone(), Style::default().bold())),
+            Line::from(""),
+            Self::field("Type", item.item_type.as_string().to_string()),
+            Self::field("Status", item.status.as_string().to_string()),
+        ];
+        if !item.author.is_empty() {
+            lines.push(Self::field("Author", item.author.clone()));
+        }
+        if !item.link.is_empty() {
+            lines.push(Self::field("Link", item.link.clone()));
+        }
+        if let Some(check) = &item.link_check {
+            let style = match check.health {
+                LinkHealth::Ok => Style::default(),
+                _ if check.is_dead() => Style::default().fg(Color::Red),
+                _ => Style::default().fg(Color::Yellow),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:<10}", "Checked"),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!("{} on {}", check.describe(), date(Some(check.checked_at))),
+                    style,
+                ),
+            ]));
+            if let Some(archived_url) = &check.archived_url {
+                lines.push(Self::field(
+                    "Archived",
+                    format!("{} (W to open)", archived_url),
+                ));
+            }
+        }
+        lines.push(Self::field("Added", date(Some(item.added_at))));
+        if item.finished_at.is_some() {
+            lines.push(Self::field("Finished", date(item.finished_at)));
+        }
+        if !item.tags.is_empty() {
+            lines.push(Self::field("Tags", item.tags.join(", ")));
+        }
+        if let Some(minutes) = item.estimated_minutes {
+            lines.push(Self::field("Time", format_minutes(minutes)));
+        }
+        if has_snapshot(item) {
+            lines.push(Self::field("Snapshot", "saved (R to read)".to_string()));
+        }
+        if let Some(bib) = &item.bib {
+            if let Some(venue) = &bib.venue {
+                lines.push(Self::field("Venue", venue.clone()));
+            }
+            if let Some(year) = bib.year {
+                lines.push(Self::field("Year", year.to_string()));
+            }
+            if let Some(doi) = &bib.doi {
+                lines.push(Self::field("DOI", doi.clone()));
+            }
+            if let Some(arxiv_id) = &bib.arxiv_id {
+                lines.push(Self::field("arXiv", arxiv_id.clone()));
+            }
+        }
+
+        if !item.note.is_empty() {
+            lines.push(Line::from(""));
+            lines.extend(item.note.lines().map(|line| Line::from(line.to_string())));
+        }
+
+        if !item.highlights.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("Highlights ({})", item.highlights.len()),
+                Style::default().fg(Color::Yellow).bold(),
+            )));
+            for highlight in &item.highlights {
+                lines.push(Line::from(""));
+                if !highlight.text.is_empty() {
+                    lines.push(Line::from(Span::styled(
+                        format!("“{}”", highlight.text),
+                        Style::default().italic(),
+                    )));
+                }
+                if !highlight.note.is_empty() {
+                    lines.push(Line::from(format!("Note: {}", highlight.note)));
+                }
+                if let Some(location) = &highlight.location {
+                    lines.push(Line::from(Span::styled(
+                        location.clone(),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+            }
+        }
+
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, area);
+    }
+}
+
+pub struct SnapshotReader;
+
+impl SnapshotReader {
+    // Greedy word wrap, so the reader knows how many rows the text takes and where it ends.
+    fn wrap(text: &str, width: usize) -> Vec<String> {
+        let mut rows = Vec::new();
+        let mut row = String::new();
+        for word in text.split(' ') {
+            let row_len = row.chars().count();
+            if row_len > 0 && row_len + 1 + word.chars().count() > width {
+                rows.push(std::mem::take(&mut row));
+            }
+            if !row.is_empty() {
+                row.push(' ');
+            }
+            row.push_str(word);
+        }
+        rows.push(row);
+        rows
+    }
+
+    fn lines(snapshot: &Snapshot, width: usize) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        let muted = Style::default().fg(Color::DarkGray);
+
+        if let Some(title) = &snapshot.title {
+            for row in Self::wrap(title, width) {
+                lines.push(Line::from(Span::styled(row, Style::default().bold())));
+            }
+        }
+        if let Some(byline) = &snapshot.byline {
+            lines.push(Line::from(Span::styled(format!("by {}", byline), muted)));
+        }
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{} · saved {} · {} words",
+                snapshot.url,
+                snapshot.fetched_at.format("%Y-%m-%d"),
+                snapshot.word_count()
+            ),
+            muted,
+        )));
+        lines.push(Line::from(""));
+
+        for text_line in snapshot.text.lines() {
+            let (style, indent) = if text_line.starts_with('#') {
+                (Style::default().fg(Color::Yellow).bold(), "")
+            } else if text_line.starts_with("> ") {
+                (Style::default().fg(Color::Gray).italic(), "> ")
+            } else if text_line.starts_with("    ") {
+                (Style::default().fg(Color::Green), "    ")
+            } else if text_line.starts_with("- ") {
+                (Style::default(), "  ")
+            } else {
+                (Style::default(), "")
+            };
+
+            // Code keeps its layout; everything else wraps, continuing under the marker.
+            if indent == "    " {
+                lines.push(Line::from(Span::styled(text_line.to_string(), style)));
+                continue;
+            }
+            let rows = Self::wrap(text_line, width.saturating_sub(indent.len()).max(10));
+            for (index, row) in rows.into_iter().enumerate() {
+                let row = if index == 0 {
+                    row
+                } else {
+                    format!("{}{}", indent, row)
+                };
+                lines.push(Line::from(Span::styled(row, style)));
+            }
+        }
+
+        lines
+    }
+
+    // Renders from `scroll` and returns it clamped to the last screenful.
+    pub fn render(frame: &mut Frame, snapshot: &Snapshot, scroll: usize, area: Rect) -> usize {
+        let width = (area.width.saturating_sub(4) as usize).clamp(20, 100);
+        let height = area.height.saturating_sub(2) as usize;
+        let lines = Self::lines(snapshot, width);
+        let scroll = scroll.min(lines.len().saturating_sub(height));
+
+        let progress = if lines.len() <= height {
+            100
+        } else {
+            (scroll + height) * 100 / lines.len()
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Reader ")
+            .title_bottom(format!(
+                " j/k scroll · PgUp/PgDn page · g/G top/end · q close · {}% ",
+                progress
+            ))
+            .border_style(Style::default().fg(Color::Cyan));
+
+        let visible: Vec<Line> = lines.into_iter().skip(scroll).take(height).collect();
+        let text_area = Rect {
+            x: area.x + area.width.saturating_sub(width as u16 + 2) / 2,
+            width: (width as u16 + 2).min(area.width),
+            ..area
+        };
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(visible).block(block), text_area);
+        scroll
+    }
+}